@@ -1269,6 +1269,16 @@ impl Builder {
         })
     }
 
+    /// An inclusive range pattern, `lo..=hi`
+    pub fn range_pat(self, lo: Box<Expr>, hi: Box<Expr>) -> Pat {
+        Pat::Range(PatRange {
+            attrs: self.attrs,
+            lo,
+            limits: RangeLimits::Closed(Token![..=](self.span)),
+            hi,
+        })
+    }
+
     pub fn mac_pat(self, mac: Macro) -> Pat {
         Pat::Macro(PatMacro {
             attrs: self.attrs,
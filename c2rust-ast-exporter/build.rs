@@ -1,9 +1,11 @@
-use c2rust_build_paths::find_llvm_config;
+use c2rust_build_paths::{find_llvm_config, parse_link_libs, rustlib_dylib_path};
 use cmake::Config;
 use std::env;
 use std::ffi::OsStr;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{self, Command};
+use std::time::UNIX_EPOCH;
 
 // Use `cargo build -vv` to get detailed output on this script's progress.
 
@@ -62,6 +64,10 @@ binary.",
 }
 
 fn generate_bindings() -> Result<(), &'static str> {
+    // Regenerate the bindings whenever the headers they're generated from change
+    println!("cargo:rerun-if-changed=src/ast_tags.hpp");
+    println!("cargo:rerun-if-changed=src/ExportResult.hpp");
+
     // The bindgen::Builder is the main entry point
     // to bindgen, and lets you build up options for
     // the resulting bindings.
@@ -124,6 +130,16 @@ fn build_native(llvm_info: &LLVMInfo) {
             println!("cargo:rustc-link-search=native={}", libdir);
         }
         _ => {
+            // Emitting any rerun-if-changed disables cargo's default of rerunning
+            // on every package change, so the exporter sources need listing too
+            println!("cargo:rerun-if-changed=src");
+
+            // CMake's own error for a missing C++ compiler is hard to decipher
+            if let Err(e) = check_cxx_compiler() {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+
             // Build libclangAstExporter.a with cmake
             let dst = Config::new("src")
                 // Where to find LLVM/Clang CMake files
@@ -192,6 +208,17 @@ fn build_native(llvm_info: &LLVMInfo) {
             clang_libs.insert(sema_pos + 1, "clangSupport");
         }
 
+        // A lib split or merged by another LLVM version otherwise only shows
+        // up as a wall of undefined symbols when linking
+        for lib in &clang_libs {
+            if !lib_exists(Path::new(llvm_lib_dir), lib) {
+                println!(
+                    "cargo:warning=Clang library `{}` not found in {}, linking will likely fail",
+                    lib, llvm_lib_dir
+                );
+            }
+        }
+
         for lib in &clang_libs {
             println!("cargo:rustc-link-lib={}", lib);
         }
@@ -212,6 +239,68 @@ fn build_native(llvm_info: &LLVMInfo) {
     }
 }
 
+/// Whether `dir` contains a static or shared library the linker would
+/// find for `-l<name>`
+fn lib_exists(dir: &Path, name: &str) -> bool {
+    let file_names = [
+        format!("lib{}.a", name),
+        format!("lib{}.so", name),
+        format!("lib{}.dylib", name),
+        format!("{}.lib", name),
+    ];
+
+    file_names
+        .iter()
+        .any(|file_name| dir.join(file_name).exists())
+}
+
+/// Checks that a C++ compiler is available for CMake to build the exporter
+/// with, preferring the one named by `CXX` like CMake itself does
+fn check_cxx_compiler() -> Result<(), String> {
+    println!("cargo:rerun-if-env-changed=CXX");
+
+    let cxx = env::var("CXX").ok();
+    let mut candidates: Vec<&str> = match cxx.as_deref() {
+        // `CXX` may carry flags after the compiler itself
+        Some(cxx) => cxx.split_whitespace().take(1).collect(),
+        None => vec!["c++", "clang++", "g++"],
+    };
+    if cfg!(windows) && cxx.is_none() {
+        candidates.push("cl.exe");
+    }
+
+    // Only whether the compiler can be spawned matters here; `cl.exe`
+    // doesn't understand `--version` and exits unsuccessfully
+    let found = candidates
+        .iter()
+        .any(|cxx| Command::new(cxx).arg("--version").output().is_ok());
+
+    if found {
+        return Ok(());
+    }
+
+    Err(format!(
+        "
+Couldn't find a C++ compiler, which is required to build the AST exporter.
+Tried: {tried}. Please install a C++11 capable compiler, for example:
+
+  $ sudo apt install clang          # Debian/Ubuntu
+  $ xcode-select --install          # macOS
+
+install the Visual Studio C++ build tools on Windows, or set the `CXX` environment variable to the path of one, then re-build.",
+        tried = candidates.join(", "),
+    ))
+}
+
+/// Environment variables which affect what `LLVMInfo` detects
+const LLVM_INFO_ENV_VARS: &[&str] = &[
+    "LLVM_CONFIG_PATH",
+    "LLVM_LIB_DIR",
+    "LLVM_SYSTEM_LIBS",
+    "RUSTC",
+    "TARGET",
+];
+
 /// Holds information about LLVM paths we have found
 struct LLVMInfo {
     /// LLVM lib dir containing libclang* and libLLVM* libraries
@@ -225,7 +314,106 @@ struct LLVMInfo {
 }
 
 impl LLVMInfo {
+    /// Detects LLVM via `llvm-config`, reusing the result cached in `OUT_DIR`
+    /// by a previous run of this build script when `llvm-config` and the
+    /// environment it was detected in are unchanged
     fn new() -> Self {
+        for var in LLVM_INFO_ENV_VARS {
+            println!("cargo:rerun-if-env-changed={}", var);
+        }
+
+        let llvm_config = find_llvm_config();
+        let cache_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("llvm-info.cache");
+        let cache_key = llvm_config.as_deref().and_then(Self::cache_key);
+
+        if let Some(cache_key) = &cache_key {
+            if let Some(llvm_info) = Self::read_cache(&cache_path, cache_key) {
+                return llvm_info;
+            }
+        }
+
+        let llvm_info = Self::detect(llvm_config.as_deref());
+
+        if let Some(cache_key) = &cache_key {
+            llvm_info.write_cache(&cache_path, cache_key);
+        }
+
+        llvm_info
+    }
+
+    /// Describes the `llvm-config` in use by its resolved path and mtime,
+    /// along with the environment variables which affect detection
+    fn cache_key(llvm_config: &Path) -> Option<String> {
+        // Bare command names found by `find_llvm_config` are resolved via $PATH
+        let llvm_config = if llvm_config.components().count() > 1 {
+            llvm_config.to_owned()
+        } else {
+            let file_name = format!("{}{}", llvm_config.display(), env::consts::EXE_SUFFIX);
+
+            env::split_paths(&env::var_os("PATH")?)
+                .map(|dir| dir.join(&file_name))
+                .find(|path| path.is_file())?
+        };
+        let mtime = fs::metadata(&llvm_config)
+            .and_then(|metadata| metadata.modified())
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?;
+
+        let mut key = format!(
+            "llvm_config={}\nmtime={}.{:09}\nllvm_static={}\n",
+            llvm_config.display(),
+            mtime.as_secs(),
+            mtime.subsec_nanos(),
+            cfg!(feature = "llvm-static"),
+        );
+        for var in LLVM_INFO_ENV_VARS {
+            let value = env::var(var).unwrap_or_default();
+            key.push_str(&format!("{}={}\n", var, value));
+        }
+
+        Some(key)
+    }
+
+    fn read_cache(cache_path: &Path, cache_key: &str) -> Option<Self> {
+        let cache = fs::read_to_string(cache_path).ok()?;
+        let cached = cache.strip_prefix(cache_key)?;
+
+        let mut lib_dir = None;
+        let mut llvm_major_version = None;
+        let mut libs = Vec::new();
+        for line in cached.lines() {
+            match line.split_once('=')? {
+                ("lib_dir", value) => lib_dir = Some(value.to_owned()),
+                ("llvm_major_version", value) => llvm_major_version = value.parse().ok(),
+                ("lib", value) => libs.push(value.to_owned()),
+                _ => return None,
+            }
+        }
+
+        Some(Self {
+            lib_dir: lib_dir?,
+            libs,
+            llvm_major_version: llvm_major_version?,
+        })
+    }
+
+    fn write_cache(&self, cache_path: &Path, cache_key: &str) {
+        let mut cache = format!(
+            "{}lib_dir={}\nllvm_major_version={}\n",
+            cache_key, self.lib_dir, self.llvm_major_version
+        );
+        for lib in &self.libs {
+            cache.push_str(&format!("lib={}\n", lib));
+        }
+
+        // A missing cache only costs the next run some time
+        if let Err(e) = fs::write(cache_path, cache) {
+            eprintln!("Couldn't write {}: {}", cache_path.display(), e);
+        }
+    }
+
+    fn detect(llvm_config: Option<&Path>) -> Self {
         /// Invoke given `command`, if any, with the specified arguments.
         fn invoke_command<I, S>(command: Option<&Path>, args: I) -> Option<String>
         where
@@ -243,7 +431,6 @@ impl LLVMInfo {
             })
         }
 
-        let llvm_config = find_llvm_config();
         let llvm_config_missing = "
         Couldn't find LLVM lib dir. Try setting the `LLVM_LIB_DIR` environment
         variable or make sure `llvm-config` is on $PATH then re-build. For example:
@@ -253,7 +440,7 @@ impl LLVMInfo {
         let lib_dir = {
             let path_str = env::var("LLVM_LIB_DIR")
                 .ok()
-                .or_else(|| invoke_command(llvm_config.as_deref(), &["--libdir"]))
+                .or_else(|| invoke_command(llvm_config, &["--libdir"]))
                 .expect(llvm_config_missing);
             String::from(
                 Path::new(&path_str)
@@ -263,44 +450,31 @@ impl LLVMInfo {
             )
         };
 
-        let llvm_shared_libs = invoke_command(llvm_config.as_deref(), &["--libs", "--link-shared"]);
+        let llvm_shared_libs = invoke_command(llvm_config, &["--libs", "--link-shared"]);
 
         // <sysroot>/lib/rustlib/<target>/lib/ contains a libLLVM DSO for the
-        // rust compiler. On MacOS, this lib is named libLLVM.dylib, which will
-        // always conflict with the dylib we are trying to link against. On
-        // Linux we generally will not hit this issue because the prebuilt lib
-        // includes the `svn` suffix. This would conflict with a source build
-        // from master, however.
+        // rust compiler (Windows toolchains keep it in <sysroot>/bin/ instead).
+        // On MacOS, this lib is named libLLVM.dylib, which will always conflict
+        // with the dylib we are trying to link against. On Linux we generally
+        // will not hit this issue because the prebuilt lib includes the `svn`
+        // suffix. This would conflict with a source build from master, however.
         //
         // We check here if the lib we want to link against will conflict with
         // the rustlib version. If so we can't dynamically link against libLLVM.
         let conflicts_with_rustlib_llvm = {
             if let Some(llvm_shared_libs) = llvm_shared_libs.as_ref() {
-                let dylib_suffix = {
-                    if cfg!(target_os = "macos") {
-                        ".dylib"
-                    } else {
-                        ".so"
-                    } // Windows is not supported
-                };
-                let mut dylib_file = String::from("lib");
-                dylib_file.push_str(llvm_shared_libs.trim_start_matches("-l"));
-                dylib_file.push_str(dylib_suffix);
                 let sysroot = invoke_command(
                     env::var_os("RUSTC").map(PathBuf::from).as_deref(),
                     &["--print=sysroot"],
                 )
                 .unwrap();
 
-                // Does <sysroot>/lib/rustlib/<target>/lib/<dylib_file> exist?
-                let mut libllvm_path = PathBuf::new();
-                libllvm_path.push(sysroot);
-                libllvm_path.push("lib/rustlib");
-                libllvm_path.push(env::var("TARGET").unwrap());
-                libllvm_path.push("lib");
-                libllvm_path.push(dylib_file);
-
-                libllvm_path.as_path().exists()
+                rustlib_dylib_path(
+                    Path::new(&sysroot),
+                    &env::var("TARGET").unwrap(),
+                    llvm_shared_libs,
+                )
+                .exists()
             } else {
                 false
             }
@@ -312,7 +486,7 @@ impl LLVMInfo {
             } else {
                 vec!["--shared-mode"]
             };
-            invoke_command(llvm_config.as_deref(), &args).map_or(false, |c| c == "static")
+            invoke_command(llvm_config, &args).map_or(false, |c| c == "static")
         };
 
         let link_mode = if link_statically {
@@ -322,8 +496,7 @@ impl LLVMInfo {
         };
 
         let llvm_major_version = {
-            let version =
-                invoke_command(llvm_config.as_deref(), &["--version"]).expect(llvm_config_missing);
+            let version = invoke_command(llvm_config, &["--version"]).expect(llvm_config_missing);
             let emsg = format!("invalid version string {}", version);
             version
                 .split('.')
@@ -353,20 +526,18 @@ impl LLVMInfo {
             args.push("WindowsDriver");
         }
 
-        let mut libs: Vec<String> = invoke_command(llvm_config.as_deref(), &args)
-            .unwrap_or_else(|| "-lLLVM".to_string())
-            .split_whitespace()
-            .map(|lib| String::from(lib.trim_start_matches("-l")))
-            .collect();
+        let mut libs = parse_link_libs(
+            &invoke_command(llvm_config, &args).unwrap_or_else(|| "-lLLVM".to_string()),
+        );
 
-        libs.extend(
-            env::var("LLVM_SYSTEM_LIBS")
+        // On macOS the system libs may include `-framework Foo` pairs, which
+        // `parse_link_libs` turns into `framework=Foo`
+        libs.extend(parse_link_libs(
+            &env::var("LLVM_SYSTEM_LIBS")
                 .ok()
-                .or_else(|| invoke_command(llvm_config.as_deref(), &["--system-libs", link_mode]))
-                .unwrap_or_default()
-                .split_whitespace()
-                .map(|lib| String::from(lib.trim_start_matches("-l"))),
-        );
+                .or_else(|| invoke_command(llvm_config, &["--system-libs", link_mode]))
+                .unwrap_or_default(),
+        ));
 
         Self {
             lib_dir,
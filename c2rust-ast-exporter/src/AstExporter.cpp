@@ -3,6 +3,7 @@
 #include <fstream>
 #include <iostream>
 #include <iterator>
+#include <map>
 #include <set>
 #include <unordered_map>
 #include <unordered_set>
@@ -318,6 +319,20 @@ class TypeEncoder final : public TypeVisitor<TypeEncoder> {
         VisitQualType(t);
     }
 
+#if CLANG_VERSION_MAJOR >= 14
+    void VisitBitIntType(const BitIntType *T) {
+#elif CLANG_VERSION_MAJOR >= 11
+    // `_BitInt(N)` was spelled `_ExtInt(N)` before clang 14
+    void VisitExtIntType(const ExtIntType *T) {
+#endif // CLANG_VERSION_MAJOR >= 14
+#if CLANG_VERSION_MAJOR >= 11
+        encodeType(T, TagBitIntType, [T](CborEncoder *local) {
+            cbor_encode_boolean(local, T->isUnsigned());
+            cbor_encode_uint(local, T->getNumBits());
+        });
+    }
+#endif // CLANG_VERSION_MAJOR >= 11
+
     void VisitBuiltinType(const BuiltinType *T) {
         auto kind = T->getKind();
 
@@ -532,13 +547,62 @@ class TypeEncoder final : public TypeVisitor<TypeEncoder> {
     void VisitDecayedType(const DecayedType *T) {
         auto t = T->desugar();
         auto qt = encodeQualType(t);
-        encodeType(T, TagDecayedType,
-                   [qt](CborEncoder *local) { cbor_encode_uint(local, qt); });
+
+        // Keep the type as written so that parameters such as `int a[n]`
+        // are still known to be VLAs, along with their size expressions
+        auto original = T->getOriginalType();
+        auto original_qt = encodeQualType(original);
+
+        encodeType(T, TagDecayedType, [qt, original_qt](CborEncoder *local) {
+            cbor_encode_uint(local, qt);
+            cbor_encode_uint(local, original_qt);
+        });
 
         VisitQualType(t);
+        VisitQualType(original);
     }
 };
 
+// A rule read from the file named by the C2RUST_EXPORT_ANNOTATIONS
+// environment variable. Each line of that file has the form
+// `<decl kind> <name> <annotation>`, e.g. `Function handle_irq #[cold]`,
+// where the decl kind is a clang decl kind name such as `Function`, `Var` or
+// `Record`, or `*` to match declarations of any kind. Lines starting with `#`
+// are comments.
+struct AnnotationRule {
+    std::string kind;
+    std::string name;
+    std::string annotation;
+};
+
+static std::vector<AnnotationRule> loadAnnotationRules() {
+    std::vector<AnnotationRule> rules;
+    auto path = std::getenv("C2RUST_EXPORT_ANNOTATIONS");
+    if (!path)
+        return rules;
+    std::ifstream file(path);
+    if (!file) {
+        std::cerr << "Could not open annotation rules: " << path << std::endl;
+        return rules;
+    }
+    std::string line;
+    while (std::getline(file, line)) {
+        auto rest = StringRef(line).trim();
+        if (rest.empty() || rest.front() == '#')
+            continue;
+        auto kind = rest.split(' ');
+        auto name = kind.second.ltrim().split(' ');
+        auto annotation = name.second.trim();
+        if (name.first.empty() || annotation.empty()) {
+            std::cerr << "Skipping malformed annotation rule: " << line
+                      << std::endl;
+            continue;
+        }
+        rules.push_back({kind.first.str(), name.first.str(), annotation.str()});
+    }
+    return rules;
+}
+
 class TranslateASTVisitor final
     : public RecursiveASTVisitor<TranslateASTVisitor> {
 
@@ -555,6 +619,25 @@ class TranslateASTVisitor final
     DenseMap<FileID, size_t> file_id_mapping;
     std::set<std::pair<void *, ASTEntryTag>> exportedTags;
     std::unordered_map<MacroInfo*, MacroExpansionInfo> macros;
+    // Doc comments of exported file scope declarations
+    std::map<Decl *, const RawComment *> docComments;
+    // Annotations attached to exported declarations by the rules in
+    // C2RUST_EXPORT_ANNOTATIONS
+    std::vector<AnnotationRule> annotationRules;
+    std::map<Decl *, std::vector<std::string>> annotations;
+    // Source spelling of exported expressions, only recorded when the
+    // C2RUST_EXPORT_SOURCE_TEXT environment variable is set
+    bool exportSourceText;
+    std::map<Expr *, std::string> sourceTexts;
+    // Locations as adjusted by `#line` directives and line markers, for the
+    // entries where they differ from the spelling locations exported with
+    // every entry
+    struct PresumedLocation {
+        std::string filename;
+        unsigned line;
+        unsigned column;
+    };
+    std::map<void *, PresumedLocation> presumedLocs;
 
     // This stores a raw encoding of the macro call site SourceLocation, since
     // SourceLocation isn't hashable.
@@ -629,6 +712,7 @@ class TranslateASTVisitor final
         // 6 - End Line number
         // 7 - End Column number
         encodeSourceSpan(&local, loc, isVaList);
+        recordPresumedLoc(ast, loc.getBegin());
 
         // 8 - Type ID (only for expressions)
         encode_qualtype(&local, ty);
@@ -684,6 +768,7 @@ class TranslateASTVisitor final
         encode_entry_raw(ast, tag, ast->getSourceRange(), ty, isRValue, isVaList,
                          encodeMacroExpansions, childIds, extra);
         typeEncoder.VisitQualType(ty);
+        recordSourceText(ast);
     }
 
     void encode_entry(
@@ -705,6 +790,8 @@ class TranslateASTVisitor final
         auto encodeMacroExpansions = false;
         encode_entry_raw(ast, tag, ast->getSourceRange(), T, rvalue,
                          isVaList(ast, T), encodeMacroExpansions, childIds, extra);
+        recordDocComment(ast, tag);
+        recordAnnotations(ast, tag);
     }
 
     /// Explicitly override the source location of this decl for cases where the
@@ -718,6 +805,80 @@ class TranslateASTVisitor final
         auto encodeMacroExpansions = false;
         encode_entry_raw(ast, tag, loc, T, rvalue,
                          isVaList(ast, T), encodeMacroExpansions, childIds, extra);
+        recordDocComment(ast, tag);
+        recordAnnotations(ast, tag);
+    }
+
+    // Remember the `/** */` or `///` comment documenting a file scope
+    // declaration, which may be attached to any of its redeclarations.
+    // Non-canonical decls are never translated, so their comments are
+    // attributed to the canonical decl instead.
+    void recordDocComment(Decl *D, ASTEntryTag tag) {
+        if (tag == TagNonCanonicalDecl || !D->getDeclContext()->isFileContext())
+            return;
+        if (!isa<FunctionDecl>(D) && !isa<VarDecl>(D) && !isa<RecordDecl>(D) &&
+            !isa<EnumDecl>(D) && !isa<TypedefNameDecl>(D))
+            return;
+
+        auto comment = Context->getRawCommentForAnyRedecl(D);
+        if (comment && comment->isDocumentation())
+            docComments.emplace(D, comment);
+    }
+
+    // Attach the annotation of every rule matching the kind and name of an
+    // exported declaration
+    void recordAnnotations(Decl *D, ASTEntryTag tag) {
+        if (annotationRules.empty() || tag == TagNonCanonicalDecl)
+            return;
+        auto named = dyn_cast<NamedDecl>(D);
+        if (!named)
+            return;
+
+        auto name = named->getNameAsString();
+        for (auto const &rule : annotationRules) {
+            if ((rule.kind == "*" || rule.kind == D->getDeclKindName()) &&
+                rule.name == name)
+                annotations[D].push_back(rule.annotation);
+        }
+    }
+
+    // Remember the tokens an expression is spelled with. Expressions produced
+    // by a macro expansion have no spelling of their own and are skipped.
+    void recordSourceText(Expr *E) {
+        if (!exportSourceText)
+            return;
+        auto range = E->getSourceRange();
+        if (range.isInvalid() || range.getBegin().isMacroID() ||
+            range.getEnd().isMacroID())
+            return;
+
+        auto text = Lexer::getSourceText(CharSourceRange::getTokenRange(range),
+                                         Context->getSourceManager(),
+                                         Context->getLangOpts());
+        if (!text.empty())
+            sourceTexts.emplace(E, text.str());
+    }
+
+    // Remember where `#line` directives or line markers claim an entry is,
+    // when that isn't where it's spelled, as in preprocessed `.i` files
+    void recordPresumedLoc(void *ast, SourceLocation loc) {
+        auto &manager = Context->getSourceManager();
+        if (loc.isInvalid())
+            return;
+        if (manager.isMacroArgExpansion(loc) ||
+            manager.isMacroBodyExpansion(loc))
+            loc = manager.getFileLoc(loc);
+
+        auto presumed = manager.getPresumedLoc(loc);
+        if (presumed.isInvalid())
+            return;
+        if (presumed.getLine() == manager.getSpellingLineNumber(loc) &&
+            manager.getFilename(loc) == presumed.getFilename())
+            return;
+
+        presumedLocs.emplace(
+            ast, PresumedLocation{presumed.getFilename(), presumed.getLine(),
+                                  presumed.getColumn()});
     }
 
     MacroInfo* getMacroInfo(SourceLocation loc, StringRef &name) const {
@@ -799,11 +960,35 @@ class TranslateASTVisitor final
                                  Preprocessor &PP)
         : Context(Context), typeEncoder(Context, encoder, sugared, this),
           encoder(encoder), PP(PP),
-          files{{"", {}}} {}
+          files{{"", {}}}, annotationRules(loadAnnotationRules()),
+          exportSourceText(std::getenv("C2RUST_EXPORT_SOURCE_TEXT") != nullptr) {}
 
     // Override the default behavior of the RecursiveASTVisitor
     bool shouldVisitImplicitCode() const { return true; }
 
+    // Returns true if `ptr` has been exported under any tag
+    bool wasExported(void *ptr) {
+        auto search = exportedTags.lower_bound(
+            std::make_pair(ptr, static_cast<ASTEntryTag>(0)));
+        return search != std::end(exportedTags) && search->first == ptr;
+    }
+
+    const std::map<Decl *, const RawComment *> &getDocComments() {
+        return docComments;
+    }
+
+    const std::map<Decl *, std::vector<std::string>> &getAnnotations() {
+        return annotations;
+    }
+
+    const std::map<Expr *, std::string> &getSourceTexts() {
+        return sourceTexts;
+    }
+
+    const std::map<void *, PresumedLocation> &getPresumedLocs() {
+        return presumedLocs;
+    }
+
     // Return the filenames as a vector. Indices correspond to file IDs.
     const std::vector<std::pair<string, SourceLocation>> &getFiles() {
         // Iterate file include locations until fix point
@@ -862,8 +1047,8 @@ class TranslateASTVisitor final
             loc = manager.getFileLoc(loc);
 
         auto fileid = getExporterFileId(manager.getFileID(loc), isVaList);
-        auto line = manager.getPresumedLineNumber(loc);
-        auto col = manager.getPresumedColumnNumber(loc);
+        auto line = manager.getSpellingLineNumber(loc);
+        auto col = manager.getSpellingColumnNumber(loc);
 
         cbor_encode_uint(enc, fileid);
         cbor_encode_uint(enc, line);
@@ -884,10 +1069,10 @@ class TranslateASTVisitor final
             end = manager.getFileLoc(end);
 
         auto fileid = getExporterFileId(manager.getFileID(begin), isVaList);
-        auto begin_line = manager.getPresumedLineNumber(begin);
-        auto begin_col = manager.getPresumedColumnNumber(begin);
-        auto end_line = manager.getPresumedLineNumber(end);
-        auto end_col = manager.getPresumedColumnNumber(end);
+        auto begin_line = manager.getSpellingLineNumber(begin);
+        auto begin_col = manager.getSpellingColumnNumber(begin);
+        auto end_line = manager.getSpellingLineNumber(end);
+        auto end_col = manager.getSpellingColumnNumber(end);
 
         cbor_encode_uint(enc, fileid);
         cbor_encode_uint(enc, begin_line);
@@ -1063,15 +1248,35 @@ class TranslateASTVisitor final
             abort();
         }
 
-        std::vector<void *> childIds{expr, CS->getSubStmt()};
-        encode_entry(CS, TagCaseStmt, childIds, [value](CborEncoder *extra) {
-            cbor_encode_boolean(extra, value.isSigned());
+        // The upper bound of a GNU case range, `case lo ... hi:`
+        auto rhs = CS->getRHS();
+        APSInt rhsValue;
+        if (rhs && !evaluateConstantInt(rhs, rhsValue)) {
+            std::string msg =
+                "Case range upper bound is not an integer. Aborting.";
+            printError(msg, CS);
+            abort();
+        }
+
+        auto encodeValue = [](CborEncoder *extra, const APSInt &value) {
             if (value.isSigned()) {
                 cbor_encode_int(extra, value.getSExtValue());
             } else {
                 cbor_encode_uint(extra, value.getZExtValue());
             }
-        });
+        };
+
+        std::vector<void *> childIds{expr, CS->getSubStmt(), rhs};
+        encode_entry(CS, TagCaseStmt, childIds,
+                     [value, rhs, rhsValue, encodeValue](CborEncoder *extra) {
+                         cbor_encode_boolean(extra, value.isSigned());
+                         encodeValue(extra, value);
+                         if (rhs) {
+                             encodeValue(extra, rhsValue);
+                         } else {
+                             cbor_encode_null(extra);
+                         }
+                     });
         return true;
     }
 
@@ -1158,6 +1363,14 @@ class TranslateASTVisitor final
         return true;
     }
 
+    // MS-style `__asm { ... }` blocks have no operand constraints for the
+    // translator to work from, so report them rather than dropping them.
+    bool VisitMSAsmStmt(MSAsmStmt *E) {
+        std::string msg = "MS-style inline assembly is not supported";
+        printError(msg, E);
+        return true;
+    }
+
     std::string SimplifyConstraint(const std::string &constraint) {
         std::string res;
         const char *p = constraint.c_str();
@@ -1471,7 +1684,7 @@ class TranslateASTVisitor final
      Designator format:
      [1, array_index]            { [1]      = 2 }
      [2, field_id]               { .field   = 1 }
-     [3, array_start, array_end] { [1 .. 2] = 3 }
+     [3, array_start, array_end] { [1 ... 2] = 3 }
      */
     bool VisitDesignatedInitExpr(DesignatedInitExpr *E) {
         std::vector<void *> childIds{E->getInit()};
@@ -1683,6 +1896,17 @@ class TranslateASTVisitor final
         // manually traverse them from this point.
         TraverseDecl(decl);
 
+        // The canonical decl may be a prototype or an extern declaration, but
+        // only the definition's body or initializer gets traversed. When
+        // C2RUST_EXPORT_ONLY prunes the translation unit, nothing else reaches it.
+        Decl *def = nullptr;
+        if (auto FD = dyn_cast<FunctionDecl>(decl))
+            def = FD->getDefinition();
+        else if (auto VD = dyn_cast<VarDecl>(decl))
+            def = VD->getDefinition();
+        if (def && def != decl && !wasExported(def))
+            TraverseDecl(def);
+
         return true;
     }
 
@@ -1691,7 +1915,17 @@ class TranslateASTVisitor final
         for (auto x : CE->arguments()) {
             childIds.push_back(x);
         }
-        encode_entry(CE, TagCallExpr, childIds);
+        // Record which builtin, if any, is being called so the translator
+        // doesn't have to recover it from the callee expression.
+        auto bid = CE->getBuiltinCallee();
+        encode_entry(CE, TagCallExpr, childIds, [this, bid](CborEncoder *extras) {
+            if (bid) {
+                cbor_encode_string(extras,
+                                   std::string(Context->BuiltinInfo.getName(bid)));
+            } else {
+                cbor_encode_null(extras);
+            }
+        });
         return true;
     }
 
@@ -1822,7 +2056,7 @@ class TranslateASTVisitor final
         auto span = paramsFD->getSourceRange();
         encode_entry(
             FD, TagFunctionDecl, span, childIds, functionType,
-            [this, FD](CborEncoder *array) {
+            [this, FD, paramsFD](CborEncoder *array) {
                 auto name = FD->getNameAsString();
                 cbor_encode_string(array, name);
 
@@ -1878,6 +2112,10 @@ class TranslateASTVisitor final
                 }
 
                 cbor_encoder_close_container(array, &attr_info);
+
+                // K&R style declarations such as `void f();` have no prototype,
+                // unlike `void f(void);`
+                cbor_encode_boolean(array, paramsFD->hasPrototype());
             });
         typeEncoder.VisitQualType(functionType);
 
@@ -1973,7 +2211,7 @@ class TranslateASTVisitor final
                 if (has_attrs) {
                     auto attrs = def ? def->getAttrs() : VD->getAttrs();
 
-                    for (auto attr : def->attrs()) {
+                    for (auto attr : attrs) {
                         cbor_encode_text_stringz(&attr_info,
                                                  attr->getSpelling());
 
@@ -1983,11 +2221,27 @@ class TranslateASTVisitor final
                         } else if (auto *aa = dyn_cast<AliasAttr>(attr)) {
                             cbor_encode_text_stringz(
                                 &attr_info, aa->getAliasee().str().c_str());
+                        } else if (auto *va = dyn_cast<VisibilityAttr>(attr)) {
+                            const char *vis = VisibilityAttr::ConvertVisibilityTypeToStr(va->getVisibility());
+                            cbor_encode_text_stringz(&attr_info, vis);
                         }
                     }
                 }
 
+                // The register storage class is passed along with the attributes
+                if (VD->getStorageClass() == SC_Register) {
+                    cbor_encode_text_stringz(&attr_info, "register");
+                }
+
                 cbor_encoder_close_container(array, &attr_info);
+
+                // Encode alignment specified by `_Alignas` or the aligned attribute
+                auto align = def ? def->getMaxAlignment() : VD->getMaxAlignment();
+                if (align == 0) {
+                    cbor_encode_null(array);
+                } else {
+                    cbor_encode_uint(array, align / 8);
+                }
             });
 
         typeEncoder.VisitQualType(T);
@@ -2199,6 +2453,20 @@ class TranslateASTVisitor final
                          // 4. Encode the type's full bit width (even if a
                          // bitfield)
                          cbor_encode_uint(array, bitWidth);
+
+                         // 5. Encode manually specified alignment
+                         auto align = D->getMaxAlignment();
+                         if (align == 0) {
+                             cbor_encode_null(array);
+                         } else {
+                             cbor_encode_uint(array, align / 8);
+                         }
+
+                         // 6. Encode whether this is an anonymous struct or
+                         // union member, whose fields are accessed as if they
+                         // were the parent record's
+                         cbor_encode_boolean(array,
+                                             D->isAnonymousStructOrUnion());
                      });
 
         // This might be the only occurence of this type in the translation unit
@@ -2450,10 +2718,17 @@ void TypeEncoder::VisitTypedefType(const TypedefType *T) {
 
     auto D = T->getDecl()->getCanonicalDecl();
 
-    encodeType(T, TagTypedefType, [D](CborEncoder *local) {
+    // Alongside the typedef as written, export the type it ultimately names
+    // so consumers needn't chase chains of typedefs themselves
+    auto canonical = T->getCanonicalTypeInternal();
+    auto canonical_qt = encodeQualType(canonical);
+
+    encodeType(T, TagTypedefType, [D, canonical_qt](CborEncoder *local) {
         cbor_encode_uint(local, uintptr_t(D));
+        cbor_encode_uint(local, canonical_qt);
     });
     astEncoder->TraverseDecl(D);
+    VisitQualType(canonical);
 }
 
 void TypeEncoder::VisitVariableArrayType(const VariableArrayType *T) {
@@ -2495,6 +2770,24 @@ class TranslateConsumer : public clang::ASTConsumer {
     explicit TranslateConsumer(Outputs *outputs, llvm::StringRef InFile, Preprocessor &PP)
         : outputs(outputs), outfile(InFile.str()), PP(PP) {}
 
+    // Names listed in the comma-separated C2RUST_EXPORT_ONLY environment
+    // variable. When non-empty, only these top-level declarations and the
+    // declarations reachable from them are exported.
+    static std::unordered_set<std::string> exportOnlyNames() {
+        std::unordered_set<std::string> names;
+        auto env = std::getenv("C2RUST_EXPORT_ONLY");
+        if (!env)
+            return names;
+        SmallVector<StringRef, 8> parts;
+        StringRef(env).split(parts, ',', -1, false);
+        for (auto part : parts) {
+            auto name = part.trim();
+            if (!name.empty())
+                names.insert(name.str());
+        }
+        return names;
+    }
+
     virtual void HandleTranslationUnit(clang::ASTContext &Context) {
 
         CborEncoder encoder;
@@ -2510,7 +2803,7 @@ class TranslateConsumer : public clang::ASTConsumer {
             cbor_encoder_init(&encoder, buffer, len, 0);
 
             CborEncoder outer;
-            cbor_encoder_create_array(&encoder, &outer, 6);
+            cbor_encoder_create_array(&encoder, &outer, 10);
 
             CborEncoder array;
 
@@ -2518,7 +2811,18 @@ class TranslateConsumer : public clang::ASTConsumer {
             cbor_encoder_create_array(&outer, &array, CborIndefiniteLength);
             TranslateASTVisitor visitor(&Context, &array, &sugared, PP);
             auto translation_unit = Context.getTranslationUnitDecl();
-            visitor.TraverseDecl(translation_unit);
+            auto export_only = exportOnlyNames();
+            if (export_only.empty()) {
+                visitor.TraverseDecl(translation_unit);
+            } else {
+                // Only start from the named declarations. Anything they
+                // refer to is pulled in by the visitor as it is encountered.
+                for (auto d : translation_unit->decls()) {
+                    auto named = dyn_cast<NamedDecl>(d);
+                    if (named && export_only.count(named->getNameAsString()))
+                        visitor.TraverseDecl(d);
+                }
+            }
             visitor.encodeMacros();
             cbor_encoder_close_container(&outer, &array);
 
@@ -2541,6 +2845,11 @@ class TranslateConsumer : public clang::ASTConsumer {
                     continue;
                 }
 
+                // Declarations pruned by C2RUST_EXPORT_ONLY have no AST node
+                if (!export_only.empty() && !visitor.wasExported(d)) {
+                    continue;
+                }
+
                 cbor_encode_uint(&array, reinterpret_cast<std::uintptr_t>(d));
             }
             cbor_encoder_close_container(&outer, &array);
@@ -2616,6 +2925,71 @@ class TranslateConsumer : public clang::ASTConsumer {
             auto target = Context.getTargetInfo().getTriple().str();
             cbor_encode_string(&outer, target);
 
+            // 7. Emit doc comments as array of arrays. Each doc comment is
+            // represented as an array of the documented decl's ID, the
+            // comment's source position and the comment string.
+            auto &docComments = visitor.getDocComments();
+            cbor_encoder_create_array(&outer, &array, docComments.size());
+            for (auto const &docComment : docComments) {
+                CborEncoder entry;
+                cbor_encoder_create_array(&array, &entry, 5);
+                cbor_encode_uint(&entry, reinterpret_cast<std::uintptr_t>(docComment.first));
+#if CLANG_VERSION_MAJOR < 8
+                SourceLocation loc = docComment.second->getLocStart();
+#else
+                SourceLocation loc = docComment.second->getBeginLoc();
+#endif // CLANG_VERSION_MAJOR < 8
+                visitor.encodeSourcePos(&entry, loc); // emits 3 values
+                auto raw_text = docComment.second->getRawText(sourceMgr);
+                cbor_encode_byte_string(&entry, raw_text.bytes_begin(),
+                                        raw_text.size());
+                cbor_encoder_close_container(&array, &entry);
+            }
+            cbor_encoder_close_container(&outer, &array);
+
+            // 8. Emit the extension map of annotations attached by
+            // C2RUST_EXPORT_ANNOTATIONS, from each annotated decl's ID to the
+            // array of its annotation strings.
+            auto &annotations = visitor.getAnnotations();
+            CborEncoder map;
+            cbor_encoder_create_map(&outer, &map, annotations.size());
+            for (auto const &annotated : annotations) {
+                cbor_encode_uint(&map, reinterpret_cast<std::uintptr_t>(annotated.first));
+                CborEncoder entry;
+                cbor_encoder_create_array(&map, &entry, annotated.second.size());
+                for (auto const &annotation : annotated.second)
+                    cbor_encode_string(&entry, annotation);
+                cbor_encoder_close_container(&map, &entry);
+            }
+            cbor_encoder_close_container(&outer, &map);
+
+            // 9. Emit the extension map of source spellings recorded with
+            // C2RUST_EXPORT_SOURCE_TEXT, from each expression's ID to its
+            // source text.
+            auto &sourceTexts = visitor.getSourceTexts();
+            cbor_encoder_create_map(&outer, &map, sourceTexts.size());
+            for (auto const &sourceText : sourceTexts) {
+                cbor_encode_uint(&map, reinterpret_cast<std::uintptr_t>(sourceText.first));
+                cbor_encode_string(&map, sourceText.second);
+            }
+            cbor_encoder_close_container(&outer, &map);
+
+            // 10. Emit the extension map of presumed locations, from the ID of
+            // each entry whose location was adjusted by a `#line` directive or
+            // line marker to its presumed file name, line and column.
+            auto &presumedLocs = visitor.getPresumedLocs();
+            cbor_encoder_create_map(&outer, &map, presumedLocs.size());
+            for (auto const &presumedLoc : presumedLocs) {
+                cbor_encode_uint(&map, reinterpret_cast<std::uintptr_t>(presumedLoc.first));
+                CborEncoder entry;
+                cbor_encoder_create_array(&map, &entry, 3);
+                cbor_encode_string(&entry, presumedLoc.second.filename);
+                cbor_encode_uint(&entry, presumedLoc.second.line);
+                cbor_encode_uint(&entry, presumedLoc.second.column);
+                cbor_encoder_close_container(&map, &entry);
+            }
+            cbor_encoder_close_container(&outer, &map);
+
             cbor_encoder_close_container(&encoder, &outer);
         };
 
@@ -2689,9 +3063,17 @@ static std::vector<const char *> augment_argv(int argc, const char *argv[]) {
     // Build a -resource-dir argument based on the path to the linked clang
     // installation. Without this, ClangTool builds the resource directory from
     // the path to the tool (in this case, the binary running the AST Exporter).
-    SmallString<128> P("-extra-arg=-resource-dir=" CLANG_BIN_PATH);
-    llvm::sys::path::append(P, "..", Twine("lib") + CLANG_LIBDIR_SUFFIX,
-                            "clang", CLANG_VERSION_STRING);
+    // Installations which keep the resource directory elsewhere can point at
+    // it with the C2RUST_CLANG_RESOURCE_DIR environment variable instead.
+    SmallString<128> P("-extra-arg=-resource-dir=");
+    auto env_resource_dir = std::getenv("C2RUST_CLANG_RESOURCE_DIR");
+    if (env_resource_dir && *env_resource_dir) {
+        P.append(env_resource_dir);
+    } else {
+        P.append(CLANG_BIN_PATH);
+        llvm::sys::path::append(P, "..", Twine("lib") + CLANG_LIBDIR_SUFFIX,
+                                "clang", CLANG_VERSION_STRING);
+    }
     std::string resource_dir = P.str().str();
     char *resource_dir_cstr = new char[resource_dir.length() + 1];
     strncpy(resource_dir_cstr, resource_dir.c_str(), resource_dir.length() + 1);
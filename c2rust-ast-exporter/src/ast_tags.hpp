@@ -142,6 +142,7 @@ enum TypeTag {
     TagComplexType,
     TagHalf,
     TagBFloat16,
+    TagBitIntType,
 };
 
 enum StringTypeTag {
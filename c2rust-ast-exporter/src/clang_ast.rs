@@ -24,7 +24,7 @@ impl LRValue {
     }
 }
 
-#[derive(Copy, Debug, Clone, PartialOrd, PartialEq, Ord, Eq)]
+#[derive(Copy, Debug, Clone, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub struct SrcLoc {
     pub fileid: u64,
     pub line: u64,
@@ -97,6 +97,15 @@ pub struct CommentNode {
     pub string: String,
 }
 
+/// Where `#line` directives or line markers claim a node is, as opposed to
+/// the spelling location of its `SrcSpan`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresumedLoc {
+    pub file: String,
+    pub line: u64,
+    pub column: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct SrcFile {
     pub path: Option<PathBuf>,
@@ -117,6 +126,18 @@ pub struct AstContext {
     pub type_nodes: HashMap<u64, TypeNode>,
     pub top_nodes: Vec<u64>,
     pub comments: Vec<CommentNode>,
+    /// Doc comments keyed by the ID of the file scope decl they document
+    pub doc_comments: HashMap<u64, CommentNode>,
+    /// Annotations attached to decls by the rules named by the
+    /// `C2RUST_EXPORT_ANNOTATIONS` environment variable, keyed by decl ID
+    pub annotations: HashMap<u64, Vec<String>>,
+    /// Source spelling of expressions, recorded when the
+    /// `C2RUST_EXPORT_SOURCE_TEXT` environment variable is set, keyed by
+    /// expression ID
+    pub source_texts: HashMap<u64, String>,
+    /// Presumed locations of the nodes whose location was adjusted by a
+    /// `#line` directive or line marker, keyed by node ID
+    pub presumed_locs: HashMap<u64, PresumedLoc>,
     pub files: Vec<SrcFile>,
     pub va_list_kind: BuiltinVaListKind,
     pub target: String,
@@ -161,16 +182,39 @@ pub fn process(items: Value) -> error::Result<AstContext> {
     type RawComment = (u64, u64, u64, ByteBuf);
     type VaListKind = u64;
     type Target = String;
-    let (all_nodes, top_nodes, files, raw_comments, va_list_kind, target): (
+    type RawDocComment = (u64, u64, u64, u64, ByteBuf);
+    type Annotations = HashMap<u64, Vec<String>>;
+    type SourceTexts = HashMap<u64, String>;
+    type PresumedLocs = HashMap<u64, (String, u64, u64)>;
+    let (
+        all_nodes,
+        top_nodes,
+        files,
+        raw_comments,
+        va_list_kind,
+        target,
+        raw_doc_comments,
+        annotations,
+        source_texts,
+        presumed_locs,
+    ): (
         Vec<AllNode>,
         Vec<TopNode>,
         Vec<File>,
         Vec<RawComment>,
         VaListKind,
         Target,
+        Vec<RawDocComment>,
+        Annotations,
+        SourceTexts,
+        PresumedLocs,
     ) = from_value(items)?;
 
     let va_list_kind = import_va_list_kind(va_list_kind);
+    let presumed_locs = presumed_locs
+        .into_iter()
+        .map(|(id, (file, line, column))| (id, PresumedLoc { file, line, column }))
+        .collect();
 
     for (fileid, line, column, bytes) in raw_comments {
         comments.push(CommentNode {
@@ -183,6 +227,22 @@ pub fn process(items: Value) -> error::Result<AstContext> {
         })
     }
 
+    let doc_comments = raw_doc_comments
+        .into_iter()
+        .map(|(decl_id, fileid, line, column, bytes)| {
+            let comment = CommentNode {
+                loc: SrcLoc {
+                    fileid,
+                    line,
+                    column,
+                },
+                string: String::from_utf8_lossy(&bytes).to_string(),
+            };
+
+            (decl_id, comment)
+        })
+        .collect();
+
     let files = files
         .into_iter()
         .map(|(path, loc)| {
@@ -269,6 +329,10 @@ pub fn process(items: Value) -> error::Result<AstContext> {
         ast_nodes: asts,
         type_nodes: types,
         comments,
+        doc_comments,
+        annotations,
+        source_texts,
+        presumed_locs,
         files,
         va_list_kind,
         target,
@@ -1,13 +1,13 @@
 #![recursion_limit = "512"]
 
-use proc_macro::{Span, TokenStream};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::quote;
 use syn::parse::Error;
-use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, Attribute, Field, Fields, Ident, ItemStruct, Lit, Meta, NestedMeta, Path,
-    PathArguments, PathSegment, Token,
+    parse_macro_input, Attribute, Field, Fields, GenericArgument, Ident, Index, ItemEnum,
+    ItemStruct, Lit, Member, Meta, NestedMeta, Path, PathArguments, Type,
 };
 
 #[cfg(target_endian = "big")]
@@ -17,90 +17,261 @@ compile_error!("Big endian architectures are not currently supported");
 /// as well as the bitfield's field name.
 #[derive(Debug)]
 struct BFFieldAttr {
-    field_name: Ident,
-    name: String,
-    ty: String,
+    field_name: Member,
+    field_ty: Type,
+    name: (String, proc_macro2::Span),
+    ty: (String, proc_macro2::Span),
     bits: (String, proc_macro2::Span),
+    cfg: Option<(String, proc_macro2::Span)>,
+    enum_ty: Option<(String, proc_macro2::Span)>,
+    must_use: bool,
+    // The span of a `bit_scan` param
+    bit_scan: Option<proc_macro2::Span>,
+    raw: bool,
+    msb0: bool,
+    // The span of an `endian = "big"` param
+    big_endian: Option<proc_macro2::Span>,
+    // The span of an `endian = "little"` param
+    little_endian: Option<proc_macro2::Span>,
+    allow_overlap: bool,
 }
 
 fn parse_bitfield_attr(
     attr: &Attribute,
-    field_ident: &Ident,
-) -> Result<Option<BFFieldAttr>, Error> {
+    field_name: &Member,
+    field_ty: &Type,
+) -> Result<Vec<BFFieldAttr>, Error> {
     let mut name = None;
     let mut ty = None;
     let mut bits = None;
-    let mut bits_span = None;
+    let mut cfg = None;
+    let mut enum_ty = None;
+    let mut must_use = false;
+    let mut bit_scan = None;
+    let mut raw = false;
+    let mut msb0 = false;
+    let mut big_endian = None;
+    let mut little_endian = None;
+    let mut allow_overlap = false;
+    let mut flags = None;
+    let mut reserved = false;
+    let mut bytes = false;
 
     if let Meta::List(meta_list) = attr.parse_meta()? {
         for nested_meta in meta_list.nested {
-            if let NestedMeta::Meta(Meta::NameValue(meta_name_value)) = nested_meta {
-                let rhs_string = match meta_name_value.lit {
-                    Lit::Str(lit_str) => lit_str.value(),
-                    _ => {
-                        let err_str = "Found bitfield attribute with non str literal assignment";
-                        let span = meta_name_value.path.span();
+            let meta_name_value = match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(meta_name_value)) => meta_name_value,
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("padding") => {
+                    return Ok(Vec::new());
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("must_use") => {
+                    must_use = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("bit_scan") => {
+                    bit_scan = Some(path.span());
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("raw") => {
+                    raw = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("msb0") => {
+                    msb0 = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("allow_overlap") => {
+                    allow_overlap = true;
+                    continue;
+                }
+                _ => {
+                    let err_str = "Unrecognized bitfield param, expected `param = \"value\"`";
+                    let span = nested_meta.span();
+
+                    return Err(Error::new(span, err_str));
+                }
+            };
+            let rhs_span = meta_name_value.lit.span();
+            let rhs_string = match meta_name_value.lit {
+                Lit::Str(lit_str) => lit_str.value(),
+                _ => {
+                    let err_str = "Found bitfield attribute with non str literal assignment";
+                    let span = meta_name_value.path.span();
+
+                    return Err(Error::new(span, err_str));
+                }
+            };
+            let lhs_string = match meta_name_value.path.get_ident() {
+                Some(lhs_ident) => lhs_ident.to_string(),
+                None => {
+                    let err_str = "Bitfield params must be plain identifiers";
+                    let span = meta_name_value.path.span();
+
+                    return Err(Error::new(span, err_str));
+                }
+            };
 
-                        return Err(Error::new(span, err_str));
+            match lhs_string.as_str() {
+                "name" => name = Some((rhs_string, rhs_span)),
+                "ty" => ty = Some((rhs_string, rhs_span)),
+                "bits" => bits = Some((rhs_string, meta_name_value.path.span())),
+                "cfg" => cfg = Some((rhs_string, meta_name_value.path.span())),
+                "enum_ty" => enum_ty = Some((rhs_string, rhs_span)),
+                "flags" => flags = Some((rhs_string, rhs_span)),
+                "endian" => match rhs_string.as_str() {
+                    "big" => {
+                        big_endian = Some(rhs_span);
+                        little_endian = None;
                     }
-                };
+                    "little" => {
+                        big_endian = None;
+                        little_endian = Some(rhs_span);
+                    }
+                    _ => {
+                        let err_str = "endian param must be \"big\" or \"little\"";
 
-                if let Some(lhs_ident) = meta_name_value.path.get_ident() {
-                    match lhs_ident.to_string().as_str() {
-                        "name" => name = Some(rhs_string),
-                        "ty" => ty = Some(rhs_string),
-                        "bits" => {
-                            bits = Some(rhs_string);
-                            bits_span = Some(meta_name_value.path.span());
-                        }
-                        // This one shouldn't ever occur here,
-                        // but we're handling it just to be safe
-                        "padding" => {
-                            return Ok(None);
-                        }
-                        _ => {}
+                        return Err(Error::new(rhs_span, err_str));
                     }
+                },
+                // Its bits are read by `check_exhaustive`
+                "reserved" => reserved = true,
+                // Its byte range is read by `generate_byte_spans`
+                "bytes" => bytes = true,
+                // This one shouldn't ever occur here,
+                // but we're handling it just to be safe
+                "padding" => {
+                    return Ok(Vec::new());
                 }
-            } else if let NestedMeta::Meta(Meta::Path(ref path)) = nested_meta {
-                if let Some(ident) = path.get_ident() {
-                    if ident == "padding" {
-                        return Ok(None);
-                    }
+                _ => {
+                    let err_str = format!("Unknown bitfield param `{}`", lhs_string);
+                    let span = meta_name_value.path.span();
+
+                    return Err(Error::new(span, err_str));
                 }
             }
         }
     }
 
-    if name.is_none() || ty.is_none() || bits.is_none() {
-        let mut missing_fields = Vec::new();
+    let make_bitfield = |name, ty, bits| BFFieldAttr {
+        field_name: field_name.clone(),
+        field_ty: field_ty.clone(),
+        name,
+        ty,
+        bits,
+        cfg: cfg.clone(),
+        enum_ty: enum_ty.clone(),
+        must_use,
+        bit_scan,
+        raw,
+        msb0,
+        big_endian,
+        little_endian,
+        allow_overlap,
+    };
+
+    // Reserved bits get no accessors, they only count as covered for `exhaustive`
+    if reserved {
+        if name.is_some() || ty.is_some() || bits.is_some() || flags.is_some() {
+            let err_str = "reserved param excludes name, ty, bits and flags";
+            let span = attr.path.segments.span();
 
-        if name.is_none() {
-            missing_fields.push("name");
+            return Err(Error::new(span, err_str));
         }
 
-        if ty.is_none() {
-            missing_fields.push("ty");
+        return Ok(Vec::new());
+    }
+
+    // Byte spans get their own accessors rather than being bitfields
+    if bytes {
+        if name.is_none() || ty.is_some() || bits.is_some() || flags.is_some() || enum_ty.is_some()
+        {
+            let err_str =
+                "bytes param requires a name param and excludes ty, bits, flags and enum_ty";
+            let span = attr.path.segments.span();
+
+            return Err(Error::new(span, err_str));
         }
 
-        if bits.is_none() {
-            missing_fields.push("bits");
+        return Ok(Vec::new());
+    }
+
+    // `flags` is shorthand for one single bit bitfield per bit of the member
+    if let Some((flags, flags_span)) = flags {
+        let ty = match (name, ty, bits) {
+            (None, Some(ty), None) => ty,
+            _ => {
+                let err_str = "flags param requires a ty param and excludes name and bits";
+                let span = attr.path.segments.span();
+
+                return Err(Error::new(span, err_str));
+            }
+        };
+        let names: Vec<_> = flags.split(',').map(str::trim).collect();
+        let bit_width = backing_byte_len(cell_inner_ty(field_ty).unwrap_or(field_ty))
+            .map(|byte_len| byte_len * 8);
+
+        match bit_width {
+            Some(bit_width) if bit_width == names.len() => {}
+            Some(bit_width) => {
+                let err_str = format!(
+                    "flags param names {} bitfields but its member has {} bits",
+                    names.len(),
+                    bit_width
+                );
+
+                return Err(Error::new(flags_span, err_str));
+            }
+            None => {
+                let err_str = "flags param requires an integer or `[u8; N]` member";
+
+                return Err(Error::new(flags_span, err_str));
+            }
         }
 
-        let err_str = format!("Missing bitfield params: {:?}", missing_fields);
-        let span = attr.path.segments.span();
+        let bitfields = names
+            .into_iter()
+            .enumerate()
+            .map(|(bit, name)| {
+                let bits = (format!("{}..={}", bit, bit), flags_span);
 
-        return Err(Error::new(span, err_str));
+                make_bitfield((name.to_owned(), flags_span), ty.clone(), bits)
+            })
+            .collect();
+
+        return Ok(bitfields);
     }
 
-    Ok(Some(BFFieldAttr {
-        field_name: field_ident.clone(),
-        name: name.unwrap(),
-        ty: ty.unwrap(),
-        bits: (bits.unwrap(), bits_span.unwrap()),
-    }))
+    let (name, ty, bits) = match (name, ty, bits) {
+        (Some(name), Some(ty), Some(bits)) => (name, ty, bits),
+        // A bare `#[bitfield(allow_overlap)]` applies to the whole member
+        // and is picked up by `filter_and_parse_fields`
+        (None, None, None) if allow_overlap => return Ok(Vec::new()),
+        (name, ty, bits) => {
+            let mut missing_fields = Vec::new();
+
+            if name.is_none() {
+                missing_fields.push("name");
+            }
+
+            if ty.is_none() {
+                missing_fields.push("ty");
+            }
+
+            if bits.is_none() {
+                missing_fields.push("bits");
+            }
+
+            let err_str = format!("Missing bitfield params: {:?}", missing_fields);
+            let span = attr.path.segments.span();
+
+            return Err(Error::new(span, err_str));
+        }
+    };
+
+    Ok(vec![make_bitfield(name, ty, bits)])
 }
 
-fn filter_and_parse_fields(field: &Field) -> Vec<Result<BFFieldAttr, Error>> {
+fn filter_and_parse_fields(field: &Field) -> Result<Vec<BFFieldAttr>, Error> {
     let attrs: Vec<_> = field
         .attrs
         .iter()
@@ -108,154 +279,3837 @@ fn filter_and_parse_fields(field: &Field) -> Vec<Result<BFFieldAttr, Error>> {
         .collect();
 
     if attrs.is_empty() {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
-    attrs
-        .into_iter()
-        .map(|attr| parse_bitfield_attr(attr, field.ident.as_ref().unwrap()))
-        .flat_map(Result::transpose) // Remove the Ok(None) values
-        .collect()
+    let field_name = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => {
+            let err_str = "Bitfields may only be declared on named members";
+
+            return Err(Error::new(field.span(), err_str));
+        }
+    };
+    let mut bitfields = Vec::new();
+
+    for attr in &attrs {
+        bitfields.extend(parse_bitfield_attr(attr, &field_name, &field.ty)?);
+    }
+
+    if attrs.iter().any(|attr| is_bare_allow_overlap(attr)) {
+        for bitfield in &mut bitfields {
+            bitfield.allow_overlap = true;
+        }
+    }
+
+    Ok(bitfields)
 }
 
-fn parse_bitfield_ty_path(field: &BFFieldAttr) -> Path {
-    let leading_colon = if field.ty.starts_with("::") {
-        Some(Token![::]([
-            Span::call_site().into(),
-            Span::call_site().into(),
-        ]))
-    } else {
-        None
+fn is_bare_allow_overlap(attr: &Attribute) -> bool {
+    match attr.parse_meta() {
+        Ok(Meta::List(meta_list)) => {
+            meta_list.nested.len() == 1
+                && matches!(
+                    &meta_list.nested[0],
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("allow_overlap")
+                )
+        }
+        _ => false,
+    }
+}
+
+/// This struct keeps track of the params of `#[bitfield(...)]` attrs
+/// placed on the struct itself rather than on one of its members.
+#[derive(Debug, Default)]
+struct BFStructAttr {
+    ord_by: Option<(String, proc_macro2::Span)>,
+    storage: Option<(String, proc_macro2::Span)>,
+    must_use: bool,
+    msb0: bool,
+    display: bool,
+    display_hex: bool,
+    debug: bool,
+    view: bool,
+    allow_overlap: bool,
+    exhaustive: bool,
+    diff: bool,
+    dump: bool,
+    with_setters: bool,
+    explicit_setters: bool,
+    if_changed: bool,
+    try_new: bool,
+    byte_conversion: bool,
+    member_matches: bool,
+    member_zero: bool,
+    member_getters: bool,
+    validate_layout: bool,
+    at_offset: bool,
+    // The span of an `endian = "big"` struct param
+    big_endian: Option<proc_macro2::Span>,
+    accessor_mod: Option<(String, proc_macro2::Span)>,
+    accessor_trait: Option<proc_macro2::Span>,
+    trace: Option<(String, proc_macro2::Span)>,
+    offsets: Option<(String, proc_macro2::Span)>,
+    size: Option<(String, proc_macro2::Span)>,
+    core_path: Option<(String, proc_macro2::Span)>,
+    libc_path: Option<(String, proc_macro2::Span)>,
+}
+
+/// Struct level `#[bitfield(...)]` attrs either hold struct params or,
+/// when the struct uses synthesized storage, describe a single bitfield
+fn is_bitfield_spec(attr: &Attribute) -> bool {
+    let is_spec_param = |nested: &NestedMeta| match nested {
+        NestedMeta::Meta(Meta::NameValue(meta_name_value)) => {
+            meta_name_value.path.is_ident("name")
+                || meta_name_value.path.is_ident("ty")
+                || meta_name_value.path.is_ident("bits")
+                || meta_name_value.path.is_ident("flags")
+                || meta_name_value.path.is_ident("reserved")
+        }
+        NestedMeta::Meta(Meta::Path(path)) => path.is_ident("padding"),
+        _ => false,
     };
 
-    let mut segments = Punctuated::new();
-    let mut segment_strings = field.ty.split("::").peekable();
+    match attr.parse_meta() {
+        Ok(Meta::List(meta_list)) => meta_list.nested.iter().any(is_spec_param),
+        _ => false,
+    }
+}
 
-    while let Some(segment_string) = segment_strings.next() {
-        segments.push_value(PathSegment {
-            ident: Ident::new(segment_string, Span::call_site().into()),
-            arguments: PathArguments::None,
-        });
+fn parse_struct_attrs(attrs: &[Attribute]) -> Result<BFStructAttr, Error> {
+    let mut struct_attr = BFStructAttr::default();
+    let bitfield_attrs = attrs
+        .iter()
+        .filter(|attr| attr.path.segments.last().unwrap().ident == "bitfield")
+        .filter(|attr| !is_bitfield_spec(attr));
+
+    for attr in bitfield_attrs {
+        let meta_list = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list,
+            meta => {
+                let err_str = "Expected a list of bitfield struct params";
+
+                return Err(Error::new(meta.span(), err_str));
+            }
+        };
+
+        for nested_meta in meta_list.nested {
+            let meta_name_value = match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(meta_name_value)) => meta_name_value,
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("must_use") => {
+                    struct_attr.must_use = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("msb0") => {
+                    struct_attr.msb0 = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("display") => {
+                    struct_attr.display = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("display_hex") => {
+                    struct_attr.display_hex = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("debug") => {
+                    struct_attr.debug = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("view") => {
+                    struct_attr.view = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("allow_overlap") => {
+                    struct_attr.allow_overlap = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("exhaustive") => {
+                    struct_attr.exhaustive = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("diff") => {
+                    struct_attr.diff = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("if_changed") => {
+                    struct_attr.if_changed = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("at_offset") => {
+                    struct_attr.at_offset = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("accessor_trait") => {
+                    struct_attr.accessor_trait = Some(path.span());
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("with_setters") => {
+                    struct_attr.with_setters = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("explicit_setters") => {
+                    struct_attr.explicit_setters = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("try_new") => {
+                    struct_attr.try_new = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("byte_conversion") => {
+                    struct_attr.byte_conversion = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("member_matches") => {
+                    struct_attr.member_matches = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("member_zero") => {
+                    struct_attr.member_zero = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("member_getters") => {
+                    struct_attr.member_getters = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("validate_layout") => {
+                    struct_attr.validate_layout = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("dump") => {
+                    struct_attr.dump = true;
+                    continue;
+                }
+                _ => {
+                    let err_str = "Unrecognized bitfield param, expected `param = \"value\"`";
+                    let span = nested_meta.span();
+
+                    return Err(Error::new(span, err_str));
+                }
+            };
+            let rhs_span = meta_name_value.lit.span();
+            let rhs_string = match meta_name_value.lit {
+                Lit::Str(lit_str) => lit_str.value(),
+                _ => {
+                    let err_str = "Found bitfield attribute with non str literal assignment";
+                    let span = meta_name_value.path.span();
+
+                    return Err(Error::new(span, err_str));
+                }
+            };
+
+            if meta_name_value.path.is_ident("ord_by") {
+                struct_attr.ord_by = Some((rhs_string, rhs_span));
+            } else if meta_name_value.path.is_ident("storage") {
+                struct_attr.storage = Some((rhs_string, rhs_span));
+            } else if meta_name_value.path.is_ident("endian") {
+                struct_attr.big_endian = match rhs_string.as_str() {
+                    "big" => Some(rhs_span),
+                    "little" => None,
+                    _ => {
+                        let err_str = "endian param must be \"big\" or \"little\"";
 
-        if segment_strings.peek().is_some() {
-            segments.push_punct(Token![::]([
-                Span::call_site().into(),
-                Span::call_site().into(),
-            ]));
+                        return Err(Error::new(rhs_span, err_str));
+                    }
+                };
+            } else if meta_name_value.path.is_ident("accessor_mod") {
+                struct_attr.accessor_mod = Some((rhs_string, rhs_span));
+            } else if meta_name_value.path.is_ident("trace") {
+                struct_attr.trace = Some((rhs_string, rhs_span));
+            } else if meta_name_value.path.is_ident("offsets") {
+                struct_attr.offsets = Some((rhs_string, rhs_span));
+            } else if meta_name_value.path.is_ident("size") {
+                struct_attr.size = Some((rhs_string, rhs_span));
+            } else if meta_name_value.path.is_ident("core_path") {
+                struct_attr.core_path = Some((rhs_string, rhs_span));
+            } else if meta_name_value.path.is_ident("libc_path") {
+                struct_attr.libc_path = Some((rhs_string, rhs_span));
+            } else {
+                let err_str = "Unknown bitfield struct param";
+                let span = meta_name_value.path.span();
+
+                return Err(Error::new(span, err_str));
+            }
         }
     }
 
-    Path {
-        leading_colon,
-        segments,
+    Ok(struct_attr)
+}
+
+/// Parses the value of the `core_path` or `libc_path` struct param
+fn parse_crate_path(
+    param: &str,
+    path: &Option<(String, proc_macro2::Span)>,
+) -> Result<Option<proc_macro2::TokenStream>, Error> {
+    let (path, span) = match path {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    let path: Path = syn::parse_str(path).map_err(|_| {
+        let err_str = format!("{} `{}` is not a valid path", param, path);
+
+        Error::new(*span, err_str)
+    })?;
+
+    Ok(Some(quote! { #path }))
+}
+
+/// Calls one of a bitfield's generated methods on `receiver`, a reference to
+/// the struct. With `accessor_mod` or `accessor_trait` the call goes through
+/// the accessor trait, so hand written inherent methods of the same name
+/// don't take precedence.
+fn call_accessor(
+    accessor_trait: Option<&Path>,
+    receiver: proc_macro2::TokenStream,
+    method: &Ident,
+    args: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match accessor_trait {
+        Some(accessor_trait) => quote! { <Self as #accessor_trait>::#method(#receiver, #args) },
+        None => quote! { #receiver.#method(#args) },
+    }
+}
+
+/// Moves the generated bitfield methods out of the struct's inherent impl and
+/// into a `<Struct>Fields` trait, declared and implemented in a submodule
+/// named by the `accessor_mod` struct param or, with `accessor_trait`,
+/// alongside the struct itself
+fn generate_accessor_trait(
+    struct_ident: &Ident,
+    vis: &syn::Visibility,
+    mod_ident: Option<&Ident>,
+    trait_ident: &Ident,
+    methods: proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let methods: syn::ItemImpl = syn::parse2(quote! { impl #struct_ident { #methods } })?;
+    let mut decls = Vec::new();
+    let mut impls = Vec::new();
+
+    for item in methods.items {
+        let mut method = match item {
+            syn::ImplItem::Method(method) => method,
+            item => return Err(Error::new(item.span(), "Expected a bitfield method")),
+        };
+        let is_decl_attr = |attr: &&Attribute| {
+            ["doc", "cfg", "must_use"]
+                .iter()
+                .any(|name| attr.path.is_ident(name))
+        };
+        let decl_attrs = method.attrs.iter().filter(is_decl_attr);
+        let sig = &method.sig;
+
+        decls.push(quote! {
+            #(#decl_attrs)*
+            #sig;
+        });
+
+        // `must_use` has no effect on trait impl methods
+        method.attrs.retain(|attr| !attr.path.is_ident("must_use"));
+        method.vis = syn::Visibility::Inherited;
+        impls.push(method);
+    }
+
+    let doc = format!("The bitfield accessors of [`{}`]", struct_ident);
+    let mod_ident = match mod_ident {
+        Some(mod_ident) => mod_ident,
+        None => {
+            return Ok(quote! {
+                #[doc = #doc]
+                #vis trait #trait_ident {
+                    #(#decls)*
+                }
+
+                #[automatically_derived]
+                impl #trait_ident for #struct_ident {
+                    #(#impls)*
+                }
+            })
+        }
+    };
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis mod #mod_ident {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[doc = #doc]
+            pub trait #trait_ident {
+                #(#decls)*
+            }
+
+            #[automatically_derived]
+            impl #trait_ident for super::#struct_ident {
+                #(#impls)*
+            }
+        }
+    })
+}
+
+/// Points the bitfield `ty`s starting with `libc::` at the crate path given
+/// by the `libc_path` struct param
+fn apply_libc_path(bitfields: &mut [BFFieldAttr], libc_path: &str) {
+    for bitfield in bitfields {
+        if let Some(rest) = bitfield.ty.0.trim_start().strip_prefix("libc::") {
+            bitfield.ty.0 = format!("{}::{}", libc_path.trim(), rest);
+        }
     }
 }
 
+fn parse_bitfield_ty_path(field: &BFFieldAttr) -> Result<Path, Error> {
+    let (ty, span) = &field.ty;
+
+    syn::parse_str(ty).map_err(|_| {
+        let err_str = format!("Bitfield ty `{}` is not a valid type path", ty);
+
+        Error::new(*span, err_str)
+    })
+}
+
+fn parse_bitfield_name(field: &BFFieldAttr) -> Result<Ident, Error> {
+    let (name, span) = &field.name;
+
+    syn::parse_str(name).map_err(|_| {
+        let err_str = format!("Bitfield name `{}` is not a valid identifier", name);
+
+        Error::new(*span, err_str)
+    })
+}
+
+/// Returns the `#[cfg(...)]` attr to put on the generated items of a bitfield
+/// with a `cfg` param, or nothing
+fn cfg_attr(field: &BFFieldAttr) -> Result<proc_macro2::TokenStream, Error> {
+    let (cfg, span) = match &field.cfg {
+        Some(cfg) => cfg,
+        None => return Ok(quote! {}),
+    };
+    let predicate: proc_macro2::TokenStream = syn::parse_str(cfg)
+        .map_err(|_| Error::new(*span, "cfg param must be a valid cfg predicate"))?;
+
+    Ok(quote! { #[cfg(#predicate)] })
+}
+
+/// Parses the `enum_ty` param of a bitfield, if it has one
+fn enum_ty(field: &BFFieldAttr) -> Result<Option<Path>, Error> {
+    let (enum_ty, span) = match &field.enum_ty {
+        Some(enum_ty) => enum_ty,
+        None => return Ok(None),
+    };
+
+    syn::parse_str(enum_ty).map(Some).map_err(|_| {
+        let err_str = format!("Bitfield enum_ty `{}` is not a valid type path", enum_ty);
+
+        Error::new(*span, err_str)
+    })
+}
+
 #[proc_macro_derive(BitfieldStruct, attributes(bitfield))]
 pub fn bitfield_struct(input: TokenStream) -> TokenStream {
     let struct_item = parse_macro_input!(input as ItemStruct);
 
     match bitfield_struct_impl(struct_item) {
-        Ok(ts) => ts,
+        Ok(ts) => ts.into(),
         Err(error) => error.to_compile_error().into(),
     }
 }
 
-fn bitfield_struct_impl(struct_item: ItemStruct) -> Result<TokenStream, Error> {
-    // REVIEW: Should we throw a compile error if bit ranges on a single field overlap?
-    let struct_ident = struct_item.ident;
-    let fields = match struct_item.fields {
-        Fields::Named(named_fields) => named_fields.named,
-        Fields::Unnamed(_) => {
-            let err_str =
-                "Unnamed struct fields are not currently supported but may be in the future.";
-            let span = struct_ident.span();
+#[proc_macro_derive(BitfieldEnum)]
+pub fn bitfield_enum(input: TokenStream) -> TokenStream {
+    let enum_item = parse_macro_input!(input as ItemEnum);
 
-            return Err(Error::new(span, err_str));
-        }
-        Fields::Unit => {
-            let err_str = "Cannot create bitfield struct out of struct with no fields";
-            let span = struct_ident.span();
+    match bitfield_enum_impl(enum_item) {
+        Ok(ts) => ts.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
 
-            return Err(Error::new(span, err_str));
-        }
-    };
-    let bitfields: Result<Vec<BFFieldAttr>, Error> =
-        fields.iter().flat_map(filter_and_parse_fields).collect();
-    let bitfields = bitfields?;
-    let field_types: Vec<_> = bitfields.iter().map(parse_bitfield_ty_path).collect();
-    let field_types_return = &field_types;
-    let field_types_typedef = &field_types;
-    let field_types_setter_arg = &field_types;
-    let method_names: Vec<_> = bitfields
-        .iter()
-        .map(|field| Ident::new(&field.name, Span::call_site().into()))
-        .collect();
-    let field_names: Vec<_> = bitfields.iter().map(|field| &field.field_name).collect();
-    let field_names_setters = &field_names;
-    let field_names_getters = &field_names;
-    let method_name_setters: Vec<_> = method_names
+fn bitfield_enum_impl(enum_item: ItemEnum) -> Result<proc_macro2::TokenStream, Error> {
+    let enum_ident = enum_item.ident;
+
+    if let Some(variant) = enum_item
+        .variants
         .iter()
-        .map(|field_ident| {
-            let span = Span::call_site().into();
-            let setter_name = &format!("set_{}", field_ident);
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        let err_str = "BitfieldEnum can only be derived for fieldless enums";
 
-            Ident::new(setter_name, span)
-        })
-        .collect();
-    let field_bit_info: Result<Vec<_>, Error> = bitfields
+        return Err(Error::new(variant.span(), err_str));
+    }
+
+    let variants: Vec<_> = enum_item
+        .variants
         .iter()
-        .map(|field| {
-            let bit_string = &field.bits.0;
-            let nums: Vec<_> = bit_string.split("..=").collect();
-            let err_str = "bits param must be in the format \"1..=4\"";
+        .map(|variant| &variant.ident)
+        .collect();
 
-            if nums.len() != 2 {
-                return Err(Error::new(field.bits.1, err_str));
+    let q = quote! {
+        #[automatically_derived]
+        impl c2rust_bitfields::BitfieldEnum for #enum_ident {
+            const HAS_NEGATIVE_DISCRIMINANT: bool = false #(|| (#enum_ident::#variants as i128) < 0)*;
+
+            fn to_discriminant(self) -> i128 {
+                self as i128
             }
 
-            let lhs = nums[0].parse::<usize>();
-            let rhs = nums[1].parse::<usize>();
+            fn from_discriminant(discriminant: i128) -> Option<Self> {
+                #(
+                    if discriminant == #enum_ident::#variants as i128 {
+                        return Some(#enum_ident::#variants);
+                    }
+                )*
 
-            let (lhs, rhs) = match (lhs, rhs) {
-                (Err(_), _) | (_, Err(_)) => return Err(Error::new(field.bits.1, err_str)),
-                (Ok(lhs), Ok(rhs)) => (lhs, rhs),
-            };
+                None
+            }
+        }
+    };
 
-            Ok(quote! { (#lhs, #rhs) })
-        })
-        .collect();
-    let field_bit_info = field_bit_info?;
-    let field_bit_info_setters = &field_bit_info;
-    let field_bit_info_getters = &field_bit_info;
+    Ok(q)
+}
 
-    // TODO: Method visibility determined by struct field visibility?
-    let q = quote! {
-        #[automatically_derived]
-        impl #struct_ident {
-            #(
-                /// This method allows you to write to a bitfield with a value
-                pub fn #method_name_setters(&mut self, int: #field_types_setter_arg) {
-                    use c2rust_bitfields::FieldType;
+/// Whether a bitfield `ty` is `bool` or one of the integer types which
+/// `FieldType` is implemented for, possibly through a C type alias such as
+/// `libc::c_ulong`. Any other `ty` has a `FieldType` impl of its own.
+fn is_primitive_ty(ty: &Path) -> bool {
+    let segment = match ty.segments.last() {
+        Some(segment) if segment.arguments.is_empty() => segment,
+        _ => return false,
+    };
 
-                    let field = &mut self.#field_names_setters;
-                    let (lhs_bit, rhs_bit) = #field_bit_info_setters;
-                    int.set_field(field, (lhs_bit, rhs_bit));
-                }
+    matches!(
+        segment.ident.to_string().as_str(),
+        "bool"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "c_char"
+            | "c_schar"
+            | "c_uchar"
+            | "c_short"
+            | "c_ushort"
+            | "c_int"
+            | "c_uint"
+            | "c_long"
+            | "c_ulong"
+            | "c_longlong"
+            | "c_ulonglong"
+    )
+}
+
+/// Returns the integer type wrapped by a `NonZero*` bitfield ty, such as the
+/// `u8` of `core::num::NonZeroU8`
+fn non_zero_int_ty(ty: &Path) -> Option<Ident> {
+    let ty_name = ty.segments.last()?.ident.to_string();
+    let int_ty = ty_name.strip_prefix("NonZero")?.to_lowercase();
 
-                /// This method allows you to read from a bitfield to a value
-                pub fn #method_names(&self) -> #field_types_return {
-                    use c2rust_bitfields::FieldType;
+    match int_ty.as_str() {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" => {
+            Some(Ident::new(&int_ty, Span::call_site()))
+        }
+        _ => None,
+    }
+}
 
-                    type IntType = #field_types_typedef;
+/// Returns the integer type of a scalar backing member, such as the `u32` of
+/// a `#[repr(transparent)]` register wrapper. `None` means the member is
+/// treated as a byte array.
+fn scalar_backing_ty(ty: &Type) -> Option<&Ident> {
+    let path = match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+        _ => return None,
+    };
+    let ident = path.get_ident()?;
 
-                    let field = &self.#field_names_getters;
-                    let (lhs_bit, rhs_bit) = #field_bit_info_getters;
-                    <IntType as FieldType>::get_field(field, (lhs_bit, rhs_bit))
-                }
-            )*
+    match ident.to_string().as_str() {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" => {
+            Some(ident)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the wrapped type of a `Cell` backing member, such as the
+/// `[u8; 2]` of a `Cell<[u8; 2]>`
+fn cell_inner_ty(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Cell" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner_ty) => Some(inner_ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the size in bytes of a backing member, if it is a scalar integer
+/// or a `[u8; N]` array with a literal length
+fn backing_byte_len(ty: &Type) -> Option<usize> {
+    if let Some(scalar_ty) = scalar_backing_ty(ty) {
+        let bits: usize = scalar_ty.to_string()[1..].parse().ok()?;
+
+        return Some(bits / 8);
+    }
+
+    match ty {
+        Type::Array(type_array) => match &type_array.len {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Int(len), ..
+            }) => len.base10_parse().ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns an expression for an all zero backing member. Byte arrays and
+/// scalars are zeroed directly, since arrays longer than 32 bytes don't
+/// implement `Default`.
+fn zeroed_backing(ty: &Type, core_path: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if let Some(inner_ty) = cell_inner_ty(ty) {
+        let inner = zeroed_backing(inner_ty, core_path);
+
+        return quote! { #core_path::cell::Cell::new(#inner) };
+    }
+
+    match ty {
+        Type::Array(type_array) => {
+            let len = &type_array.len;
+
+            quote! { [0; #len] }
+        }
+        _ if scalar_backing_ty(ty).is_some() => quote! { 0 },
+        _ => quote! { #core_path::default::Default::default() },
+    }
+}
+
+/// Returns an expression for a backing member with every bit set, the
+/// counterpart of `zeroed_backing`
+fn ones_backing(ty: &Type, core_path: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if let Some(inner_ty) = cell_inner_ty(ty) {
+        let inner = ones_backing(inner_ty, core_path);
+
+        return quote! { #core_path::cell::Cell::new(#inner) };
+    }
+
+    match ty {
+        Type::Array(type_array) => {
+            let len = &type_array.len;
+
+            quote! { [0xFF; #len] }
         }
+        _ if scalar_backing_ty(ty).is_some() => quote! { !0 },
+        _ => quote! { #core_path::default::Default::default() },
+    }
+}
+
+/// Returns the smallest unsigned integer type able to hold `byte_len` bytes
+fn raw_int_ty(byte_len: usize) -> Option<Ident> {
+    let ty = match byte_len {
+        1 => "u8",
+        2 => "u16",
+        3..=4 => "u32",
+        5..=8 => "u64",
+        9..=16 => "u128",
+        _ => return None,
     };
 
-    Ok(q.into())
+    Some(Ident::new(ty, Span::call_site()))
+}
+
+/// Generates an expression reading the whole backing member as a
+/// little endian integer, along with the integer's type
+fn read_raw_member(
+    field_name: &Ident,
+    field_ty: &Type,
+    core_path: &proc_macro2::TokenStream,
+) -> Option<(Ident, proc_macro2::TokenStream)> {
+    if let Some(scalar_ty) = scalar_backing_ty(field_ty) {
+        return Some((scalar_ty.clone(), quote! { self.#field_name }));
+    }
+
+    let byte_len = backing_byte_len(field_ty)?;
+    let raw_ty = raw_int_ty(byte_len)?;
+    let read = quote! {{
+        let mut bytes = [0u8; #core_path::mem::size_of::<#raw_ty>()];
+        bytes[..#byte_len].copy_from_slice(&self.#field_name[..]);
+        #raw_ty::from_le_bytes(bytes)
+    }};
+
+    Some((raw_ty, read))
+}
+
+/// Generates the helpers which operate on a whole backing member rather
+/// than on an individual bitfield, as asked for by the `member_matches` and
+/// `member_zero` struct params
+fn generate_member_helpers(
+    field: &Field,
+    matches: bool,
+    zero: bool,
+    core_path: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    // Only named members back bitfields which get member helpers
+    let field_name = match &field.ident {
+        Some(field_name) => field_name,
+        None => return quote! {},
+    };
+    let zero_helpers = if zero {
+        generate_zero_helpers(field_name, &field.ty, core_path)
+    } else {
+        quote! {}
+    };
+    let read_raw = if matches {
+        read_raw_member(field_name, &field.ty, core_path)
+    } else {
+        None
+    };
+    let (raw_ty, read_raw) = match read_raw {
+        Some(raw) => raw,
+        None => return zero_helpers,
+    };
+    let matches_name = Ident::new(&format!("{}_matches", field_name), Span::call_site());
+
+    quote! {
+        /// This method checks whether the bits of the backing member selected
+        /// by `mask` are equal to `value`
+        pub fn #matches_name(&self, mask: #raw_ty, value: #raw_ty) -> bool {
+            let raw: #raw_ty = #read_raw;
+
+            raw & mask == value
+        }
+
+        #zero_helpers
+    }
+}
+
+/// Generates a getter named after a backing member which reads all of the
+/// member's bitfields from a single read of it, as a tuple in declaration
+/// order. Members backing fewer than two bitfields, or a bitfield sharing
+/// the member's name, don't get one.
+fn generate_grouped_getter(
+    field: &Field,
+    bitfields: &[BFFieldAttr],
+) -> Result<proc_macro2::TokenStream, Error> {
+    let field_name = match &field.ident {
+        Some(field_name) => field_name,
+        None => return Ok(quote! {}),
+    };
+    let member = Member::Named(field_name.clone());
+    let grouped: Vec<_> = bitfields
+        .iter()
+        .filter(|bitfield| bitfield.field_name == member && bitfield.cfg.is_none())
+        .collect();
+
+    if grouped.len() < 2
+        || grouped
+            .iter()
+            .any(|bitfield| field_name == &bitfield.name.0)
+    {
+        return Ok(quote! {});
+    }
+
+    let mut tys = Vec::with_capacity(grouped.len());
+    let mut values = Vec::with_capacity(grouped.len());
+
+    for bitfield in grouped {
+        let field_type = parse_bitfield_ty_path(bitfield)?;
+        let (lhs, rhs) = parse_bit_range(bitfield)?;
+
+        if let Some(int_ty) = non_zero_int_ty(&field_type) {
+            tys.push(quote! { Option<#field_type> });
+            values.push(quote! {
+                <#field_type>::new(<#int_ty as FieldType>::get_field(field, (#lhs, #rhs)))
+            });
+        } else if let Some(enum_ty) = enum_ty(bitfield)? {
+            tys.push(quote! { Option<#enum_ty> });
+            values.push(quote! {
+                <#enum_ty as c2rust_bitfields::BitfieldEnum>::from_discriminant(
+                    <#field_type as FieldType>::get_field(field, (#lhs, #rhs)) as i128,
+                )
+            });
+        } else {
+            tys.push(quote! { #field_type });
+            values.push(quote! { <#field_type as FieldType>::get_field(field, (#lhs, #rhs)) });
+        }
+    }
+
+    let cell_ty = cell_inner_ty(&field.ty);
+    let scalar_ty = scalar_backing_ty(cell_ty.unwrap_or(&field.ty));
+    // `check_endian` ensures a member's bitfields agree on its endianness
+    let big_endian = bitfields
+        .iter()
+        .any(|bitfield| bitfield.field_name == member && bitfield.big_endian.is_some());
+    let read_field = read_backing_field(
+        &quote! { self },
+        &member,
+        scalar_ty,
+        cell_ty.is_some(),
+        big_endian,
+    );
+
+    Ok(quote! {
+        /// This method reads every bitfield of the backing member at once,
+        /// returning them in declaration order just as their getters would
+        #[allow(clippy::unnecessary_cast)]
+        pub fn #field_name(&self) -> (#(#tys),*) {
+            use c2rust_bitfields::FieldType;
+
+            #read_field
+
+            (#(#values),*)
+        }
+    })
+}
+
+/// Generates `clear_<member>` and `<member>_is_zero`, which zero and test a
+/// whole integer or `[u8; N]` backing member at once rather than bitfield by
+/// bitfield
+fn generate_zero_helpers(
+    field_name: &Ident,
+    field_ty: &Type,
+    core_path: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let cell_ty = cell_inner_ty(field_ty);
+    let backing_ty = cell_ty.unwrap_or(field_ty);
+
+    if backing_byte_len(backing_ty).is_none() {
+        return quote! {};
+    }
+
+    let span = Span::call_site();
+    let clear_name = Ident::new(&format!("clear_{}", field_name), span);
+    let is_zero_name = Ident::new(&format!("{}_is_zero", field_name), span);
+    let zeroed = zeroed_backing(backing_ty, core_path);
+    let (receiver, clear, read) = match cell_ty {
+        Some(_) => (
+            quote! { &self },
+            quote! { self.#field_name.set(#zeroed); },
+            quote! { self.#field_name.get() },
+        ),
+        None => (
+            quote! { &mut self },
+            quote! { self.#field_name = #zeroed; },
+            quote! { self.#field_name },
+        ),
+    };
+    let is_zero = match scalar_backing_ty(backing_ty) {
+        Some(_) => quote! { #read == 0 },
+        None => quote! { #read.iter().all(|&byte| byte == 0) },
+    };
+
+    quote! {
+        /// This method zeroes every bit of the backing member, clearing all
+        /// of its bitfields at once
+        pub fn #clear_name(#receiver) {
+            #clear
+        }
+
+        /// This method checks whether every bit of the backing member is zero
+        pub fn #is_zero_name(&self) -> bool {
+            #is_zero
+        }
+    }
+}
+
+fn is_repr_transparent(attrs: &[Attribute]) -> bool {
+    let is_transparent = |nested: &NestedMeta| match nested {
+        NestedMeta::Meta(Meta::Path(path)) => path.is_ident("transparent"),
+        _ => false,
+    };
+
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(meta_list) => meta_list.nested.iter().any(is_transparent),
+            _ => false,
+        })
+}
+
+/// Returns the max field alignment of a `#[repr(C)]` struct, `Some(N)` for
+/// `packed(N)` or `Some(1)` for `packed`, or an error for other reprs
+fn repr_c_packing(attrs: &[Attribute], span: Span) -> Result<Option<usize>, Error> {
+    let mut is_c = false;
+    let mut packing = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        let meta_list = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list,
+            _ => continue,
+        };
+
+        for nested in meta_list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("C") => is_c = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("packed") => packing = Some(1),
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("packed") => {
+                    packing = match list.nested.first() {
+                        Some(NestedMeta::Lit(Lit::Int(int))) => Some(int.base10_parse()?),
+                        _ => return Err(Error::new(list.span(), "Expected `packed(N)`")),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !is_c {
+        return Err(Error::new(
+            span,
+            "offsets param requires a `#[repr(C)]` struct",
+        ));
+    }
+
+    Ok(packing)
+}
+
+/// Generates compile time assertions that each member named in the `offsets`
+/// struct param lies at the given offset, and that the struct is `size`
+/// bytes, as reported for the C struct. Offsets are worked out from the
+/// members' sizes and alignments the way `repr(C)` lays them out, so a member
+/// type which doesn't match C's turns into a compile error.
+fn generate_layout_assertions(
+    struct_item: &ItemStruct,
+    offsets: Option<&(String, proc_macro2::Span)>,
+    size: Option<&(String, proc_macro2::Span)>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let struct_ident = &struct_item.ident;
+    let size_check = match size {
+        Some((size, span)) => {
+            let size: usize = size
+                .trim()
+                .parse()
+                .map_err(|_| Error::new(*span, "size param must be a number of bytes"))?;
+            let err_str = format!("`{}` isn't {} bytes, as it is in C", struct_ident, size);
+
+            quote! {
+                assert!(#core_path::mem::size_of::<#struct_ident>() == #size, #err_str);
+            }
+        }
+        None => quote! {},
+    };
+    let (offsets, span) = match offsets {
+        Some((offsets, span)) => (offsets, *span),
+        None => return Ok(quote! { const _: () = { #size_check }; }),
+    };
+    let packing = repr_c_packing(&struct_item.attrs, span)?;
+    let mut expected = Vec::new();
+
+    for entry in offsets.split(',').filter(|entry| !entry.trim().is_empty()) {
+        let err = || {
+            let err_str = format!(
+                "Expected `member = offset` in offsets param, found `{}`",
+                entry
+            );
+
+            Error::new(span, err_str)
+        };
+        let (member, offset) = entry.split_once('=').ok_or_else(err)?;
+        let member: Member = syn::parse_str(member.trim()).map_err(|_| err())?;
+        let offset: usize = offset.trim().parse().map_err(|_| err())?;
+
+        expected.push((member, offset));
+    }
+
+    let mut checks = Vec::new();
+
+    for (index, field) in struct_item.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let field_ty = &field.ty;
+        let align = match packing {
+            Some(packing) => quote! {{
+                let align = #core_path::mem::align_of::<#field_ty>();
+
+                if align < #packing { align } else { #packing }
+            }},
+            None => quote! { #core_path::mem::align_of::<#field_ty>() },
+        };
+
+        checks.push(quote! {
+            offset = align_up(offset, #align);
+        });
+
+        if let Some(position) = expected.iter().position(|(name, _)| name == &member) {
+            let (_, expected_offset) = expected.remove(position);
+            let err_str = format!(
+                "`{}` isn't at offset {} of `{}`, as it is in C",
+                quote! { #member },
+                expected_offset,
+                struct_ident
+            );
+
+            checks.push(quote! {
+                assert!(offset == #expected_offset, #err_str);
+            });
+        }
+
+        checks.push(quote! {
+            offset += #core_path::mem::size_of::<#field_ty>();
+        });
+    }
+
+    if let Some((member, _)) = expected.first() {
+        let err_str = format!(
+            "offsets param names unknown member `{}`",
+            quote! { #member }
+        );
+
+        return Err(Error::new(span, err_str));
+    }
+
+    Ok(quote! {
+        #[allow(unused_assignments)]
+        const _: () = {
+            const fn align_up(offset: usize, align: usize) -> usize {
+                (offset + align - 1) / align * align
+            }
+
+            let mut offset = 0;
+
+            #(#checks)*
+            #size_check
+        };
+    })
+}
+
+/// Generates `PartialOrd` and `Ord` impls comparing structs by the logical
+/// value of the bitfield named in `ord_by`. `PartialEq` and `Eq` are left
+/// to the user, who should keep them consistent with this ordering.
+fn generate_ordering(
+    struct_ident: &Ident,
+    ord_by: &(String, proc_macro2::Span),
+    bitfields: &[BFFieldAttr],
+    accessor_trait: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let (ord_by, span) = ord_by;
+
+    if !bitfields.iter().any(|bitfield| &bitfield.name.0 == ord_by) {
+        let err_str = format!("ord_by names unknown bitfield `{}`", ord_by);
+
+        return Err(Error::new(*span, err_str));
+    }
+
+    let method_name = Ident::new(ord_by, *span);
+    let lhs = call_accessor(accessor_trait, quote! { self }, &method_name, quote! {});
+    let rhs = call_accessor(accessor_trait, quote! { other }, &method_name, quote! {});
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #core_path::cmp::PartialOrd for #struct_ident {
+            fn partial_cmp(&self, other: &Self) -> Option<#core_path::cmp::Ordering> {
+                Some(#core_path::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        #[automatically_derived]
+        impl #core_path::cmp::Ord for #struct_ident {
+            fn cmp(&self, other: &Self) -> #core_path::cmp::Ordering {
+                #core_path::cmp::Ord::cmp(&#lhs, &#rhs)
+            }
+        }
+    })
+}
+
+/// Whether a member backs bitfields, or carries `#[bitfield(...)]` attrs
+/// which only reserve padding. Byte spans alone don't make a backing member.
+fn is_backing_member(member: &Field, member_name: &Member, bitfields: &[BFFieldAttr]) -> bool {
+    let has_attrs = member.attrs.iter().any(|attr| {
+        attr.path.segments.last().unwrap().ident == "bitfield"
+            && !matches!(parse_byte_span_attr(attr), Ok(Some(_)))
+    });
+
+    has_attrs
+        || bitfields
+            .iter()
+            .any(|field| &field.field_name == member_name)
+}
+
+/// Generates a `Display` impl formatting each bitfield, and each named member
+/// not backing any bitfield, as `name=value` in declaration order, separated
+/// by spaces. Enum bitfields show their discriminant, or `?` when the
+/// bitfield doesn't hold one, and zero NonZero bitfields show `0`.
+fn generate_display(
+    struct_ident: &Ident,
+    fields: &Fields,
+    bitfields: &[BFFieldAttr],
+    hex: bool,
+    accessor_trait: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let value_fmt = if hex { "{}{}={:#x}" } else { "{}{}={}" };
+    let mut entries = Vec::with_capacity(bitfields.len());
+
+    for (index, member) in fields.iter().enumerate() {
+        let member_name = match &member.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let is_backing = is_backing_member(member, &member_name, bitfields);
+
+        // Plain members are formatted directly and so must implement
+        // `Display`, or `LowerHex` for `display_hex`
+        if let (Member::Named(ident), false) = (&member_name, is_backing) {
+            let name = ident.to_string();
+
+            entries.push(quote! {
+                write!(f, #value_fmt, separator, #name, self.#ident)?;
+                separator = " ";
+            });
+            continue;
+        }
+
+        for field in bitfields
+            .iter()
+            .filter(|field| field.field_name == member_name)
+        {
+            entries.push(generate_display_entry(field, hex, accessor_trait)?);
+        }
+    }
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #core_path::fmt::Display for #struct_ident {
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn fmt(&self, f: &mut #core_path::fmt::Formatter<'_>) -> #core_path::fmt::Result {
+                let mut separator = "";
+
+                #(#entries)*
+
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Generates a `Debug` impl showing each bitfield as read by its getter, and
+/// each named member not backing any bitfield, in declaration order, in place
+/// of the raw bytes `#[derive(Debug)]` would show. Enum bitfields therefore
+/// need their enum to implement `Debug`.
+fn generate_debug(
+    struct_ident: &Ident,
+    fields: &Fields,
+    bitfields: &[BFFieldAttr],
+    accessor_trait: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let struct_name = struct_ident.to_string();
+    let mut entries = Vec::with_capacity(bitfields.len());
+
+    for (index, member) in fields.iter().enumerate() {
+        let member_name = match &member.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+
+        if let (Member::Named(ident), false) = (
+            &member_name,
+            is_backing_member(member, &member_name, bitfields),
+        ) {
+            let name = ident.to_string();
+
+            entries.push(quote! { debug.field(#name, &self.#ident); });
+            continue;
+        }
+
+        for field in bitfields
+            .iter()
+            .filter(|field| field.field_name == member_name)
+        {
+            let method_name = parse_bitfield_name(field)?;
+            let value = call_accessor(accessor_trait, quote! { self }, &method_name, quote! {});
+            let name = &field.name.0;
+            let cfg_attr = cfg_attr(field)?;
+
+            entries.push(quote! {
+                #cfg_attr
+                {
+                    debug.field(#name, &#value);
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #core_path::fmt::Debug for #struct_ident {
+            fn fmt(&self, f: &mut #core_path::fmt::Formatter<'_>) -> #core_path::fmt::Result {
+                let mut debug = f.debug_struct(#struct_name);
+
+                #(#entries)*
+
+                debug.finish()
+            }
+        }
+    })
+}
+
+fn generate_display_entry(
+    field: &BFFieldAttr,
+    hex: bool,
+    accessor_trait: Option<&Path>,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let field_type = parse_bitfield_ty_path(field)?;
+    let method_name = parse_bitfield_name(field)?;
+    let value = call_accessor(accessor_trait, quote! { self }, &method_name, quote! {});
+    let cfg_attr = cfg_attr(field)?;
+    let value_fmt = if hex && !field_type.is_ident("bool") {
+        format!("{{}}{}={{:#x}}", field.name.0)
+    } else {
+        format!("{{}}{}={{}}", field.name.0)
+    };
+
+    let write_value = if let Some(enum_ty) = enum_ty(field)? {
+        let unknown_fmt = format!("{{}}{}=?", field.name.0);
+
+        quote! {
+            match #value {
+                Some(variant) => write!(
+                    f,
+                    #value_fmt,
+                    separator,
+                    <#enum_ty as c2rust_bitfields::BitfieldEnum>::to_discriminant(variant),
+                )?,
+                None => write!(f, #unknown_fmt, separator)?,
+            }
+        }
+    } else if non_zero_int_ty(&field_type).is_some() {
+        quote! {
+            write!(f, #value_fmt, separator, #value.map_or(0, |value| value.get()))?;
+        }
+    } else {
+        quote! {
+            write!(f, #value_fmt, separator, #value)?;
+        }
+    };
+
+    Ok(quote! {
+        #cfg_attr
+        {
+            #write_value
+            separator = " ";
+        }
+    })
+}
+
+/// Generates a `BitfieldDiff` impl comparing the bits of each bitfield, so
+/// that enum bitfields which don't hold a discriminant still compare by value
+fn generate_diff(
+    struct_ident: &Ident,
+    bitfields: &[BFFieldAttr],
+) -> Result<proc_macro2::TokenStream, Error> {
+    let mut entries = Vec::with_capacity(bitfields.len());
+
+    for field in bitfields {
+        let field_type = parse_bitfield_ty_path(field)?;
+        let int_ty = match non_zero_int_ty(&field_type) {
+            Some(int_ty) => quote! { #int_ty },
+            None => quote! { #field_type },
+        };
+        let name = &field.name.0;
+        let (lhs, rhs) = parse_bit_range(field)?;
+        let cfg_attr = cfg_attr(field)?;
+        let cell_ty = cell_inner_ty(&field.field_ty);
+        let scalar_ty = scalar_backing_ty(cell_ty.unwrap_or(&field.field_ty));
+        let read_self = read_backing_field(
+            &quote! { self },
+            &field.field_name,
+            scalar_ty,
+            cell_ty.is_some(),
+            field.big_endian.is_some(),
+        );
+        let read_other = read_backing_field(
+            &quote! { other },
+            &field.field_name,
+            scalar_ty,
+            cell_ty.is_some(),
+            field.big_endian.is_some(),
+        );
+
+        entries.push(quote! {
+            #cfg_attr
+            {
+                type IntType = #int_ty;
+
+                let bit_range = (#lhs, #rhs);
+                let value = {
+                    #read_self
+                    <IntType as FieldType>::get_field(field, bit_range)
+                };
+                let other_value = {
+                    #read_other
+                    <IntType as FieldType>::get_field(field, bit_range)
+                };
+
+                if value != other_value {
+                    f(#name);
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl c2rust_bitfields::BitfieldDiff for #struct_ident {
+            #[allow(unused_imports, unused_mut, unused_variables)]
+            fn for_each_diff<F: FnMut(&'static str)>(&self, other: &Self, mut f: F) {
+                use c2rust_bitfields::FieldType;
+
+                #(#entries)*
+            }
+        }
+    })
+}
+
+/// Generates a `dump` method returning the name and value of every bitfield
+/// as an array, which needs no allocation. Bitfields with a `cfg` param are
+/// left out so that the array's length doesn't depend on the cfg.
+fn generate_dump(bitfields: &[BFFieldAttr]) -> Result<proc_macro2::TokenStream, Error> {
+    let mut entries = Vec::with_capacity(bitfields.len());
+
+    for field in bitfields.iter().filter(|field| field.cfg.is_none()) {
+        let field_type = parse_bitfield_ty_path(field)?;
+        let int_ty = match non_zero_int_ty(&field_type) {
+            Some(int_ty) => quote! { #int_ty },
+            None => quote! { #field_type },
+        };
+        let name = &field.name.0;
+        let (lhs, rhs) = parse_bit_range(field)?;
+        let cell_ty = cell_inner_ty(&field.field_ty);
+        let scalar_ty = scalar_backing_ty(cell_ty.unwrap_or(&field.field_ty));
+        let read_field = read_backing_field(
+            &quote! { self },
+            &field.field_name,
+            scalar_ty,
+            cell_ty.is_some(),
+            field.big_endian.is_some(),
+        );
+
+        entries.push(quote! {
+            (#name, {
+                #read_field
+                <#int_ty as FieldType>::get_field(field, (#lhs, #rhs)) as u64
+            })
+        });
+    }
+
+    let len = entries.len();
+
+    Ok(quote! {
+        /// This method returns the name and value of each bitfield, in
+        /// declaration order. Signed values are sign extended to `u64`.
+        #[allow(clippy::unnecessary_cast)]
+        pub fn dump(&self) -> [(&'static str, u64); #len] {
+            use c2rust_bitfields::FieldType;
+
+            [#(#entries),*]
+        }
+    })
+}
+
+/// Generates `validate_layout`, a test and debug build self-check which
+/// writes boundary values to each bitfield of an all-zero and an all-one
+/// struct through its setter, checking that its getter reads them back and
+/// that no bit of a backing member outside the bitfield changed
+fn generate_layout_validation(
+    fields: &Fields,
+    bitfields: &[BFFieldAttr],
+    accessor_trait: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let mut zero_inits = Vec::new();
+    let mut one_inits = Vec::new();
+    // The name of each backing member, and an expression for its bytes as
+    // its bitfields' bit ranges count them
+    let mut members = Vec::new();
+
+    for (index, member) in fields.iter().enumerate() {
+        let member_name = match &member.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        // Bitfields carry the resolved type of members with auto storage
+        let backing = bitfields
+            .iter()
+            .find(|field| field.field_name == member_name);
+        let (zeroed, ones) = match backing {
+            Some(field) => (
+                zeroed_backing(&field.field_ty, core_path),
+                ones_backing(&field.field_ty, core_path),
+            ),
+            None => {
+                let default = quote! { #core_path::default::Default::default() };
+
+                (default.clone(), default)
+            }
+        };
+
+        match &member.ident {
+            Some(ident) => {
+                zero_inits.push(quote! { #ident: #zeroed });
+                one_inits.push(quote! { #ident: #ones });
+            }
+            None => {
+                zero_inits.push(zeroed);
+                one_inits.push(ones);
+            }
+        }
+
+        if let Some(field) = backing {
+            let cell_ty = cell_inner_ty(&field.field_ty);
+            let scalar_ty = scalar_backing_ty(cell_ty.unwrap_or(&field.field_ty));
+            let read_field = read_backing_field(
+                &quote! { __c2rust_this },
+                &member_name,
+                scalar_ty,
+                cell_ty.is_some(),
+                field.big_endian.is_some(),
+            );
+            let name = match &member_name {
+                Member::Named(ident) => ident.to_string(),
+                Member::Unnamed(index) => index.index.to_string(),
+            };
+
+            members.push((member_name, name, read_field));
+        }
+    }
+
+    let construct = |inits: &[proc_macro2::TokenStream]| match fields {
+        Fields::Named(_) => quote! { Self { #(#inits),* } },
+        Fields::Unnamed(_) => quote! { Self(#(#inits),*) },
+        Fields::Unit => quote! { Self },
+    };
+    let zeroed = construct(&zero_inits);
+    let ones = construct(&one_inits);
+    let (this_ref, this_mut) = match accessor_trait {
+        Some(_) => (quote! { &__c2rust_this }, quote! { &mut __c2rust_this }),
+        None => (quote! { __c2rust_this }, quote! { __c2rust_this }),
+    };
+    let mut checks = Vec::with_capacity(bitfields.len());
+
+    for field in bitfields {
+        let field_type = parse_bitfield_ty_path(field)?;
+        let non_zero_ty = non_zero_int_ty(&field_type);
+
+        // There are no boundary values to write to bitfields of other tys,
+        // which have a `FieldType` impl of their own
+        if !is_primitive_ty(&field_type) && non_zero_ty.is_none() {
+            continue;
+        }
+
+        let method_name = parse_bitfield_name(field)?;
+        let setter_name = Ident::new(&format!("set_{}", method_name), Span::call_site());
+        let name = &field.name.0;
+        let (lhs, rhs) = parse_bit_range(field)?;
+        let bit_width = rhs - lhs + 1;
+        let cfg_attr = cfg_attr(field)?;
+        let enum_ty = enum_ty(field)?;
+        // Enum and non-zero bitfields are written from plain integers, which
+        // are skipped when they aren't a valid value of the bitfield
+        let int_ty = match &non_zero_ty {
+            Some(int_ty) => quote! { #int_ty },
+            None => quote! { #field_type },
+        };
+        let values = if field_type.is_ident("bool") {
+            quote! { [false, true] }
+        } else {
+            quote! {
+                [
+                    0 as IntType,
+                    FieldType::saturate_to_bits(&(1 as IntType), #bit_width),
+                    FieldType::saturate_to_bits(&<IntType>::MAX, #bit_width),
+                    FieldType::saturate_to_bits(&<IntType>::MIN, #bit_width),
+                ]
+            }
+        };
+        let get = call_accessor(accessor_trait, this_ref.clone(), &method_name, quote! {});
+        let (value, mismatch) = match (&enum_ty, &non_zero_ty) {
+            (Some(enum_ty), _) => (
+                quote! {
+                    match <#enum_ty as BitfieldEnum>::from_discriminant(int as i128) {
+                        Some(value) => value,
+                        None => continue,
+                    }
+                },
+                quote! {
+                    #get.map(BitfieldEnum::to_discriminant)
+                        != Some(BitfieldEnum::to_discriminant(value))
+                },
+            ),
+            (None, Some(_)) => (
+                quote! {
+                    match <#field_type>::new(int) {
+                        Some(value) => value,
+                        None => continue,
+                    }
+                },
+                quote! { #get != Some(value) },
+            ),
+            (None, None) => (quote! { int }, quote! { #get != value }),
+        };
+        let set = call_accessor(
+            accessor_trait,
+            this_mut.clone(),
+            &setter_name,
+            quote! { value },
+        );
+        let mut befores = Vec::with_capacity(members.len());
+        let mut member_checks = Vec::with_capacity(members.len());
+
+        for (index, (member_name, member, read_field)) in members.iter().enumerate() {
+            let before = Ident::new(&format!("__c2rust_before{}", index), Span::call_site());
+            let bit_range = if member_name == &field.field_name {
+                quote! { Some((#lhs, #rhs)) }
+            } else {
+                quote! { None }
+            };
+
+            befores.push(quote! {
+                let #before = {
+                    #read_field
+                    *field
+                };
+            });
+            member_checks.push(quote! {
+                {
+                    #read_field
+                    c2rust_bitfields::check_untouched_bits(
+                        #name,
+                        #member,
+                        &#before,
+                        field,
+                        #bit_range,
+                    )?;
+                }
+            });
+        }
+
+        checks.push(quote! {
+            #cfg_attr
+            {
+                type IntType = #int_ty;
+
+                for &all_ones in &[false, true] {
+                    for &int in &#values {
+                        let value = #value;
+                        let mut __c2rust_this = if all_ones { #ones } else { #zeroed };
+
+                        #(#befores)*
+
+                        #set;
+
+                        if #mismatch {
+                            return Err(c2rust_bitfields::LayoutError::new(#name, None));
+                        }
+
+                        #(#member_checks)*
+                    }
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        /// This method writes the zero, one, largest and smallest values
+        /// each bitfield holds to an all-zero and an all-one struct, checking
+        /// that they read back unchanged without changing any other bit of
+        /// the backing members. It's only available in tests and debug
+        /// builds.
+        #[cfg(any(test, debug_assertions))]
+        #[allow(clippy::unnecessary_cast, unused_imports, unused_mut)]
+        pub fn validate_layout() -> Result<(), c2rust_bitfields::LayoutError> {
+            use c2rust_bitfields::{BitfieldEnum, FieldType};
+
+            #(#checks)*
+
+            Ok(())
+        }
+    })
+}
+
+/// Returns the type a bitfield's setter takes, along with an expression
+/// converting the setter's `param` to the bitfield's integer type
+fn setter_param(
+    field: &BFFieldAttr,
+    field_type: &Path,
+    param: &Ident,
+) -> Result<(proc_macro2::TokenStream, proc_macro2::TokenStream), Error> {
+    if let Some(enum_ty) = enum_ty(field)? {
+        let int = quote! {
+            <#enum_ty as c2rust_bitfields::BitfieldEnum>::to_discriminant(#param) as #field_type
+        };
+
+        Ok((quote! { #enum_ty }, int))
+    } else if non_zero_int_ty(field_type).is_some() {
+        Ok((quote! { #field_type }, quote! { #param.get() }))
+    } else {
+        Ok((quote! { #field_type }, quote! { #param }))
+    }
+}
+
+/// Generates a statement returning a `BitfieldError` when `int` doesn't fit
+/// in `bit_width` bits
+fn generate_fits_check(name: &str, bit_width: usize) -> proc_macro2::TokenStream {
+    quote! {
+        if !c2rust_bitfields::FieldType::fits_in_bits(&int, #bit_width) {
+            return Err(c2rust_bitfields::BitfieldError::new(
+                #name,
+                #bit_width,
+                int as i128,
+            ));
+        }
+    }
+}
+
+/// Generates `wrapping_set_<name>`, which truncates like the plain setter,
+/// `checked_set_<name>`, which rejects values that don't fit, and, for integer
+/// bitfields, `saturating_set_<name>`, which clamps them, so that call sites
+/// can spell out which behavior they rely on
+fn generate_explicit_setters(
+    field: &BFFieldAttr,
+    is_view: bool,
+    accessor_trait: Option<&Path>,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let field_type = parse_bitfield_ty_path(field)?;
+    let method_name = parse_bitfield_name(field)?;
+    let setter_name = Ident::new(&format!("set_{}", method_name), Span::call_site());
+    let wrapping_setter_name =
+        Ident::new(&format!("wrapping_set_{}", method_name), Span::call_site());
+    let try_setter_name = Ident::new(&format!("try_set_{}", method_name), Span::call_site());
+    let checked_setter_name =
+        Ident::new(&format!("checked_set_{}", method_name), Span::call_site());
+    let saturating_setter_name = Ident::new(
+        &format!("saturating_set_{}", method_name),
+        Span::call_site(),
+    );
+    let name = &field.name.0;
+    let (lhs, rhs) = parse_bit_range(field)?;
+    let bit_width = rhs - lhs + 1;
+    let cfg_attr = cfg_attr(field)?;
+    let receiver = match (is_view, cell_inner_ty(&field.field_ty)) {
+        (false, Some(_)) => quote! { &self },
+        _ => quote! { &mut self },
+    };
+    let value = Ident::new("value", Span::call_site());
+    let (param_ty, int) = setter_param(field, &field_type, &value)?;
+    let fits_check = generate_fits_check(name, bit_width);
+    let set_value = call_accessor(
+        accessor_trait,
+        quote! { self },
+        &setter_name,
+        quote! { value },
+    );
+    let try_set_value = call_accessor(
+        accessor_trait,
+        quote! { self },
+        &try_setter_name,
+        quote! { value },
+    );
+    let is_int =
+        field.enum_ty.is_none() && is_primitive_ty(&field_type) && !field_type.is_ident("bool");
+    let saturating_setter = if is_int {
+        quote! {
+            /// This method writes to a bitfield, clamping the value to the
+            /// range the bitfield's width can hold rather than truncating it
+            #cfg_attr
+            pub fn #saturating_setter_name(#receiver, value: #param_ty) {
+                let value = c2rust_bitfields::FieldType::saturate_to_bits(&value, #bit_width);
+
+                #set_value
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        /// This method writes to a bitfield, truncating the value to the
+        /// bitfield's width just like the plain setter
+        #cfg_attr
+        pub fn #wrapping_setter_name(#receiver, value: #param_ty) {
+            #set_value
+        }
+
+        /// This method writes to a bitfield, failing without writing anything
+        /// if the value doesn't fit in the bitfield's width
+        #cfg_attr
+        pub fn #try_setter_name(
+            #receiver,
+            value: #param_ty,
+        ) -> Result<(), c2rust_bitfields::BitfieldError> {
+            let int = #int;
+
+            #fits_check
+
+            #set_value;
+
+            Ok(())
+        }
+
+        /// This method is the same as the `try_set_` setter, named to go
+        /// with the wrapping and saturating setters
+        #cfg_attr
+        pub fn #checked_setter_name(
+            #receiver,
+            value: #param_ty,
+        ) -> Result<(), c2rust_bitfields::BitfieldError> {
+            #try_set_value
+        }
+
+        #saturating_setter
+    })
+}
+
+/// Generates `with_<name>`, which consumes the struct and returns it with
+/// the bitfield written, so values can be built up in a single expression
+fn generate_with_setter(
+    field: &BFFieldAttr,
+    accessor_trait: Option<&Path>,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let field_type = parse_bitfield_ty_path(field)?;
+    let method_name = parse_bitfield_name(field)?;
+    let setter_name = Ident::new(&format!("set_{}", method_name), Span::call_site());
+    let with_name = Ident::new(&format!("with_{}", method_name), Span::call_site());
+    let cfg_attr = cfg_attr(field)?;
+    // Cell backed setters only need a shared reference
+    let (receiver, self_ref) = match cell_inner_ty(&field.field_ty) {
+        Some(_) => (quote! { self }, quote! { &self }),
+        None => (quote! { mut self }, quote! { &mut self }),
+    };
+    let value = Ident::new("value", Span::call_site());
+    let (param_ty, _) = setter_param(field, &field_type, &value)?;
+    let set_value = match accessor_trait {
+        Some(_) => call_accessor(accessor_trait, self_ref, &setter_name, quote! { value }),
+        None => call_accessor(None, quote! { self }, &setter_name, quote! { value }),
+    };
+
+    Ok(quote! {
+        /// This method writes to a bitfield and returns the updated value,
+        /// truncating like the plain setter
+        #cfg_attr
+        #[must_use]
+        pub fn #with_name(#receiver, value: #param_ty) -> Self {
+            #set_value;
+            self
+        }
+    })
+}
+
+/// Generates `set_<name>_if_changed`, which only writes to the bitfield when
+/// the value it would hold after truncation differs from its current one,
+/// for registers where a write has side effects even if nothing changes
+fn generate_if_changed_setter(
+    field: &BFFieldAttr,
+    accessor_trait: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let field_type = parse_bitfield_ty_path(field)?;
+    let int_ty = match non_zero_int_ty(&field_type) {
+        Some(int_ty) => quote! { #int_ty },
+        None => quote! { #field_type },
+    };
+    let method_name = parse_bitfield_name(field)?;
+    let setter_name = Ident::new(&format!("set_{}", method_name), Span::call_site());
+    let if_changed_name = Ident::new(
+        &format!("set_{}_if_changed", method_name),
+        Span::call_site(),
+    );
+    let (lhs, rhs) = parse_bit_range(field)?;
+    let bit_width = rhs - lhs + 1;
+    let cfg_attr = cfg_attr(field)?;
+    let cell_ty = cell_inner_ty(&field.field_ty);
+    let receiver = match cell_ty {
+        Some(_) => quote! { &self },
+        None => quote! { &mut self },
+    };
+    let scalar_ty = scalar_backing_ty(cell_ty.unwrap_or(&field.field_ty));
+    let read_field = read_backing_field(
+        &quote! { self },
+        &field.field_name,
+        scalar_ty,
+        cell_ty.is_some(),
+        field.big_endian.is_some(),
+    );
+    let value = Ident::new("value", Span::call_site());
+    let (param_ty, int) = setter_param(field, &field_type, &value)?;
+    let set_value = call_accessor(
+        accessor_trait,
+        quote! { self },
+        &setter_name,
+        quote! { value },
+    );
+
+    Ok(quote! {
+        /// This method writes to a bitfield like the plain setter, unless the
+        /// bitfield already holds the value, and returns whether it wrote
+        #cfg_attr
+        #[allow(clippy::unnecessary_cast)]
+        pub fn #if_changed_name(#receiver, value: #param_ty) -> bool {
+            use c2rust_bitfields::FieldType;
+
+            type IntType = #int_ty;
+
+            // Raw bits are compared rather than values of the ty, which the
+            // current bits might not hold a valid one of. Writing the new value
+            // to a scratch buffer truncates it just like writing it to the bitfield.
+            let mut scratch = [0u8; #core_path::mem::size_of::<IntType>()];
+            let int: IntType = #int;
+            int.set_field(&mut scratch, (0, #bit_width - 1));
+            let bits = <u128 as FieldType>::get_field(&scratch, (0, #bit_width - 1));
+
+            let current = {
+                #read_field
+                <u128 as FieldType>::get_field(field, (#lhs, #rhs))
+            };
+
+            if current == bits {
+                return false;
+            }
+
+            #set_value;
+
+            true
+        }
+    })
+}
+
+/// Generates `<name>_at` and `set_<name>_at`, which access a bitfield of the
+/// struct laid out at `base` within a byte slice, such as a trailing buffer
+/// whose layout is only known at runtime. Enum and NonZero bitfields are
+/// accessed through their integer type.
+fn generate_offset_accessors(
+    struct_ident: &Ident,
+    field: &BFFieldAttr,
+    tracer: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let field_type = parse_bitfield_ty_path(field)?;
+    let field_type = match non_zero_int_ty(&field_type) {
+        Some(int_ty) => quote! { #int_ty },
+        None => quote! { #field_type },
+    };
+    let method_name = parse_bitfield_name(field)?;
+    let getter_name = Ident::new(&format!("{}_at", method_name), Span::call_site());
+    let setter_name = Ident::new(&format!("set_{}_at", method_name), Span::call_site());
+    let (lhs, rhs) = parse_bit_range(field)?;
+    let cfg_attr = cfg_attr(field)?;
+    let write_field = generate_traced_write(
+        field,
+        tracer,
+        quote! { let field = &*field; },
+        quote! { int.set_field(field, (#lhs, #rhs)); },
+    )?;
+    let member_range = view_member_range(struct_ident, field, core_path);
+    // The member's bytes are bounds checked against the slice rather than
+    // the whole struct's, so a struct may be cut short after the member
+    let member_bytes = quote! {
+        let range = #member_range;
+        let end = base.saturating_add(range.end);
+
+        if end > bytes.len() {
+            return Err(c2rust_bitfields::ByteLengthError::new(end, bytes.len()));
+        }
+    };
+
+    Ok(quote! {
+        /// This method reads a bitfield of the struct laid out at `base`
+        /// within `bytes`, failing if its backing member extends past the
+        /// end of `bytes`
+        #cfg_attr
+        pub fn #getter_name(
+            bytes: &[u8],
+            base: usize,
+        ) -> Result<#field_type, c2rust_bitfields::ByteLengthError> {
+            use c2rust_bitfields::FieldType;
+
+            type IntType = #field_type;
+
+            #member_bytes
+
+            let field = &bytes[base + range.start..end];
+            Ok(<IntType as FieldType>::get_field(field, (#lhs, #rhs)))
+        }
+
+        /// This method writes to a bitfield of the struct laid out at `base`
+        /// within `bytes`, failing without writing anything if its backing
+        /// member extends past the end of `bytes`
+        #cfg_attr
+        pub fn #setter_name(
+            bytes: &mut [u8],
+            base: usize,
+            int: #field_type,
+        ) -> Result<(), c2rust_bitfields::ByteLengthError> {
+            use c2rust_bitfields::FieldType;
+
+            #member_bytes
+
+            let field = &mut bytes[base + range.start..end];
+            #write_field
+
+            Ok(())
+        }
+    })
+}
+
+/// Generates a `try_new` constructor taking each bitfield, and each member not
+/// backing any bitfield, in declaration order. Backing members start out
+/// zeroed and every bitfield value is checked to fit its width before it's
+/// written.
+fn generate_try_new(
+    fields: &Fields,
+    bitfields: &[BFFieldAttr],
+    accessor_trait: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let mut params = Vec::new();
+    let mut inits = Vec::new();
+    let mut writes = Vec::new();
+
+    for (index, member) in fields.iter().enumerate() {
+        let member_name = match &member.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+
+        if !is_backing_member(member, &member_name, bitfields) {
+            let param = match &member.ident {
+                Some(ident) => ident.clone(),
+                None => Ident::new(&format!("member{}", index), Span::call_site()),
+            };
+            let ty = &member.ty;
+
+            params.push(quote! { #param: #ty });
+            inits.push(quote! { #param });
+            continue;
+        }
+
+        // Auto storage members are declared with the storage alias, but their
+        // bitfields know the type it stands for
+        let backing_ty = bitfields
+            .iter()
+            .find(|field| field.field_name == member_name)
+            .map_or(&member.ty, |field| &field.field_ty);
+        let zeroed = zeroed_backing(backing_ty, core_path);
+
+        match &member.ident {
+            Some(ident) => inits.push(quote! { #ident: #zeroed }),
+            None => inits.push(zeroed),
+        }
+
+        for field in bitfields
+            .iter()
+            .filter(|field| field.field_name == member_name)
+        {
+            let field_type = parse_bitfield_ty_path(field)?;
+            let param = parse_bitfield_name(field)?;
+            let setter_name = Ident::new(&format!("set_{}", param), Span::call_site());
+            let name = &field.name.0;
+            let (lhs, rhs) = parse_bit_range(field)?;
+            let bit_width = rhs - lhs + 1;
+            let cfg_attr = cfg_attr(field)?;
+
+            let (param_ty, int) = setter_param(field, &field_type, &param)?;
+            let fits_check = generate_fits_check(name, bit_width);
+            let this = match accessor_trait {
+                Some(_) => quote! { &mut __c2rust_this },
+                None => quote! { __c2rust_this },
+            };
+            let set_param = call_accessor(accessor_trait, this, &setter_name, quote! { #param });
+
+            params.push(quote! { #cfg_attr #param: #param_ty });
+            writes.push(quote! {
+                #cfg_attr
+                {
+                    let int = #int;
+
+                    #fits_check
+
+                    #set_param;
+                }
+            });
+        }
+    }
+
+    let construct = match fields {
+        Fields::Named(_) => quote! { Self { #(#inits),* } },
+        Fields::Unnamed(_) => quote! { Self(#(#inits),*) },
+        Fields::Unit => quote! { Self },
+    };
+
+    Ok(quote! {
+        /// This method constructs the struct from the value of each bitfield
+        /// and other member, failing on the first bitfield value which
+        /// doesn't fit in its bitfield
+        #[allow(unused_mut, clippy::too_many_arguments)]
+        pub fn try_new(#(#params),*) -> Result<Self, c2rust_bitfields::BitfieldError> {
+            // Named so as not to shadow a bitfield or member named `this`
+            let mut __c2rust_this = #construct;
+
+            #(#writes)*
+
+            Ok(__c2rust_this)
+        }
+    })
+}
+
+fn bitfield_struct_impl(struct_item: ItemStruct) -> Result<proc_macro2::TokenStream, Error> {
+    let struct_ident = struct_item.ident;
+    let struct_attr = parse_struct_attrs(&struct_item.attrs)?;
+    let mut bitfields = match struct_attr.storage {
+        Some(ref storage) => parse_storage_bitfields(
+            &struct_ident,
+            &struct_item.attrs,
+            &struct_item.fields,
+            storage,
+        )?,
+        None => parse_member_bitfields(&struct_ident, &struct_item.attrs, &struct_item.fields)?,
+    };
+
+    for bitfield in &mut bitfields {
+        bitfield.must_use |= struct_attr.must_use;
+        bitfield.msb0 |= struct_attr.msb0;
+        bitfield.allow_overlap |= struct_attr.allow_overlap;
+    }
+
+    // Generated code refers to `core` through `core_path`, and bitfield types
+    // are pointed at `libc_path` before any of it is generated
+    let core_path =
+        parse_crate_path("core_path", &struct_attr.core_path)?.unwrap_or_else(|| quote! { ::core });
+
+    if parse_crate_path("libc_path", &struct_attr.libc_path)?.is_some() {
+        let (libc_path, _) = struct_attr.libc_path.as_ref().unwrap();
+
+        apply_libc_path(&mut bitfields, libc_path);
+    }
+
+    let auto_storage = match struct_attr.storage {
+        Some((ref storage, span)) if storage == AUTO_STORAGE => {
+            let (storage_ty, storage_name) = choose_auto_storage(&mut bitfields, span)?;
+            let alias = auto_storage_alias(&struct_ident);
+            let vis = &struct_item.vis;
+            let doc = format!("The backing type chosen for [`{}`]", struct_ident);
+
+            Some((
+                quote! {
+                    #[doc = #doc]
+                    #vis type #alias = #storage_ty;
+                },
+                quote! {
+                    /// The backing type chosen by `storage = "auto"`
+                    pub const STORAGE_TYPE: &'static str = #storage_name;
+                },
+            ))
+        }
+        _ => None,
+    };
+    let (storage_alias, storage_const) = auto_storage.unwrap_or_default();
+
+    check_capacity(&bitfields)?;
+    check_overlaps(&bitfields)?;
+    apply_struct_endian(&mut bitfields, struct_attr.big_endian)?;
+    check_endian(&bitfields, struct_attr.view || struct_attr.at_offset)?;
+
+    if struct_attr.exhaustive {
+        check_exhaustive(
+            &struct_item.attrs,
+            &struct_item.fields,
+            &struct_attr,
+            &bitfields,
+        )?;
+    }
+
+    let accessor_trait = match (&struct_attr.accessor_mod, struct_attr.accessor_trait) {
+        (Some(_), Some(span)) => {
+            let err_str = "accessor_trait can't be combined with accessor_mod, which already \
+                           moves the bitfield methods into a trait";
+
+            return Err(Error::new(span, err_str));
+        }
+        (Some((accessor_mod, span)), None) => {
+            let mod_ident: Ident = syn::parse_str(accessor_mod).map_err(|_| {
+                let err_str = format!("accessor_mod `{}` is not a valid module name", accessor_mod);
+
+                Error::new(*span, err_str)
+            })?;
+            let trait_ident = Ident::new(&format!("{}Fields", struct_ident), *span);
+
+            Some((Some(mod_ident), trait_ident))
+        }
+        (None, Some(span)) => {
+            let trait_ident = Ident::new(&format!("{}Fields", struct_ident), span);
+
+            Some((None, trait_ident))
+        }
+        (None, None) => None,
+    };
+    // The trait's path from within the struct's module, and from within the
+    // accessor module, where the moved methods call each other
+    let (outer_trait, inner_trait): (Option<Path>, Option<Path>) = match &accessor_trait {
+        Some((Some(mod_ident), trait_ident)) => (
+            Some(syn::parse_quote! { #mod_ident::#trait_ident }),
+            Some(syn::parse_quote! { #trait_ident }),
+        ),
+        Some((None, trait_ident)) => (
+            Some(syn::parse_quote! { #trait_ident }),
+            Some(syn::parse_quote! { #trait_ident }),
+        ),
+        None => (None, None),
+    };
+    let tracer: Option<Path> = match struct_attr.trace {
+        Some((ref trace, span)) => {
+            let tracer = syn::parse_str(trace).map_err(|_| {
+                let err_str = format!("trace `{}` is not a valid type path", trace);
+
+                Error::new(span, err_str)
+            })?;
+
+            Some(tracer)
+        }
+        None => None,
+    };
+
+    let accessors: Result<Vec<_>, Error> = bitfields
+        .iter()
+        .map(|bitfield| {
+            generate_accessors(
+                bitfield,
+                None,
+                inner_trait.as_ref(),
+                tracer.as_ref(),
+                &core_path,
+            )
+        })
+        .collect();
+    let accessors = accessors?;
+    let explicit_setters: Result<Vec<_>, Error> = if struct_attr.explicit_setters {
+        bitfields
+            .iter()
+            .map(|bitfield| generate_explicit_setters(bitfield, false, inner_trait.as_ref()))
+            .collect()
+    } else {
+        Ok(Vec::new())
+    };
+    let explicit_setters = explicit_setters?;
+    let with_setters: Result<Vec<_>, Error> = if struct_attr.with_setters {
+        bitfields
+            .iter()
+            .map(|bitfield| generate_with_setter(bitfield, inner_trait.as_ref()))
+            .collect()
+    } else {
+        Ok(Vec::new())
+    };
+    let with_setters = with_setters?;
+    let if_changed_setters: Result<Vec<_>, Error> = if struct_attr.if_changed {
+        bitfields
+            .iter()
+            .map(|bitfield| generate_if_changed_setter(bitfield, inner_trait.as_ref(), &core_path))
+            .collect()
+    } else {
+        Ok(Vec::new())
+    };
+    let if_changed_setters = if_changed_setters?;
+    let offset_accessors: Result<Vec<_>, Error> = if struct_attr.at_offset {
+        bitfields
+            .iter()
+            .map(|bitfield| {
+                generate_offset_accessors(&struct_ident, bitfield, tracer.as_ref(), &core_path)
+            })
+            .collect()
+    } else {
+        Ok(Vec::new())
+    };
+    let offset_accessors = offset_accessors?;
+    let bitfield_methods = quote! {
+        #(#accessors)*
+
+        #(#explicit_setters)*
+
+        #(#with_setters)*
+
+        #(#if_changed_setters)*
+    };
+    let (bitfield_methods, accessor_trait) = match &accessor_trait {
+        Some((mod_ident, trait_ident)) => (
+            quote! {},
+            generate_accessor_trait(
+                &struct_ident,
+                &struct_item.vis,
+                mod_ident.as_ref(),
+                trait_ident,
+                bitfield_methods,
+            )?,
+        ),
+        None => (bitfield_methods, quote! {}),
+    };
+    let member_helpers = struct_item
+        .fields
+        .iter()
+        .filter(|field| {
+            bitfields.iter().any(|bitfield| match &bitfield.field_name {
+                Member::Named(name) => Some(name) == field.ident.as_ref(),
+                Member::Unnamed(_) => false,
+            })
+        })
+        .map(|field| {
+            generate_member_helpers(
+                field,
+                struct_attr.member_matches,
+                struct_attr.member_zero,
+                &core_path,
+            )
+        });
+    let grouped_getters: Result<Vec<_>, Error> = if struct_attr.member_getters {
+        struct_item
+            .fields
+            .iter()
+            .map(|field| generate_grouped_getter(field, &bitfields))
+            .collect()
+    } else {
+        Ok(Vec::new())
+    };
+    let grouped_getters = grouped_getters?;
+    let ordering = match struct_attr.ord_by {
+        Some(ref ord_by) => generate_ordering(
+            &struct_ident,
+            ord_by,
+            &bitfields,
+            outer_trait.as_ref(),
+            &core_path,
+        )?,
+        None => quote! {},
+    };
+    let try_new = if struct_attr.try_new {
+        generate_try_new(
+            &struct_item.fields,
+            &bitfields,
+            outer_trait.as_ref(),
+            &core_path,
+        )?
+    } else {
+        quote! {}
+    };
+    let layout_assertions = if struct_attr.offsets.is_some() || struct_attr.size.is_some() {
+        generate_layout_assertions(
+            &struct_item,
+            struct_attr.offsets.as_ref(),
+            struct_attr.size.as_ref(),
+            &core_path,
+        )?
+    } else {
+        quote! {}
+    };
+    let display = if struct_attr.display || struct_attr.display_hex {
+        generate_display(
+            &struct_ident,
+            &struct_item.fields,
+            &bitfields,
+            struct_attr.display_hex,
+            outer_trait.as_ref(),
+            &core_path,
+        )?
+    } else {
+        quote! {}
+    };
+
+    let debug = if struct_attr.debug {
+        generate_debug(
+            &struct_ident,
+            &struct_item.fields,
+            &bitfields,
+            outer_trait.as_ref(),
+            &core_path,
+        )?
+    } else {
+        quote! {}
+    };
+
+    let view = if struct_attr.view {
+        generate_view(
+            &struct_ident,
+            &struct_item.vis,
+            &bitfields,
+            struct_attr.explicit_setters,
+            tracer.as_ref(),
+            &core_path,
+        )?
+    } else {
+        quote! {}
+    };
+
+    let diff = if struct_attr.diff {
+        generate_diff(&struct_ident, &bitfields)?
+    } else {
+        quote! {}
+    };
+
+    let dump = if struct_attr.dump {
+        generate_dump(&bitfields)?
+    } else {
+        quote! {}
+    };
+    let layout_validation = if struct_attr.validate_layout {
+        generate_layout_validation(
+            &struct_item.fields,
+            &bitfields,
+            outer_trait.as_ref(),
+            &core_path,
+        )?
+    } else {
+        quote! {}
+    };
+
+    let byte_conversion = if struct_attr.byte_conversion {
+        generate_byte_conversion(
+            &struct_ident,
+            &struct_item.fields,
+            &bitfields,
+            struct_attr.big_endian.is_some(),
+            &core_path,
+        )?
+    } else {
+        quote! {}
+    };
+    // Only structs with a byte conversion can be built from a slice
+    let try_from = if cfg!(feature = "try_from") && !byte_conversion.is_empty() {
+        generate_try_from(&struct_ident, struct_attr.big_endian.is_some(), &core_path)
+    } else {
+        quote! {}
+    };
+
+    // Synthesized storage has no member to carve byte spans out of
+    let byte_spans = match struct_attr.storage {
+        Some(_) => {
+            for attr in &struct_item.attrs {
+                if attr.path.segments.last().unwrap().ident != "bitfield" {
+                    continue;
+                }
+
+                if let Some((_, (_, span))) = parse_byte_span_attr(attr)? {
+                    let err_str = "bytes param requires a named `[u8; N]` member";
+
+                    return Err(Error::new(span, err_str));
+                }
+            }
+
+            Vec::new()
+        }
+        None => {
+            let byte_spans: Result<Vec<_>, Error> =
+                struct_item.fields.iter().map(generate_byte_spans).collect();
+            byte_spans?
+        }
+    };
+
+    let q = quote! {
+        #[automatically_derived]
+        impl #struct_ident {
+            #storage_const
+
+            #bitfield_methods
+
+            #(#offset_accessors)*
+
+            #(#member_helpers)*
+
+            #(#grouped_getters)*
+
+            #(#byte_spans)*
+
+            #try_new
+
+            #byte_conversion
+
+            #dump
+
+            #layout_validation
+        }
+
+        #accessor_trait
+
+        #storage_alias
+
+        #try_from
+
+        #ordering
+
+        #display
+
+        #debug
+
+        #view
+
+        #diff
+
+        #layout_assertions
+    };
+
+    Ok(q)
+}
+
+/// Generates a `<Struct>View<'a>` type wrapping a mutable byte slice laid
+/// out like the struct, with the same bitfield accessors as the struct itself.
+/// The slice's length is checked once when the view is constructed.
+fn generate_view(
+    struct_ident: &Ident,
+    vis: &syn::Visibility,
+    bitfields: &[BFFieldAttr],
+    explicit_setters: bool,
+    tracer: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let view_ident = Ident::new(&format!("{}View", struct_ident), struct_ident.span());
+    let accessors: Result<Vec<_>, Error> = bitfields
+        .iter()
+        .map(|bitfield| generate_accessors(bitfield, Some(struct_ident), None, tracer, core_path))
+        .collect();
+    let accessors = accessors?;
+    let explicit_setters: Result<Vec<_>, Error> = if explicit_setters {
+        bitfields
+            .iter()
+            .map(|bitfield| generate_explicit_setters(bitfield, true, None))
+            .collect()
+    } else {
+        Ok(Vec::new())
+    };
+    let explicit_setters = explicit_setters?;
+    let doc = format!(
+        "A view applying the bitfields of [`{}`] to borrowed bytes",
+        struct_ident
+    );
+    let try_from = if cfg!(feature = "try_from") {
+        quote! {
+            #[automatically_derived]
+            impl<'a> #core_path::convert::TryFrom<&'a mut [u8]> for #view_ident<'a> {
+                type Error = c2rust_bitfields::ByteLengthError;
+
+                fn try_from(bytes: &'a mut [u8]) -> Result<Self, Self::Error> {
+                    let expected = #core_path::mem::size_of::<#struct_ident>();
+                    let actual = bytes.len();
+
+                    Self::new(bytes)
+                        .ok_or_else(|| c2rust_bitfields::ByteLengthError::new(expected, actual))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #view_ident<'a>(&'a mut [u8]);
+
+        #[automatically_derived]
+        impl<'a> #view_ident<'a> {
+            /// This method wraps `bytes` in a view, if it holds at least
+            /// as many bytes as the struct
+            pub fn new(bytes: &'a mut [u8]) -> Option<Self> {
+                if bytes.len() < #core_path::mem::size_of::<#struct_ident>() {
+                    return None;
+                }
+
+                Some(Self(bytes))
+            }
+
+            #(#accessors)*
+
+            #(#explicit_setters)*
+        }
+
+        #try_from
+    })
+}
+
+/// Generates an expression for the range of bytes a backing member occupies
+/// within its struct
+fn view_member_range(
+    struct_ident: &Ident,
+    field: &BFFieldAttr,
+    core_path: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    member_range(struct_ident, &field.field_name, &field.field_ty, core_path)
+}
+
+/// Generates an expression for the range of bytes a member occupies within
+/// its struct
+fn member_range(
+    struct_ident: &Ident,
+    field_name: &Member,
+    field_ty: &Type,
+    core_path: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {{
+        let uninit = #core_path::mem::MaybeUninit::<#struct_ident>::uninit();
+        let base = uninit.as_ptr();
+        // The member's address is only computed, never read through
+        #[allow(unused_unsafe)]
+        let offset = unsafe { #core_path::ptr::addr_of!((*base).#field_name) as usize - base as usize };
+
+        offset..offset + #core_path::mem::size_of::<#field_ty>()
+    }}
+}
+
+/// Generates `from_be_bytes` and `to_be_bytes` for structs declared
+/// `endian = "big"`, or `from_ne_bytes` and `to_ne_bytes` otherwise, converting
+/// each member between its in memory and its wire byte order. Padding between
+/// members reads as zero. Members other than integers and `[u8; N]` arrays,
+/// whether they back bitfields or not, are an error.
+fn generate_byte_conversion(
+    struct_ident: &Ident,
+    fields: &Fields,
+    bitfields: &[BFFieldAttr],
+    big_endian: bool,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let mut values = Vec::new();
+    let mut writes = Vec::new();
+
+    for (index, member) in fields.iter().enumerate() {
+        let member_name = match &member.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        // Bitfields carry the resolved type of members with auto storage
+        let member_ty = bitfields
+            .iter()
+            .find(|field| field.field_name == member_name)
+            .map_or(&member.ty, |field| &field.field_ty);
+        let inner_ty = cell_inner_ty(member_ty);
+        let is_cell = inner_ty.is_some();
+        let backing_ty = inner_ty.unwrap_or(member_ty);
+        let scalar_ty = scalar_backing_ty(backing_ty);
+        let is_byte_array = match backing_ty {
+            Type::Array(type_array) => {
+                matches!(&*type_array.elem, Type::Path(elem) if elem.path.is_ident("u8"))
+            }
+            _ => false,
+        };
+        let byte_len = match backing_byte_len(backing_ty) {
+            Some(byte_len) if scalar_ty.is_some() || is_byte_array => byte_len,
+            _ => {
+                let err_str = format!(
+                    "byte_conversion can't convert member `{}`, which isn't an integer or `[u8; N]`",
+                    quote! { #member_name },
+                );
+
+                return Err(Error::new(member.ty.span(), err_str));
+            }
+        };
+        let range = member_range(struct_ident, &member_name, member_ty, core_path);
+
+        // `[u8; N]` members of big endian structs already hold big endian
+        // bytes, which their bitfields read reversed, so they're copied as is
+        let value = match (scalar_ty, big_endian) {
+            (Some(scalar_ty), false) => quote! { #scalar_ty::from_ne_bytes(member) },
+            (Some(scalar_ty), true) => quote! { #scalar_ty::from_be_bytes(member) },
+            (None, _) => quote! { member },
+        };
+        let value = quote! {{
+            let mut member = [0u8; #byte_len];
+            member.copy_from_slice(&bytes[#range]);
+            #value
+        }};
+        let value = if is_cell {
+            quote! { #core_path::cell::Cell::new(#value) }
+        } else {
+            value
+        };
+
+        let read = if is_cell {
+            quote! { self.#member_name.get() }
+        } else {
+            quote! { self.#member_name }
+        };
+        let member_bytes = match (scalar_ty, big_endian) {
+            (Some(_), false) => quote! { #read.to_ne_bytes() },
+            (Some(_), true) => quote! { #read.to_be_bytes() },
+            (None, _) => read,
+        };
+
+        values.push(match &member.ident {
+            Some(ident) => quote! { #ident: #value },
+            None => value,
+        });
+        writes.push(quote! {
+            bytes[#range].copy_from_slice(&#member_bytes);
+        });
+    }
+
+    let construct = match fields {
+        Fields::Named(_) => quote! { Self { #(#values),* } },
+        Fields::Unnamed(_) => quote! { Self(#(#values),*) },
+        Fields::Unit => quote! { Self },
+    };
+    let (from_name, to_name, order) = if big_endian {
+        ("from_be_bytes", "to_be_bytes", "big endian")
+    } else {
+        ("from_ne_bytes", "to_ne_bytes", "native endian")
+    };
+    let from_name = Ident::new(from_name, Span::call_site());
+    let to_name = Ident::new(to_name, Span::call_site());
+    let from_doc = format!(
+        "This method constructs the struct from its bytes, each backing member being {}",
+        order
+    );
+    let to_doc = format!(
+        "This method returns the bytes of the struct, each backing member being {}",
+        order
+    );
+
+    Ok(quote! {
+        #[doc = #from_doc]
+        pub fn #from_name(bytes: [u8; #core_path::mem::size_of::<#struct_ident>()]) -> Self {
+            #construct
+        }
+
+        #[doc = #to_doc]
+        pub fn #to_name(&self) -> [u8; #core_path::mem::size_of::<#struct_ident>()] {
+            let mut bytes = [0u8; #core_path::mem::size_of::<#struct_ident>()];
+
+            #(#writes)*
+
+            bytes
+        }
+    })
+}
+
+/// Generates a `TryFrom<&[u8]>` impl constructing the struct from a slice
+/// of exactly its size, with the same byte order as its byte conversion
+fn generate_try_from(
+    struct_ident: &Ident,
+    big_endian: bool,
+    core_path: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let from_name = if big_endian {
+        "from_be_bytes"
+    } else {
+        "from_ne_bytes"
+    };
+    let from_name = Ident::new(from_name, Span::call_site());
+
+    quote! {
+        #[automatically_derived]
+        impl #core_path::convert::TryFrom<&[u8]> for #struct_ident {
+            type Error = c2rust_bitfields::ByteLengthError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                let expected = #core_path::mem::size_of::<#struct_ident>();
+
+                match #core_path::convert::TryInto::try_into(bytes) {
+                    Ok(bytes) => Ok(Self::#from_name(bytes)),
+                    Err(_) => Err(c2rust_bitfields::ByteLengthError::new(expected, bytes.len())),
+                }
+            }
+        }
+    }
+}
+
+/// Parses the bitfields declared on the named members of a struct
+fn parse_member_bitfields(
+    struct_ident: &Ident,
+    attrs: &[Attribute],
+    fields: &Fields,
+) -> Result<Vec<BFFieldAttr>, Error> {
+    let fields = match fields {
+        Fields::Named(named_fields) => &named_fields.named,
+        Fields::Unnamed(_) => {
+            let err_str =
+                "Unnamed struct fields are not currently supported but may be in the future.";
+            let span = struct_ident.span();
+
+            return Err(Error::new(span, err_str));
+        }
+        Fields::Unit => {
+            let err_str = "Cannot create bitfield struct out of struct with no fields";
+            let span = struct_ident.span();
+
+            return Err(Error::new(span, err_str));
+        }
+    };
+
+    if is_repr_transparent(attrs) && fields.len() != 1 {
+        let err_str = "repr(transparent) bitfield structs must have exactly one member";
+        let span = struct_ident.span();
+
+        return Err(Error::new(span, err_str));
+    }
+
+    if let Some(attr) = attrs.iter().find(|attr| is_bitfield_spec(attr)) {
+        let err_str = "Bitfields may only be declared on the struct along with a storage param";
+
+        return Err(Error::new(attr.span(), err_str));
+    }
+
+    let mut bitfields = Vec::new();
+
+    for field in fields {
+        bitfields.extend(filter_and_parse_fields(field)?);
+    }
+
+    Ok(bitfields)
+}
+
+/// Parses the bitfields declared on a struct using synthesized storage,
+/// which all live in the struct's single, private storage member. A derive
+/// can't add members to the struct it is applied to, so that member is
+/// spelled out as the only member of a tuple struct, e.g. `struct Reg(u32);`
+fn parse_storage_bitfields(
+    struct_ident: &Ident,
+    attrs: &[Attribute],
+    fields: &Fields,
+    storage: &(String, proc_macro2::Span),
+) -> Result<Vec<BFFieldAttr>, Error> {
+    let (storage, span) = storage;
+    let is_auto = storage == AUTO_STORAGE;
+    // Auto storage is spelled out as an alias the derive defines once the
+    // bitfields are known, and until then stands in for the backing type
+    let (storage, storage_ty): (_, Type) = if is_auto {
+        let alias = auto_storage_alias(struct_ident);
+
+        (alias.to_string(), syn::parse_quote!(#alias))
+    } else {
+        let storage_ty = syn::parse_str(storage).map_err(|_| {
+            let err_str = format!("Bitfield storage `{}` is not a valid type", storage);
+
+            Error::new(*span, err_str)
+        })?;
+
+        (storage.clone(), storage_ty)
+    };
+
+    if !is_auto && backing_byte_len(&storage_ty).is_none() {
+        let err_str = "Bitfield storage must be an integer or a `[u8; N]` array";
+
+        return Err(Error::new(*span, err_str));
+    }
+
+    let field_ty = match fields {
+        Fields::Unnamed(unnamed_fields) if unnamed_fields.unnamed.len() == 1 => {
+            &unnamed_fields.unnamed[0].ty
+        }
+        _ => {
+            let err_str = format!(
+                "Bitfield storage requires a tuple struct with a single `{}` member",
+                storage
+            );
+
+            return Err(Error::new(struct_ident.span(), err_str));
+        }
+    };
+
+    if quote!(#field_ty).to_string() != quote!(#storage_ty).to_string() {
+        let err_str = format!("Bitfield storage member must have type `{}`", storage);
+
+        return Err(Error::new(field_ty.span(), err_str));
+    }
+
+    let field_name = Member::Unnamed(Index::from(0));
+    let spec_attrs = attrs
+        .iter()
+        .filter(|attr| attr.path.segments.last().unwrap().ident == "bitfield")
+        .filter(|attr| is_bitfield_spec(attr));
+    let mut bitfields = Vec::new();
+
+    for attr in spec_attrs {
+        bitfields.extend(parse_bitfield_attr(attr, &field_name, &storage_ty)?);
+    }
+
+    Ok(bitfields)
+}
+
+/// The `storage` param value asking for the smallest backing type which
+/// covers every bitfield
+const AUTO_STORAGE: &str = "auto";
+
+/// The alias naming the backing type of a struct with auto storage
+fn auto_storage_alias(struct_ident: &Ident) -> Ident {
+    Ident::new(&format!("{}Storage", struct_ident), struct_ident.span())
+}
+
+/// Picks the smallest of `u8`, `u16`, `u32`, `u64` or `[u8; N]` covering the
+/// highest bit of any bitfield and makes it the type backing every bitfield.
+/// Returns the type along with how it's spelled.
+fn choose_auto_storage(
+    bitfields: &mut [BFFieldAttr],
+    span: proc_macro2::Span,
+) -> Result<(Type, String), Error> {
+    let mut bit_len = 0;
+
+    for bitfield in bitfields.iter() {
+        // msb0 bits count from the top of the backing type, which is what is
+        // being chosen here
+        if bitfield.msb0 {
+            let err_str = "msb0 requires an explicit storage type";
+
+            return Err(Error::new(bitfield.bits.1, err_str));
+        }
+
+        let (_, rhs) = parse_bit_range(bitfield)?;
+
+        bit_len = bit_len.max(rhs + 1);
+    }
+
+    let storage_name = match bit_len {
+        0..=8 => "u8".to_owned(),
+        9..=16 => "u16".to_owned(),
+        17..=32 => "u32".to_owned(),
+        33..=64 => "u64".to_owned(),
+        _ => format!("[u8; {}]", (bit_len + 7) / 8),
+    };
+    let storage_ty: Type = syn::parse_str(&storage_name).map_err(|e| Error::new(span, e))?;
+
+    for bitfield in bitfields.iter_mut() {
+        bitfield.field_ty = storage_ty.clone();
+    }
+
+    Ok((storage_ty, storage_name))
+}
+
+fn parse_bit_range(field: &BFFieldAttr) -> Result<(usize, usize), Error> {
+    parse_bits(&field.bits, &field.field_ty, field.msb0)
+}
+
+/// Parses a `"1..=4"` or, equivalently, `"1..5"` style bit range, or a
+/// single `"3"` bit, of a member of type `field_ty` into inclusive lsb0 bit
+/// indices
+fn parse_bits(
+    bits: &(String, proc_macro2::Span),
+    field_ty: &Type,
+    msb0: bool,
+) -> Result<(usize, usize), Error> {
+    let (bit_string, span) = bits;
+    let err_str = "bits param must be in the format \"1..=4\", \"1..5\" or \"3\"";
+    let (nums, exclusive) = match bit_string.split_once("..=") {
+        Some(nums) => (nums, false),
+        None => match bit_string.split_once("..") {
+            Some(nums) => (nums, true),
+            None => ((bit_string.as_str(), bit_string.as_str()), false),
+        },
+    };
+
+    let lhs = nums.0.parse::<usize>();
+    let rhs = nums.1.parse::<usize>();
+
+    let (lhs, rhs) = match (lhs, rhs) {
+        (Err(_), _) | (_, Err(_)) => return Err(Error::new(*span, err_str)),
+        (Ok(lhs), Ok(rhs)) if exclusive && lhs < rhs => (lhs, rhs - 1),
+        (Ok(lhs), Ok(rhs)) if !exclusive && lhs <= rhs => (lhs, rhs),
+        (Ok(_), Ok(_)) => {
+            let err_str = format!("bits range `{}` is empty or reversed", bit_string);
+
+            return Err(Error::new(*span, err_str));
+        }
+    };
+
+    if !msb0 {
+        return Ok((lhs, rhs));
+    }
+
+    // msb0 bit indices count down from the most significant bit of the
+    // backing member, so they are mirrored into the usual lsb0 indices
+    let backing_ty = cell_inner_ty(field_ty).unwrap_or(field_ty);
+    let bit_width = match backing_byte_len(backing_ty) {
+        Some(byte_len) => byte_len * 8,
+        None => {
+            let err_str = "msb0 requires an integer or `[u8; N]` member";
+
+            return Err(Error::new(*span, err_str));
+        }
+    };
+
+    if lhs > rhs || rhs >= bit_width {
+        let err_str = format!(
+            "msb0 bits must be an ascending range within the member's {} bits",
+            bit_width
+        );
+
+        return Err(Error::new(*span, err_str));
+    }
+
+    Ok((bit_width - 1 - rhs, bit_width - 1 - lhs))
+}
+
+/// Returns the bit range of a `#[bitfield(reserved = "...")]` attr, along with
+/// whether the attr is marked `msb0`
+fn parse_reserved_attr(
+    attr: &Attribute,
+) -> Result<Option<((String, proc_macro2::Span), bool)>, Error> {
+    let meta_list = match attr.parse_meta()? {
+        Meta::List(meta_list) => meta_list,
+        _ => return Ok(None),
+    };
+    let mut reserved = None;
+    let mut msb0 = false;
+
+    for nested_meta in &meta_list.nested {
+        match nested_meta {
+            NestedMeta::Meta(Meta::NameValue(meta_name_value))
+                if meta_name_value.path.is_ident("reserved") =>
+            {
+                if let Lit::Str(lit_str) = &meta_name_value.lit {
+                    reserved = Some((lit_str.value(), lit_str.span()));
+                }
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("msb0") => msb0 = true,
+            _ => {}
+        }
+    }
+
+    Ok(reserved.map(|reserved| (reserved, msb0)))
+}
+
+/// Returns the name and byte range of a `#[bitfield(name = "...", bytes = "...")]`
+/// attr, which carves a span of bytes out of its member
+fn parse_byte_span_attr(
+    attr: &Attribute,
+) -> Result<Option<((String, proc_macro2::Span), (String, proc_macro2::Span))>, Error> {
+    let meta_list = match attr.parse_meta()? {
+        Meta::List(meta_list) => meta_list,
+        _ => return Ok(None),
+    };
+    let mut name = None;
+    let mut bytes = None;
+
+    for nested_meta in &meta_list.nested {
+        if let NestedMeta::Meta(Meta::NameValue(meta_name_value)) = nested_meta {
+            if let Lit::Str(lit_str) = &meta_name_value.lit {
+                if meta_name_value.path.is_ident("name") {
+                    name = Some((lit_str.value(), lit_str.span()));
+                } else if meta_name_value.path.is_ident("bytes") {
+                    bytes = Some((lit_str.value(), lit_str.span()));
+                }
+            }
+        }
+    }
+
+    Ok(match (name, bytes) {
+        (Some(name), Some(bytes)) => Some((name, bytes)),
+        _ => None,
+    })
+}
+
+/// Parses a `"0..=1"` style byte range of a member `byte_len` bytes long
+fn parse_bytes(
+    bytes: &(String, proc_macro2::Span),
+    byte_len: usize,
+) -> Result<(usize, usize), Error> {
+    let (byte_string, span) = bytes;
+    let nums: Vec<_> = byte_string.split("..=").collect();
+    let err_str = "bytes param must be in the format \"0..=1\"";
+
+    if nums.len() != 2 {
+        return Err(Error::new(*span, err_str));
+    }
+
+    let (lhs, rhs) = match (nums[0].parse::<usize>(), nums[1].parse::<usize>()) {
+        (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+        _ => return Err(Error::new(*span, err_str)),
+    };
+
+    if lhs > rhs || rhs >= byte_len {
+        let err_str = format!(
+            "bytes must be an ascending range within the member's {} bytes",
+            byte_len
+        );
+
+        return Err(Error::new(*span, err_str));
+    }
+
+    Ok((lhs, rhs))
+}
+
+/// Generates a `<name>` and `<name>_mut` accessor borrowing each span of
+/// bytes declared on a `[u8; N]` member, such as a nested C struct
+fn generate_byte_spans(field: &Field) -> Result<proc_macro2::TokenStream, Error> {
+    let mut accessors = Vec::new();
+
+    for attr in &field.attrs {
+        if attr.path.segments.last().unwrap().ident != "bitfield" {
+            continue;
+        }
+
+        let ((name, name_span), bytes) = match parse_byte_span_attr(attr)? {
+            Some(byte_span) => byte_span,
+            None => continue,
+        };
+        let byte_len = match (&field.ident, &field.ty) {
+            (Some(field_name), Type::Array(_)) => {
+                backing_byte_len(&field.ty).map(|byte_len| (field_name, byte_len))
+            }
+            _ => None,
+        };
+        let (field_name, byte_len) = match byte_len {
+            Some(byte_len) => byte_len,
+            None => {
+                let err_str = "bytes param requires a named `[u8; N]` member";
+
+                return Err(Error::new(bytes.1, err_str));
+            }
+        };
+        let (lhs, rhs) = parse_bytes(&bytes, byte_len)?;
+        let method_name = Ident::new(&name, name_span);
+        let mut_method_name = Ident::new(&format!("{}_mut", name), name_span);
+        let doc = format!(
+            "This method borrows bytes `{}` of the `{}` member",
+            bytes.0, field_name
+        );
+        let mut_doc = format!(
+            "This method mutably borrows bytes `{}` of the `{}` member",
+            bytes.0, field_name
+        );
+
+        accessors.push(quote! {
+            #[doc = #doc]
+            pub fn #method_name(&self) -> &[u8] {
+                &self.#field_name[#lhs..=#rhs]
+            }
+
+            #[doc = #mut_doc]
+            pub fn #mut_method_name(&mut self) -> &mut [u8] {
+                &mut self.#field_name[#lhs..=#rhs]
+            }
+        });
+    }
+
+    Ok(quote! { #(#accessors)* })
+}
+
+/// Checks that every bit of every backing member belongs to a bitfield or a
+/// reserved range, so that mistakes in transcribing a layout don't go unnoticed.
+/// Members marked `padding` are reserved as a whole.
+fn check_exhaustive(
+    attrs: &[Attribute],
+    fields: &Fields,
+    struct_attr: &BFStructAttr,
+    bitfields: &[BFFieldAttr],
+) -> Result<(), Error> {
+    let is_padding = |attr: &&Attribute| {
+        match attr.parse_meta() {
+        Ok(Meta::List(meta_list)) => meta_list.nested.iter().any(|nested| {
+            matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("padding"))
+        }),
+        _ => false,
+    }
+    };
+
+    for (index, member) in fields.iter().enumerate() {
+        let member_name = match &member.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+
+        if !is_backing_member(member, &member_name, bitfields) {
+            continue;
+        }
+
+        // Synthesized storage has its bitfields declared on the struct itself
+        let member_attrs = match struct_attr.storage {
+            Some(_) => attrs,
+            None => &member.attrs,
+        };
+        let bitfield_attrs: Vec<_> = member_attrs
+            .iter()
+            .filter(|attr| attr.path.segments.last().unwrap().ident == "bitfield")
+            .collect();
+
+        if bitfield_attrs.iter().any(is_padding) {
+            continue;
+        }
+
+        // Bitfields carry the resolved type of members with auto storage
+        let member_ty = bitfields
+            .iter()
+            .find(|field| field.field_name == member_name)
+            .map_or(&member.ty, |field| &field.field_ty);
+        let backing_ty = cell_inner_ty(member_ty).unwrap_or(member_ty);
+        let bit_width = match backing_byte_len(backing_ty) {
+            Some(byte_len) => byte_len * 8,
+            None => {
+                let err_str = "exhaustive requires integer or `[u8; N]` backing members";
+
+                return Err(Error::new(member.ty.span(), err_str));
+            }
+        };
+        let mut ranges = Vec::new();
+
+        for field in bitfields
+            .iter()
+            .filter(|field| field.field_name == member_name)
+        {
+            ranges.push(parse_bit_range(field)?);
+        }
+
+        for attr in &bitfield_attrs {
+            if let Some((bits, msb0)) = parse_reserved_attr(attr)? {
+                ranges.push(parse_bits(&bits, member_ty, msb0 || struct_attr.msb0)?);
+            }
+        }
+
+        let mut covered = vec![false; bit_width];
+
+        for (lhs, rhs) in ranges {
+            for bit in covered.iter_mut().take(rhs + 1).skip(lhs) {
+                *bit = true;
+            }
+        }
+
+        // Gaps are reported in the struct's own bit numbering
+        let to_bit = |bit: usize| {
+            if struct_attr.msb0 {
+                bit_width - 1 - bit
+            } else {
+                bit
+            }
+        };
+        let mut gaps = Vec::new();
+        let mut bit = 0;
+
+        while bit < bit_width {
+            if covered[bit] {
+                bit += 1;
+                continue;
+            }
+
+            let start = bit;
+
+            while bit < bit_width && !covered[bit] {
+                bit += 1;
+            }
+
+            let (lhs, rhs) = (to_bit(start), to_bit(bit - 1));
+
+            gaps.push(format!("{}..={}", lhs.min(rhs), lhs.max(rhs)));
+        }
+
+        if struct_attr.msb0 {
+            gaps.reverse();
+        }
+
+        if !gaps.is_empty() {
+            let err_str = format!(
+                "bits {} of `{}` are neither a bitfield nor reserved",
+                gaps.join(", "),
+                quote!(#member_name),
+            );
+            let span = match &member.ident {
+                Some(ident) => ident.span(),
+                None => member.ty.span(),
+            };
+
+            return Err(Error::new(span, err_str));
+        }
+    }
+
+    Ok(())
+}
+
+/// Makes the bitfields of every `[u8; N]` member of an `endian = "big"`
+/// struct big endian, as if each had its own `endian = "big"` param. Scalar
+/// members are converted to native endian by `from_be_bytes`, so their
+/// bitfields are left alone. A bitfield asking for `endian = "little"` in
+/// such a struct contradicts the struct and is an error.
+fn apply_struct_endian(
+    bitfields: &mut [BFFieldAttr],
+    big_endian: Option<proc_macro2::Span>,
+) -> Result<(), Error> {
+    let struct_span = match big_endian {
+        Some(span) => span,
+        None => return Ok(()),
+    };
+
+    for field in bitfields {
+        if let Some(span) = field.little_endian {
+            let err_str = format!(
+                "bitfield `{}` can't be endian = \"little\" in an endian = \"big\" struct",
+                field.name.0
+            );
+
+            return Err(Error::new(span, err_str));
+        }
+
+        let backing_ty = cell_inner_ty(&field.field_ty).unwrap_or(&field.field_ty);
+
+        if field.big_endian.is_none() && matches!(backing_ty, Type::Array(_)) {
+            field.big_endian = Some(struct_span);
+        }
+    }
+
+    Ok(())
+}
+
+/// Big endian bitfields are read from a reversed copy of their member's
+/// bytes, so the member must be a byte array which every bitfield in it
+/// reverses alike, and can't be viewed in place through a slice
+fn check_endian(bitfields: &[BFFieldAttr], has_slice_accessors: bool) -> Result<(), Error> {
+    for field in bitfields {
+        let span = match field.big_endian {
+            Some(span) => span,
+            None => continue,
+        };
+        let backing_ty = cell_inner_ty(&field.field_ty).unwrap_or(&field.field_ty);
+
+        if !matches!(backing_ty, Type::Array(_)) {
+            let err_str = "endian = \"big\" requires a `[u8; N]` member";
+
+            return Err(Error::new(span, err_str));
+        }
+
+        if has_slice_accessors {
+            let err_str = "endian = \"big\" can't be combined with view or at_offset";
+
+            return Err(Error::new(span, err_str));
+        }
+
+        let mixed = bitfields
+            .iter()
+            .find(|other| other.field_name == field.field_name && other.big_endian.is_none());
+
+        if let Some(other) = mixed {
+            let err_str = format!(
+                "bitfield `{}` shares its member with big endian bitfield `{}`, so it needs \
+                 endian = \"big\" too",
+                other.name.0, field.name.0
+            );
+
+            return Err(Error::new(other.name.1, err_str));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects bitfields ending past the last bit of their backing member, whose
+/// accessors would otherwise index out of bounds. Members whose size isn't
+/// known from their type alone, such as `[u8; N]` with a const `N`, aren't
+/// checked.
+fn check_capacity(bitfields: &[BFFieldAttr]) -> Result<(), Error> {
+    for field in bitfields {
+        let backing_ty = cell_inner_ty(&field.field_ty).unwrap_or(&field.field_ty);
+        let capacity = match backing_byte_len(backing_ty) {
+            Some(byte_len) => byte_len * 8,
+            None => continue,
+        };
+        let (_, rhs) = parse_bit_range(field)?;
+
+        if rhs >= capacity {
+            let member = match &field.field_name {
+                Member::Named(ident) => ident.to_string(),
+                Member::Unnamed(index) => index.index.to_string(),
+            };
+            let err_str = format!(
+                "bitfield `{}` ends at bit {}, but its member `{}` only has {} bits",
+                field.name.0, rhs, member, capacity
+            );
+
+            return Err(Error::new(field.bits.1, err_str));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects bitfields whose bit ranges overlap within the same backing member,
+/// unless either of them allows overlapping
+fn check_overlaps(bitfields: &[BFFieldAttr]) -> Result<(), Error> {
+    for (index, field) in bitfields.iter().enumerate() {
+        let (lhs, rhs) = parse_bit_range(field)?;
+
+        for other in &bitfields[..index] {
+            if other.field_name != field.field_name || field.allow_overlap || other.allow_overlap {
+                continue;
+            }
+
+            let (other_lhs, other_rhs) = parse_bit_range(other)?;
+
+            if lhs <= other_rhs && other_lhs <= rhs {
+                let err_str = format!(
+                    "Bitfield `{}` overlaps bitfield `{}`, add `allow_overlap` if this is intended",
+                    field.name.0, other.name.0
+                );
+
+                return Err(Error::new(field.bits.1, err_str));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates a `let field = ...;` statement binding the bytes of the
+/// member backing a bitfield of `receiver`. The bytes of a `big_endian`
+/// member are reversed, so that bits are counted from the end of the member.
+fn read_backing_field(
+    receiver: &proc_macro2::TokenStream,
+    field_name: &Member,
+    scalar_ty: Option<&Ident>,
+    is_cell: bool,
+    big_endian: bool,
+) -> proc_macro2::TokenStream {
+    match (scalar_ty, is_cell, big_endian) {
+        (Some(_), false, _) => quote! { let field = &#receiver.#field_name.to_le_bytes(); },
+        (None, false, false) => quote! { let field = &#receiver.#field_name; },
+        (None, false, true) => quote! {
+            let field = &{
+                let mut bytes = #receiver.#field_name;
+                bytes.reverse();
+                bytes
+            };
+        },
+        (Some(_), true, _) => quote! { let field = &#receiver.#field_name.get().to_le_bytes(); },
+        (None, true, false) => quote! { let field = &#receiver.#field_name.get(); },
+        (None, true, true) => quote! {
+            let field = &{
+                let mut bytes = #receiver.#field_name.get();
+                bytes.reverse();
+                bytes
+            };
+        },
+    }
+}
+
+/// Wraps `write`, which writes `int` to a bitfield, so that it reports the
+/// bitfield's raw bits before and after the write to the struct's `trace`
+/// type. `read_field` binds `field` to the bytes of the backing member. This
+/// is a no-op unless the `trace` feature is enabled.
+fn generate_traced_write(
+    field: &BFFieldAttr,
+    tracer: Option<&Path>,
+    read_field: proc_macro2::TokenStream,
+    write: proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let tracer = match tracer {
+        Some(tracer) if cfg!(feature = "trace") => tracer,
+        _ => return Ok(write),
+    };
+    let name = &field.name.0;
+    let (lhs, rhs) = parse_bit_range(field)?;
+    // Only the low 64 bits of wider bitfields are reported
+    let start = lhs as u32;
+    let width = (rhs - lhs + 1).min(64) as u32;
+
+    Ok(quote! {
+        let old = {
+            #read_field
+            c2rust_bitfields::extract_bits(field, #start, #width)
+        };
+        #write
+        let new = {
+            #read_field
+            c2rust_bitfields::extract_bits(field, #start, #width)
+        };
+        <#tracer as c2rust_bitfields::BitfieldTrace>::trace(#name, old, new);
+    })
+}
+
+/// Generates the getter and setter for a single bitfield. With `view_of`,
+/// they operate on the bytes of a view of that struct rather than on the
+/// struct's own backing member.
+fn generate_accessors(
+    field: &BFFieldAttr,
+    view_of: Option<&Ident>,
+    accessor_trait: Option<&Path>,
+    tracer: Option<&Path>,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let field_type = parse_bitfield_ty_path(field)?;
+    let method_name = parse_bitfield_name(field)?;
+    let setter_name = Ident::new(&format!("set_{}", method_name), Span::call_site());
+    let field_name = &field.field_name;
+    let (lhs, rhs) = parse_bit_range(field)?;
+    // The bits of a cfg'd out field are still parsed above, so they remain
+    // reserved even when its accessors aren't emitted
+    let cfg_attr = cfg_attr(field)?;
+    let must_use_attr = if field.must_use {
+        quote! { #[must_use] }
+    } else {
+        quote! {}
+    };
+
+    let cell_ty = cell_inner_ty(&field.field_ty);
+    let backing_ty = cell_ty.unwrap_or(&field.field_ty);
+    let member_range =
+        view_of.map(|struct_ident| view_member_range(struct_ident, field, core_path));
+    // Members wrapped in a `Cell` are written through `&self`, except in
+    // views which only ever hold their bytes mutably
+    let receiver = match (&member_range, cell_ty) {
+        (None, Some(_)) => quote! { &self },
+        _ => quote! { &mut self },
+    };
+
+    // Scalar members are viewed through their little endian bytes so that bit
+    // indices are counted from the least significant bit of the integer
+    let scalar_ty = scalar_backing_ty(backing_ty);
+    let read_field = match &member_range {
+        Some(range) => quote! { let field = &self.0[#range]; },
+        None => read_backing_field(
+            &quote! { self },
+            field_name,
+            scalar_ty,
+            cell_ty.is_some(),
+            field.big_endian.is_some(),
+        ),
+    };
+    // `set_method` is either `set_field` or `set_field_unchecked`
+    let write_member = |set_method: Ident| match (&member_range, scalar_ty, cell_ty) {
+        (Some(range), _, _) => quote! {
+            let field = &mut self.0[#range];
+            int.#set_method(field, (lhs_bit, rhs_bit));
+        },
+        // Big endian members are only ever byte arrays
+        (None, None, None) if field.big_endian.is_some() => quote! {
+            let mut bytes = self.#field_name;
+            bytes.reverse();
+            int.#set_method(&mut bytes, (lhs_bit, rhs_bit));
+            bytes.reverse();
+            self.#field_name = bytes;
+        },
+        (None, None, Some(_)) if field.big_endian.is_some() => quote! {
+            let mut bytes = self.#field_name.get();
+            bytes.reverse();
+            int.#set_method(&mut bytes, (lhs_bit, rhs_bit));
+            bytes.reverse();
+            self.#field_name.set(bytes);
+        },
+        (None, Some(scalar_ty), None) => quote! {
+            let mut bytes = self.#field_name.to_le_bytes();
+            int.#set_method(&mut bytes, (lhs_bit, rhs_bit));
+            self.#field_name = #scalar_ty::from_le_bytes(bytes);
+        },
+        (None, None, None) => quote! {
+            let field = &mut self.#field_name;
+            int.#set_method(field, (lhs_bit, rhs_bit));
+        },
+        (None, Some(scalar_ty), Some(_)) => quote! {
+            let mut bytes = self.#field_name.get().to_le_bytes();
+            int.#set_method(&mut bytes, (lhs_bit, rhs_bit));
+            self.#field_name.set(#scalar_ty::from_le_bytes(bytes));
+        },
+        (None, None, Some(_)) => quote! {
+            let mut bytes = self.#field_name.get();
+            int.#set_method(&mut bytes, (lhs_bit, rhs_bit));
+            self.#field_name.set(bytes);
+        },
+    };
+    let write_backing = |set_method: Ident| {
+        let write = write_member(set_method);
+
+        generate_traced_write(field, tracer, read_field.clone(), write)
+    };
+    let write_field = write_backing(Ident::new("set_field", Span::call_site()))?;
+    let raw_getter = if field.raw {
+        generate_raw_getter(
+            field,
+            &method_name,
+            (lhs, rhs),
+            &read_field,
+            &cfg_attr,
+            core_path,
+        )?
+    } else {
+        quote! {}
+    };
+
+    // The width of aliases such as `libc::c_ulong` depends on the target, so
+    // whether the bitfield fits in its ty can only be checked once it's known
+    let width_check = if field_type.is_ident("bool") {
+        // Like C's `_Bool`, a bool bitfield is a single bit
+        if rhs != lhs {
+            let err_str = format!("bool bitfield `{}` must be a single bit wide", method_name);
+
+            return Err(Error::new(field.bits.1, err_str));
+        }
+
+        quote! {}
+    } else {
+        let bit_width = rhs - lhs + 1;
+        let err_str = format!(
+            "bitfield `{}` is {} bits wide, which is more than its ty holds on this target",
+            method_name, bit_width
+        );
+
+        quote! {
+            const _: () = assert!(
+                #bit_width <= <#field_type as FieldType>::TOTAL_BIT_SIZE,
+                #err_str,
+            );
+        }
+    };
+
+    if let Some(int_ty) = non_zero_int_ty(&field_type) {
+        return Ok(quote! {
+            /// This method allows you to write to a bitfield with a non-zero value
+            #cfg_attr
+            pub fn #setter_name(#receiver, value: #field_type) {
+                use c2rust_bitfields::FieldType;
+
+                let int: #int_ty = value.get();
+                let (lhs_bit, rhs_bit) = (#lhs, #rhs);
+                #write_field
+            }
+
+            /// This method allows you to read from a bitfield to a value,
+            /// if the bitfield isn't zero
+            #cfg_attr
+            #must_use_attr
+            pub fn #method_name(&self) -> Option<#field_type> {
+                use c2rust_bitfields::FieldType;
+
+                type IntType = #int_ty;
+
+                #read_field
+                let (lhs_bit, rhs_bit) = (#lhs, #rhs);
+                let int = <IntType as FieldType>::get_field(field, (lhs_bit, rhs_bit));
+
+                <#field_type>::new(int)
+            }
+
+            #raw_getter
+        });
+    }
+
+    if let Some(enum_ty) = enum_ty(field)? {
+        // Signed fields are sign extended by `get_field` before being
+        // converted, so negative discriminants round trip
+        return Ok(quote! {
+            /// This method allows you to write to a bitfield with an enum variant
+            #cfg_attr
+            pub fn #setter_name(#receiver, variant: #enum_ty) {
+                use c2rust_bitfields::{BitfieldEnum, FieldType};
+
+                type IntType = #field_type;
+
+                #width_check
+                const _: () = assert!(
+                    !<#enum_ty as BitfieldEnum>::HAS_NEGATIVE_DISCRIMINANT
+                        || <IntType as FieldType>::IS_SIGNED,
+                    "enums with negative discriminants need a bitfield with a signed ty",
+                );
+
+                let int = <#enum_ty as BitfieldEnum>::to_discriminant(variant) as IntType;
+                let (lhs_bit, rhs_bit) = (#lhs, #rhs);
+                #write_field
+            }
+
+            /// This method allows you to read from a bitfield to an enum variant,
+            /// if the bitfield holds one of the enum's discriminants
+            #cfg_attr
+            #must_use_attr
+            pub fn #method_name(&self) -> Option<#enum_ty> {
+                use c2rust_bitfields::{BitfieldEnum, FieldType};
+
+                type IntType = #field_type;
+
+                #width_check
+                #read_field
+                let (lhs_bit, rhs_bit) = (#lhs, #rhs);
+                let int = <IntType as FieldType>::get_field(field, (lhs_bit, rhs_bit));
+
+                <#enum_ty as BitfieldEnum>::from_discriminant(int as i128)
+            }
+
+            #raw_getter
+        });
+    }
+
+    let bit_scan_helpers = if let Some(bit_scan_span) = field.bit_scan {
+        if field_type.is_ident("bool") {
+            let err_str = format!(
+                "bit_scan param requires an integer ty, but bitfield `{}` is a bool",
+                method_name
+            );
+
+            return Err(Error::new(bit_scan_span, err_str));
+        }
+
+        generate_bit_scan_helpers(
+            &method_name,
+            &field_type,
+            rhs - lhs + 1,
+            &cfg_attr,
+            accessor_trait,
+        )
+    } else {
+        quote! {}
+    };
+
+    let unchecked_accessors = if cfg!(feature = "unchecked") {
+        let span = Span::call_site();
+        let unchecked_setter_name = Ident::new(&format!("set_{}_unchecked", method_name), span);
+        let unchecked_getter_name = Ident::new(&format!("{}_unchecked", method_name), span);
+        let write_field_unchecked = write_backing(Ident::new("set_field_unchecked", span))?;
+
+        quote! {
+            /// This method allows you to write to a bitfield with a value,
+            /// without checking that the bitfield lies within its backing member
+            ///
+            /// # Safety
+            ///
+            /// The bitfield's bit range must lie within its backing member
+            #cfg_attr
+            pub unsafe fn #unchecked_setter_name(#receiver, int: #field_type) {
+                use c2rust_bitfields::FieldType;
+
+                let (lhs_bit, rhs_bit) = (#lhs, #rhs);
+                #write_field_unchecked
+            }
+
+            /// This method allows you to read from a bitfield to a value,
+            /// without checking that the bitfield lies within its backing member
+            ///
+            /// # Safety
+            ///
+            /// The bitfield's bit range must lie within its backing member
+            #cfg_attr
+            #must_use_attr
+            pub unsafe fn #unchecked_getter_name(&self) -> #field_type {
+                use c2rust_bitfields::FieldType;
+
+                type IntType = #field_type;
+
+                #read_field
+                let (lhs_bit, rhs_bit) = (#lhs, #rhs);
+                <IntType as FieldType>::get_field_unchecked(field, (lhs_bit, rhs_bit))
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Trait methods can't be called in a `const fn`, so the getter is only
+    // const when it reads the member's bytes in place with `extract_bits`,
+    // and when its ty can be cast from the extracted bits
+    let bit_width = rhs - lhs + 1;
+    let getter = if is_primitive_ty(&field_type)
+        && member_range.is_none()
+        && cell_ty.is_none()
+        && accessor_trait.is_none()
+        && field.big_endian.is_none()
+        && bit_width <= 64
+    {
+        let start = lhs as u32;
+        let width = bit_width as u32;
+        let unused_bits = 64 - width;
+        let (use_field_type, convert) = if field_type.is_ident("bool") {
+            (quote! {}, quote! { bits != 0 })
+        } else if unused_bits == 0 {
+            (
+                quote! { use c2rust_bitfields::FieldType; },
+                quote! { bits as #field_type },
+            )
+        } else {
+            // Signed bitfields are sign extended from their top bit
+            (
+                quote! { use c2rust_bitfields::FieldType; },
+                quote! {
+                    if <#field_type as FieldType>::IS_SIGNED {
+                        (((bits << #unused_bits) as i64) >> #unused_bits) as #field_type
+                    } else {
+                        bits as #field_type
+                    }
+                },
+            )
+        };
+
+        quote! {
+            /// This method allows you to read from a bitfield to a value,
+            /// including in const contexts
+            #cfg_attr
+            #must_use_attr
+            #[allow(clippy::unnecessary_cast)]
+            pub const fn #method_name(&self) -> #field_type {
+                #use_field_type
+
+                #width_check
+                #read_field
+                let bits = c2rust_bitfields::extract_bits(field, #start, #width);
+
+                #convert
+            }
+        }
+    } else {
+        quote! {
+            /// This method allows you to read from a bitfield to a value
+            #cfg_attr
+            #must_use_attr
+            pub fn #method_name(&self) -> #field_type {
+                use c2rust_bitfields::FieldType;
+
+                type IntType = #field_type;
+
+                #width_check
+                #read_field
+                let (lhs_bit, rhs_bit) = (#lhs, #rhs);
+                <IntType as FieldType>::get_field(field, (lhs_bit, rhs_bit))
+            }
+        }
+    };
+
+    // TODO: Method visibility determined by struct field visibility?
+    Ok(quote! {
+        /// This method allows you to write to a bitfield with a value
+        #cfg_attr
+        pub fn #setter_name(#receiver, int: #field_type) {
+            use c2rust_bitfields::FieldType;
+
+            #width_check
+            let (lhs_bit, rhs_bit) = (#lhs, #rhs);
+            #write_field
+        }
+
+        #getter
+
+        #unchecked_accessors
+
+        #bit_scan_helpers
+
+        #raw_getter
+    })
+}
+
+/// Generates the `raw` getter of a bitfield, which reads the bitfield's bits
+/// masked but left in place within the whole backing member
+fn generate_raw_getter(
+    field: &BFFieldAttr,
+    method_name: &Ident,
+    (lhs, rhs): (usize, usize),
+    read_field: &proc_macro2::TokenStream,
+    cfg_attr: &proc_macro2::TokenStream,
+    core_path: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let backing_ty = cell_inner_ty(&field.field_ty).unwrap_or(&field.field_ty);
+    let byte_len = backing_byte_len(backing_ty);
+    let raw_ty = match scalar_backing_ty(backing_ty) {
+        Some(scalar_ty) => Some(scalar_ty.clone()),
+        None => byte_len.and_then(raw_int_ty),
+    };
+    let (byte_len, raw_ty) = match (byte_len, raw_ty) {
+        (Some(byte_len), Some(raw_ty)) => (byte_len, raw_ty),
+        _ => {
+            let err_str = "raw requires an integer or `[u8; N]` member of at most 16 bytes";
+
+            return Err(Error::new(field.bits.1, err_str));
+        }
+    };
+
+    if lhs > rhs || rhs >= byte_len * 8 {
+        let err_str = format!(
+            "raw requires bits to be an ascending range within the member's {} bits",
+            byte_len * 8
+        );
+
+        return Err(Error::new(field.bits.1, err_str));
+    }
+
+    let raw_name = Ident::new(&format!("{}_raw", method_name), Span::call_site());
+    let bit_width = rhs - lhs + 1;
+    // Built as a `u128` so that the mask's top bit may be a signed member's
+    // sign bit
+    let mask = (u128::MAX >> (128 - bit_width)) << lhs;
+    let mask = proc_macro2::Literal::u128_suffixed(mask);
+
+    Ok(quote! {
+        /// This method reads a bitfield's bits in place within its backing
+        /// member, masked but not shifted down
+        #cfg_attr
+        #[allow(clippy::unnecessary_cast)]
+        pub fn #raw_name(&self) -> #raw_ty {
+            #read_field
+            let mut bytes = [0u8; #core_path::mem::size_of::<#raw_ty>()];
+            bytes[..#byte_len].copy_from_slice(&field[..#byte_len]);
+
+            #raw_ty::from_le_bytes(bytes) & #mask as #raw_ty
+        }
+    })
+}
+
+/// Generates the `bit_scan` helpers of an integer bitfield, which count zero
+/// bits within the bitfield's width rather than that of its integer type
+fn generate_bit_scan_helpers(
+    method_name: &Ident,
+    field_type: &Path,
+    bit_width: usize,
+    cfg_attr: &proc_macro2::TokenStream,
+    accessor_trait: Option<&Path>,
+) -> proc_macro2::TokenStream {
+    let span = Span::call_site();
+    let value = call_accessor(accessor_trait, quote! { self }, method_name, quote! {});
+    let trailing_name = Ident::new(&format!("{}_trailing_zeros", method_name), span);
+    let leading_name = Ident::new(&format!("{}_leading_zeros", method_name), span);
+    let bit_width = bit_width as u32;
+
+    quote! {
+        /// This method counts the trailing zero bits of a bitfield
+        #cfg_attr
+        pub fn #trailing_name(&self) -> u32 {
+            #value.trailing_zeros().min(#bit_width)
+        }
+
+        /// This method counts the leading zero bits of a bitfield
+        #cfg_attr
+        pub fn #leading_name(&self) -> u32 {
+            use c2rust_bitfields::FieldType;
+
+            let unused_bits = <#field_type as FieldType>::TOTAL_BIT_SIZE as u32 - #bit_width;
+
+            #value.leading_zeros().saturating_sub(unused_bits)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bitfield_struct_impl;
+
+    fn expand(src: &str) -> String {
+        let struct_item = syn::parse_str(src).unwrap();
+
+        bitfield_struct_impl(struct_item).unwrap().to_string()
+    }
+
+    #[test]
+    fn accessors_follow_declaration_order() {
+        let src = r#"
+            struct Foo {
+                #[bitfield(name = "zeta", ty = "u8", bits = "0..=3")]
+                #[bitfield(name = "alpha", ty = "u8", bits = "4..=7")]
+                first: [u8; 1],
+                #[bitfield(name = "mid", ty = "u16", bits = "0..=9")]
+                second: [u8; 2],
+            }
+        "#;
+        let expanded = expand(src);
+        let methods = [
+            "fn set_zeta",
+            "fn zeta",
+            "fn set_alpha",
+            "fn alpha",
+            "fn set_mid",
+            "fn mid",
+        ];
+        let positions: Vec<_> = methods
+            .iter()
+            .map(|method| expanded.find(method).unwrap())
+            .collect();
+
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(expanded, expand(src));
+    }
 }
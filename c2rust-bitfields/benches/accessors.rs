@@ -0,0 +1,78 @@
+use c2rust_bitfields::BitfieldStruct;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Same layout as `CompactDate` in the C compatibility tests: each field lives
+// within a single byte of the backing member
+#[repr(C, align(2))]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct CompactDate {
+    #[bitfield(name = "d", ty = "libc::c_uchar", bits = "0..=4")]
+    #[bitfield(name = "m", ty = "libc::c_uchar", bits = "8..=11")]
+    d_m: [u8; 2],
+    // Only there for the layout
+    #[allow(dead_code)]
+    y: u16,
+}
+
+// `m` straddles the boundary between the first and second byte
+#[repr(C, align(8))]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct OverlappingByteDate {
+    #[bitfield(name = "d", ty = "libc::c_ulong", bits = "0..=4")]
+    #[bitfield(name = "m", ty = "libc::c_ushort", bits = "5..=8")]
+    d_m: [u8; 2],
+    // Only there for the layout
+    #[allow(dead_code)]
+    y: u16,
+}
+
+// `year` spans three bytes of the backing member
+#[repr(C, align(1))]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct ThreeByteDate {
+    #[bitfield(name = "day", ty = "libc::c_uchar", bits = "0..=4")]
+    #[bitfield(name = "month", ty = "libc::c_uchar", bits = "5..=8")]
+    #[bitfield(name = "year", ty = "libc::c_ushort", bits = "9..=23")]
+    day_month_year: [u8; 3],
+}
+
+fn single_byte(c: &mut Criterion) {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    c.bench_function("single_byte/get", |b| {
+        b.iter(|| (black_box(&date).d(), black_box(&date).m()))
+    });
+    c.bench_function("single_byte/set", |b| {
+        b.iter(|| {
+            date.set_d(black_box(31));
+            date.set_m(black_box(12));
+        })
+    });
+}
+
+fn cross_byte(c: &mut Criterion) {
+    let mut date = OverlappingByteDate {
+        d_m: [0; 2],
+        y: 2019,
+    };
+
+    c.bench_function("cross_byte/get", |b| b.iter(|| black_box(&date).m()));
+    c.bench_function("cross_byte/set", |b| b.iter(|| date.set_m(black_box(12))));
+}
+
+fn multi_byte(c: &mut Criterion) {
+    let mut date = ThreeByteDate {
+        day_month_year: [0; 3],
+    };
+
+    c.bench_function("multi_byte/get", |b| b.iter(|| black_box(&date).year()));
+    c.bench_function("multi_byte/set", |b| {
+        b.iter(|| date.set_year(black_box(2000)))
+    });
+}
+
+criterion_group!(benches, single_byte, cross_byte, multi_byte);
+criterion_main!(benches);
@@ -176,3 +176,88 @@ void assign_three_byte_date(three_byte_date* bf, uchar day, uchar month, ushort
     bf->month = month;
     bf->year = year;
 }
+
+// *** Dumping AST Record Layout
+//          0 | struct split_units
+//      0:0-2 |   unsigned int a
+//        4:- |   unsigned int
+//      4:0-2 |   unsigned int b
+//      4:3-7 |   unsigned int c
+//            | [sizeof=8, align=4]
+typedef struct {
+    unsigned a: 3;
+    unsigned: 0;
+    unsigned b: 3;
+    unsigned c: 5;
+} split_units;
+
+unsigned int check_split_units(split_units const* bf, unsigned a, unsigned b, unsigned c) {
+    if (bf->a != a) {
+        return 2;
+    }
+
+    if (bf->b != b) {
+        return 3;
+    }
+
+    if (bf->c != c) {
+        return 4;
+    }
+
+    return 1;
+}
+
+// *** Dumping AST Record Layout
+//          0 | struct sensor_reading
+//     0:0-11 |   int temp
+//     1:4-7  |   unsigned int status
+//            | [sizeof=4, align=4]
+typedef struct {
+    int temp: 12;
+    unsigned int status: 4;
+} sensor_reading;
+
+unsigned int check_sensor_reading(sensor_reading const* reading, int temp, unsigned status) {
+    if (reading->temp != temp) {
+        return 2;
+    }
+
+    if (reading->status != status) {
+        return 3;
+    }
+
+    return 1;
+}
+
+void assign_sensor_reading(sensor_reading* reading, int temp, unsigned status) {
+    reading->temp = temp;
+    reading->status = status;
+}
+
+// *** Dumping AST Record Layout
+//          0 | struct status_flags
+//      0:0-2 |   unsigned char mode
+//      0:3-3 |   unsigned char ready
+//      0:4-7 |   unsigned char count
+//            | [sizeof=1, align=1]
+typedef struct {
+    unsigned char mode: 3;
+    unsigned char ready: 1;
+    unsigned char count: 4;
+} status_flags;
+
+unsigned int check_status_flags(status_flags const* flags, uchar mode, uchar ready, uchar count) {
+    if (flags->mode != mode) {
+        return 2;
+    }
+
+    if (flags->ready != ready) {
+        return 3;
+    }
+
+    if (flags->count != count) {
+        return 4;
+    }
+
+    return 1;
+}
@@ -1,6 +1,10 @@
-use c2rust_bitfields::BitfieldStruct;
-use libc::{c_double, c_short, c_uchar, c_uint, c_ulong, c_ushort};
+use c2rust_bitfields::{
+    assert_round_trip, BitfieldDiff, BitfieldEnum, BitfieldError, BitfieldStruct,
+};
+use libc::{c_double, c_int, c_long, c_short, c_uchar, c_uint, c_ulong, c_ushort};
+use std::cell::Cell;
 use std::mem::{size_of, transmute};
+use std::num::{NonZeroI8, NonZeroU8};
 
 #[link(name = "test")]
 extern "C" {
@@ -32,6 +36,10 @@ extern "C" {
         _: c_ushort,
     ) -> c_uint;
     fn assign_three_byte_date(_: *mut ThreeByteDate, _: c_uchar, _: c_uchar, _: c_ushort);
+    fn check_split_units(_: *const SplitUnits, _: c_uint, _: c_uint, _: c_uint) -> c_uint;
+    fn check_sensor_reading(_: *const SensorReading, _: c_int, _: c_uint) -> c_uint;
+    fn assign_sensor_reading(_: *mut SensorReading, _: c_int, _: c_uint);
+    fn check_status_flags(_: *const StatusFlags, _: c_uchar, _: c_uchar, _: c_uchar) -> c_uint;
 }
 
 // *** Dumping AST Record Layout
@@ -42,6 +50,8 @@ extern "C" {
 //           | [sizeof=4, align=2]
 #[repr(C, align(2))]
 #[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(display, debug, view, diff, dump, with_setters, at_offset, if_changed)]
+#[bitfield(explicit_setters, member_getters, try_new, validate_layout)]
 struct CompactDate {
     // Compact combination of d + m
     // which can't be accessed via ptr in C anyway
@@ -81,6 +91,113 @@ fn test_compact_date() {
     }
 }
 
+#[repr(C, align(2))]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(validate_layout)]
+struct BigEndianCompactDate {
+    #[bitfield(name = "d", ty = "libc::c_uchar", bits = "0..=4", endian = "big")]
+    #[bitfield(name = "m", ty = "libc::c_uchar", bits = "8..=11", endian = "big")]
+    d_m: [u8; 2],
+    y: u16,
+}
+
+#[test]
+fn test_big_endian_compact_date() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+    let mut be_date = BigEndianCompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    date.set_d(31);
+    date.set_m(12);
+    be_date.set_d(31);
+    be_date.set_m(12);
+
+    assert_eq!(be_date.d(), 31);
+    assert_eq!(be_date.m(), 12);
+
+    // The bits are the same, but the bytes backing them are reversed
+    assert_eq!(date.d_m, [0b00011111, 0b00001100]);
+    assert_eq!(be_date.d_m, [0b00001100, 0b00011111]);
+
+    be_date.set_d(0);
+
+    assert_eq!(be_date.d_m, [0b00001100, 0]);
+    assert_eq!(be_date.m(), 12);
+
+    // A bitfield straddling both bytes is laid out as a big endian integer
+    #[derive(BitfieldStruct)]
+    struct BigEndianWord {
+        #[bitfield(name = "x", ty = "u16", bits = "0..=11", endian = "big")]
+        x: [u8; 2],
+    }
+
+    let mut word = BigEndianWord { x: [0xF0, 0] };
+
+    word.set_x(0xABC);
+
+    assert_eq!(word.x, [0xFA, 0xBC]);
+    assert_eq!(word.x(), 0xABC);
+}
+
+#[test]
+fn test_compact_date_debug() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    date.set_d(31);
+    date.set_m(12);
+
+    // The bitfields are shown rather than the `d_m` bytes backing them
+    assert_eq!(
+        format!("{:?}", date),
+        "CompactDate { d: 31, m: 12, y: 2014 }"
+    );
+    assert_eq!(
+        format!("{:#?}", date),
+        "CompactDate {\n    d: 31,\n    m: 12,\n    y: 2014,\n}"
+    );
+}
+
+#[repr(C, align(2))]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct HalfOpenCompactDate {
+    #[bitfield(name = "d", ty = "libc::c_uchar", bits = "0..5")]
+    #[bitfield(name = "m", ty = "libc::c_uchar", bits = "8..12")]
+    d_m: [u8; 2],
+    y: u16,
+}
+
+#[test]
+fn test_half_open_bits() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+    let mut half_open = HalfOpenCompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    // `0..5` names the same bits as `0..=4`
+    for &(d, m) in &[(31, 12), (32, 16), (1, 1), (0, 0)] {
+        date.set_d(d);
+        date.set_m(m);
+        half_open.set_d(d);
+        half_open.set_m(m);
+
+        assert_eq!(half_open.d_m, date.d_m);
+        assert_eq!(half_open.d(), date.d());
+        assert_eq!(half_open.m(), date.m());
+    }
+}
+
 #[test]
 fn test_compact_date2() {
     let mut date = CompactDate {
@@ -103,6 +220,316 @@ fn test_compact_date2() {
     //    --13- |     --m- | -2014--> | <--2014-
 }
 
+#[test]
+fn test_compact_date_view() {
+    let mut bytes = [0b00011111, 0b00001100, 0b11011110, 0b00000111, 0xff];
+
+    assert!(CompactDateView::new(&mut bytes[..3]).is_none());
+
+    let mut view = CompactDateView::new(&mut bytes).unwrap();
+
+    assert_eq!(view.d(), 31);
+    assert_eq!(view.m(), 12);
+
+    view.set_d(13);
+    view.set_m(7);
+
+    assert_eq!(view.d(), 13);
+    assert_eq!(view.m(), 7);
+    assert_eq!(
+        bytes,
+        [0b00001101, 0b00000111, 0b11011110, 0b00000111, 0xff]
+    );
+}
+
+#[test]
+fn test_compact_date_diff() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    date.set_d(31);
+    date.set_m(11);
+
+    let mut next_date = date;
+
+    assert!(date.diff(&next_date).is_empty());
+
+    next_date.set_m(12);
+
+    assert_eq!(date.diff(&next_date), ["m"]);
+    assert_eq!(next_date.diff(&date), ["m"]);
+
+    let mut changed = Vec::new();
+
+    date.for_each_diff(&next_date, |name| changed.push(name));
+
+    assert_eq!(changed, ["m"]);
+}
+
+#[test]
+fn test_compact_date_dump() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    assert_eq!(date.dump(), [("d", 0), ("m", 0)]);
+
+    date.set_d(31);
+    date.set_m(12);
+
+    assert_eq!(date.dump(), [("d", 31), ("m", 12)]);
+}
+
+#[test]
+fn test_compact_date_grouped_getter() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    assert_eq!(date.d_m(), (0, 0));
+
+    date.set_d(17);
+    date.set_m(9);
+
+    let (d, m) = date.d_m();
+
+    assert_eq!((d, m), (date.d(), date.m()));
+    assert_eq!((d, m), (17, 9));
+}
+
+#[test]
+fn test_compact_date_with_setters() {
+    let date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    }
+    .with_d(31)
+    .with_m(12);
+
+    assert_eq!(date.d(), 31);
+    assert_eq!(date.m(), 12);
+    assert_eq!(date.y, 2014);
+
+    // Like the plain setters, values too wide for the bitfield are truncated
+    let date = date.with_m(0x1f);
+
+    assert_eq!(date.m(), 0xf);
+    assert_eq!(date.d(), 31);
+}
+
+#[test]
+fn test_compact_date_if_changed() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    assert!(date.set_d_if_changed(31));
+    assert!(date.set_m_if_changed(12));
+
+    let before: [u8; 4] = unsafe { transmute(date) };
+
+    assert!(!date.set_d_if_changed(31));
+    assert!(!date.set_m_if_changed(12));
+
+    // 0x1c is truncated to 12, which the bitfield already holds
+    assert!(!date.set_m_if_changed(0x1c));
+
+    let after: [u8; 4] = unsafe { transmute(date) };
+
+    assert_eq!(before, after);
+
+    assert!(date.set_m_if_changed(11));
+    assert_eq!(date.m(), 11);
+    assert_eq!(date.d(), 31);
+}
+
+#[test]
+fn test_compact_date_at_offset() {
+    use c2rust_bitfields::ByteLengthError;
+
+    // A date laid out at a runtime offset within a larger buffer
+    let mut buffer = [0xffu8; 10];
+    let base = 3;
+
+    buffer[base..base + 4].copy_from_slice(&[0; 4]);
+
+    CompactDate::set_d_at(&mut buffer, base, 31).unwrap();
+    CompactDate::set_m_at(&mut buffer, base, 12).unwrap();
+
+    assert_eq!(CompactDate::d_at(&buffer, base), Ok(31));
+    assert_eq!(CompactDate::m_at(&buffer, base), Ok(12));
+
+    let mut date_bytes = [0; 4];
+
+    date_bytes.copy_from_slice(&buffer[base..base + 4]);
+
+    let date: CompactDate = unsafe { transmute(date_bytes) };
+
+    assert_eq!(date.d(), 31);
+    assert_eq!(date.m(), 12);
+
+    // Bytes outside the date are left alone
+    assert_eq!(buffer[..base], [0xff; 3]);
+    assert_eq!(buffer[base + 4..], [0xff; 3]);
+
+    // Only the backing member needs to fit, not the whole struct
+    assert_eq!(CompactDate::d_at(&buffer, 8), Ok(0x1f));
+    assert_eq!(
+        CompactDate::d_at(&buffer, 9),
+        Err(ByteLengthError::new(11, 10))
+    );
+    assert_eq!(
+        CompactDate::set_m_at(&mut buffer, 9, 1),
+        Err(ByteLengthError::new(11, 10))
+    );
+    assert_eq!(buffer[9], 0xff);
+}
+
+#[test]
+fn test_explicit_setters() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    // d is 5 bits wide, so 33 is truncated to 1
+    date.wrapping_set_d(33);
+
+    assert_eq!(date.d(), 1);
+
+    date.wrapping_set_d(31);
+
+    assert_eq!(date.d(), 31);
+
+    assert_eq!(date.checked_set_d(17), Ok(()));
+    assert_eq!(date.d(), 17);
+
+    let err = date.checked_set_d(33).unwrap_err();
+
+    assert_eq!(err, BitfieldError::new("d", 5, 33));
+    // A rejected value leaves the bitfield untouched
+    assert_eq!(date.d(), 17);
+    assert_eq!(date.m(), 0);
+}
+
+#[test]
+fn test_signed_nibble_bounds() {
+    let mut signed = SignedBitfields { x_y_z: [0; 2] };
+
+    // x is a 4 bit signed field, so -8 and 7 are its bounds
+    for value in [-8, -1, 0, 7] {
+        signed.set_x(value);
+
+        assert_eq!(signed.x(), value);
+        assert_eq!(signed.checked_set_x(value), Ok(()));
+        assert_eq!(signed.x(), value);
+
+        signed.saturating_set_x(value);
+
+        assert_eq!(signed.x(), value);
+    }
+
+    signed.set_x(-8);
+
+    // Just out of range values are rejected rather than written
+    assert_eq!(
+        signed.checked_set_x(-9),
+        Err(BitfieldError::new("x", 4, -9))
+    );
+    assert_eq!(signed.checked_set_x(8), Err(BitfieldError::new("x", 4, 8)));
+    assert_eq!(signed.x(), -8);
+
+    // Clamped to the nearest bound
+    signed.saturating_set_x(-9);
+
+    assert_eq!(signed.x(), -8);
+
+    signed.saturating_set_x(8);
+
+    assert_eq!(signed.x(), 7);
+
+    // Or truncated to their low 4 bits and sign extended on the way out
+    signed.wrapping_set_x(-9);
+
+    assert_eq!(signed.x(), 7);
+
+    signed.wrapping_set_x(8);
+
+    assert_eq!(signed.x(), -8);
+
+    // None of which touches the neighbouring fields
+    assert_eq!(signed.y(), 0);
+    assert_eq!(signed.z(), 0);
+}
+
+#[test]
+fn test_saturating_setters() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    // d is 5 bits wide, so 33 is clamped to 31 rather than truncated to 1
+    date.saturating_set_d(33);
+
+    assert_eq!(date.d(), 31);
+
+    date.saturating_set_d(12);
+
+    assert_eq!(date.d(), 12);
+    assert_eq!(date.m(), 0);
+
+    let mut signed = SignedBitfields { x_y_z: [0; 2] };
+
+    // x is a 4 bit signed field, holding -8..=7
+    signed.saturating_set_x(100);
+
+    assert_eq!(signed.x(), 7);
+
+    signed.saturating_set_x(-100);
+
+    assert_eq!(signed.x(), -8);
+
+    signed.saturating_set_x(-3);
+
+    assert_eq!(signed.x(), -3);
+
+    // y is a 5 bit unsigned field
+    signed.saturating_set_y(u16::MAX);
+
+    assert_eq!(signed.y(), 31);
+    assert_eq!(signed.x(), -3);
+    assert_eq!(signed.z(), 0);
+}
+
+#[test]
+fn test_validate_layout() {
+    assert_eq!(CompactDate::validate_layout(), Ok(()));
+    assert_eq!(SignedBitfields::validate_layout(), Ok(()));
+    // Big endian and `Cell` members are written through their own setters
+    assert_eq!(BigEndianCompactDate::validate_layout(), Ok(()));
+    assert_eq!(Ipv4Start::validate_layout(), Ok(()));
+    assert_eq!(SharedReg::validate_layout(), Ok(()));
+    // As are accessors moved into a trait
+    assert_eq!(accessor_traits::Timer::validate_layout(), Ok(()));
+}
+
+#[test]
+fn test_round_trip_macro() {
+    let mut date = ThreeByteDate {
+        day_month_year: [0; 3],
+    };
+
+    assert_round_trip!(date, day, set_day, [0, 1, 21, 31]);
+    assert_round_trip!(date, month, set_month, [0, 11, 15]);
+    assert_round_trip!(date, year, set_year, [0, 1984, 0x7FFF]);
+}
+
 #[test]
 fn test_overflow() {
     let mut date = CompactDate {
@@ -179,6 +606,35 @@ fn test_overflow() {
     }
 }
 
+#[test]
+fn test_checked_overflow() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    date.set_d(31);
+    date.set_m(12);
+
+    let before: [u8; 4] = unsafe { transmute(date) };
+
+    // Where `set_d(32)` wraps to 0 like C, the checked setters refuse it
+    assert_eq!(date.try_set_d(32), Err(BitfieldError::new("d", 5, 32)));
+    assert_eq!(date.checked_set_d(32), Err(BitfieldError::new("d", 5, 32)));
+
+    let after: [u8; 4] = unsafe { transmute(date) };
+
+    assert_eq!(before, after);
+
+    assert_eq!(date.try_set_d(17), Ok(()));
+    assert_eq!(date.d(), 17);
+    assert_eq!(date.m(), 12);
+
+    date.set_d(32);
+
+    assert_eq!(date.d(), 0);
+}
+
 // *** Dumping AST Record Layout
 //         0 | struct overlapping_byte_date
 //     0:0-4 |   unsigned long d
@@ -293,16 +749,115 @@ fn test_unnamed_bitfield() {
 }
 
 // *** Dumping AST Record Layout
-//          0 | struct signed_bitfields
-//      0:0-3 |   short x
-//      0:4-8 |   unsigned short y
-//      1:1-5 |   short z
-//            | [sizeof=2, align=2]
-#[repr(C, align(2))]
+//          0 | struct split_units
+//      0:0-2 |   unsigned int a
+//        4:- |   unsigned int
+//      4:0-2 |   unsigned int b
+//      4:3-7 |   unsigned int c
+//            | [sizeof=8, align=4]
+#[repr(C, align(4))]
 #[derive(BitfieldStruct, Copy, Clone)]
-struct SignedBitfields {
-    #[bitfield(name = "x", ty = "libc::c_short", bits = "0..=3")]
-    #[bitfield(name = "y", ty = "libc::c_ushort", bits = "4..=8")]
+struct SplitUnits {
+    #[bitfield(name = "a", ty = "libc::c_uint", bits = "0..=2")]
+    a: [u8; 1],
+    // The zero width bitfield ends the storage unit of `a`
+    #[bitfield(padding)]
+    _pad: [u8; 3],
+    #[bitfield(name = "b", ty = "libc::c_uint", bits = "0..=2")]
+    #[bitfield(name = "c", ty = "libc::c_uint", bits = "3..=7")]
+    b_c: [u8; 1],
+    #[bitfield(padding)]
+    _pad2: [u8; 3],
+}
+
+#[test]
+fn test_zero_width_boundary() {
+    assert_eq!(size_of::<SplitUnits>(), 8);
+
+    let mut split = SplitUnits {
+        a: [0; 1],
+        _pad: [0; 3],
+        b_c: [0; 1],
+        _pad2: [0; 3],
+    };
+
+    split.set_a(5);
+    split.set_b(6);
+    split.set_c(17);
+
+    unsafe { assert_eq!(check_split_units(&split, 5, 6, 17), 1) }
+
+    let bytes: [u8; 8] = unsafe { transmute(split) };
+
+    assert_eq!(bytes, [5, 0, 0, 0, 6 | 17 << 3, 0, 0, 0]);
+}
+
+// *** Dumping AST Record Layout
+//          0 | struct sensor_reading
+//     0:0-11 |   int temp
+//     1:4-7  |   unsigned int status
+//            | [sizeof=4, align=4]
+#[repr(C, align(4))]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct SensorReading {
+    #[bitfield(name = "temp", ty = "libc::c_int", bits = "0..=11")]
+    #[bitfield(name = "status", ty = "libc::c_uint", bits = "12..=15")]
+    temp_status: [u8; 2],
+    #[bitfield(padding)]
+    _pad: [u8; 2],
+}
+
+#[test]
+fn test_sensor_reading() {
+    assert_eq!(size_of::<SensorReading>(), 4);
+
+    let mut reading = SensorReading {
+        temp_status: [0; 2],
+        _pad: [0; 2],
+    };
+
+    reading.set_temp(-5);
+    reading.set_status(9);
+
+    // The 12 bit field is sign extended rather than read back as 4091
+    assert_eq!(reading.temp(), -5);
+    assert_eq!(reading.status(), 9);
+
+    // Test C byte compatibility
+    let reading_bytes: [u8; 4] = unsafe { transmute(reading) };
+
+    assert_eq!(reading_bytes, [0b11111011, 0b10011111, 0, 0]);
+    // -5 is 0xFFB in 12 bits, followed by 9 in the next nibble
+
+    unsafe {
+        assert_eq!(check_sensor_reading(&reading, -5, 9), 1);
+    }
+
+    // The bounds of a 12 bit signed field round trip through C too
+    for temp in [-2048, -1, 0, 2047] {
+        unsafe { assign_sensor_reading(&mut reading, temp, 15) };
+
+        assert_eq!(reading.temp(), temp);
+        assert_eq!(reading.status(), 15);
+
+        reading.set_temp(temp);
+
+        unsafe { assert_eq!(check_sensor_reading(&reading, temp, 15), 1) }
+    }
+}
+
+// *** Dumping AST Record Layout
+//          0 | struct signed_bitfields
+//      0:0-3 |   short x
+//      0:4-8 |   unsigned short y
+//      1:1-5 |   short z
+//            | [sizeof=2, align=2]
+#[repr(C, align(2))]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(explicit_setters, validate_layout)]
+struct SignedBitfields {
+    #[bitfield(name = "x", ty = "libc::c_short", bits = "0..=3")]
+    #[bitfield(name = "y", ty = "libc::c_ushort", bits = "4..=8")]
     #[bitfield(name = "z", ty = "libc::c_short", bits = "9..=13")]
     x_y_z: [u8; 2],
 }
@@ -342,6 +897,29 @@ fn test_signed_bitfields() {
     assert_eq!(ret, 1);
 }
 
+#[test]
+fn test_const_getters() {
+    const DATE: CompactDate = CompactDate {
+        d_m: [0b00011111, 0b00001100],
+        y: 2014,
+    };
+    const DAY: c_uchar = DATE.d();
+    const SIGNED: SignedBitfields = SignedBitfields {
+        x_y_z: [0b01011010, 0b00100110],
+    };
+    const X: c_short = SIGNED.x();
+
+    // Getters can size arrays, as C code using bitfields in constant
+    // expressions would need
+    let months = [0u8; DATE.m() as usize];
+
+    assert_eq!(DAY, 31);
+    assert_eq!(months.len(), 12);
+    assert_eq!(X, -6);
+    assert_eq!(SIGNED.y(), 5);
+    assert_eq!(SIGNED.z(), -13);
+}
+
 #[test]
 fn test_signed_underflow_overflow() {
     let mut signed_bitfields = SignedBitfields { x_y_z: [0; 2] };
@@ -531,6 +1109,7 @@ fn test_single_bits() {
 //            | [sizeof=3, align=1]
 #[repr(C, align(1))]
 #[derive(BitfieldStruct)]
+#[bitfield(member_matches, member_zero)]
 struct ThreeByteDate {
     #[bitfield(name = "day", ty = "libc::c_uchar", bits = "0..=4")]
     #[bitfield(name = "month", ty = "libc::c_uchar", bits = "5..=8")]
@@ -620,3 +1199,1324 @@ fn test_bool_bits() {
     assert!(bool_bits.y());
     assert!(bool_bits.z());
 }
+
+// *** Dumping AST Record Layout
+//          0 | struct status_flags
+//      0:0-2 |   unsigned char mode
+//      0:3-3 |   unsigned char ready
+//      0:4-7 |   unsigned char count
+//            | [sizeof=1, align=1]
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct StatusFlags {
+    #[bitfield(name = "mode", ty = "libc::c_uchar", bits = "0..=2")]
+    #[bitfield(name = "ready", ty = "bool", bits = "3..=3")]
+    #[bitfield(name = "count", ty = "libc::c_uchar", bits = "4..=7")]
+    mode_ready_count: [u8; 1],
+}
+
+#[test]
+fn test_bool_between_ints() {
+    let mut flags = StatusFlags {
+        mode_ready_count: [0; 1],
+    };
+
+    flags.set_mode(5);
+    flags.set_ready(true);
+    flags.set_count(9);
+
+    assert_eq!(flags.mode(), 5);
+    assert!(flags.ready());
+    assert_eq!(flags.count(), 9);
+
+    // Test C byte compatibility
+    let bytes: [u8; 1] = unsafe { transmute(flags) };
+
+    // count 9, ready, mode 5
+    assert_eq!(bytes, [0b10011101]);
+
+    unsafe { assert_eq!(check_status_flags(&flags, 5, 1, 9), 1) }
+
+    flags.set_ready(false);
+
+    assert_eq!(flags.mode(), 5);
+    assert!(!flags.ready());
+    assert_eq!(flags.count(), 9);
+
+    unsafe { assert_eq!(check_status_flags(&flags, 5, 0, 9), 1) }
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct ShorthandBits {
+    #[bitfield(name = "ready", ty = "bool", bits = "3")]
+    #[bitfield(name = "parity", ty = "u8", bits = "5")]
+    ctrl: [u8; 1],
+}
+
+#[test]
+fn test_single_bit_shorthand() {
+    // Every bit but 3 is set
+    let mut bits = ShorthandBits {
+        ctrl: [0b1111_0111],
+    };
+
+    assert!(!bits.ready());
+    // Only bit 5 is read, not its set neighbours
+    assert_eq!(bits.parity(), 1);
+
+    bits.ctrl = [0];
+    bits.set_ready(true);
+
+    assert_eq!(bits.ctrl, [0b0000_1000]);
+
+    bits.set_parity(3);
+
+    // Only the low bit of the value fits
+    assert_eq!(bits.ctrl, [0b0010_1000]);
+    assert_eq!(bits.parity(), 1);
+}
+
+#[repr(transparent)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(member_matches, member_zero)]
+struct TransparentReg {
+    #[bitfield(name = "enable", ty = "bool", bits = "0..=0")]
+    #[bitfield(name = "mode", ty = "u8", bits = "1..=3")]
+    #[bitfield(name = "divisor", ty = "u16", bits = "8..=23")]
+    #[bitfield(name = "status", ty = "u8", bits = "28..=31")]
+    raw: u32,
+}
+
+#[test]
+fn test_transparent_reg() {
+    assert_eq!(size_of::<TransparentReg>(), size_of::<u32>());
+
+    let mut reg = TransparentReg { raw: 0 };
+
+    reg.set_enable(true);
+    reg.set_mode(5);
+    reg.set_divisor(0xBEEF);
+    reg.set_status(0xA);
+
+    assert!(reg.enable());
+    assert_eq!(reg.mode(), 5);
+    assert_eq!(reg.divisor(), 0xBEEF);
+    assert_eq!(reg.status(), 0xA);
+    assert_eq!(reg.raw, 0xA0BE_EF0B);
+
+    reg.raw = 0x5012_3404;
+
+    assert!(!reg.enable());
+    assert_eq!(reg.mode(), 2);
+    assert_eq!(reg.divisor(), 0x1234);
+    assert_eq!(reg.status(), 0x5);
+}
+
+#[test]
+fn test_member_matches() {
+    let mut reg = TransparentReg { raw: 0 };
+
+    reg.set_status(0x8);
+    reg.set_mode(3);
+
+    assert!(reg.raw_matches(0xF000_0000, 0x8000_0000));
+    assert!(!reg.raw_matches(0xF000_0000, 0x4000_0000));
+    assert!(reg.raw_matches(0b1110, 0b0110));
+
+    let mut date = ThreeByteDate {
+        day_month_year: [0; 3],
+    };
+
+    date.set_day(18);
+    date.set_month(7);
+
+    // 24 bit members are compared as the low bytes of a `u32`
+    assert!(date.day_month_year_matches(0x1F, 18));
+    assert!(date.day_month_year_matches(0x1E0, 7 << 5));
+    assert!(!date.day_month_year_matches(0x1F, 17));
+}
+
+#[test]
+fn test_member_zero_helpers() {
+    let mut reg = TransparentReg { raw: 0 };
+
+    assert!(reg.raw_is_zero());
+
+    reg.set_enable(true);
+    reg.set_divisor(0xBEEF);
+
+    assert!(!reg.raw_is_zero());
+
+    reg.clear_raw();
+
+    assert!(reg.raw_is_zero());
+    assert!(!reg.enable());
+    assert_eq!(reg.mode(), 0);
+    assert_eq!(reg.divisor(), 0);
+    assert_eq!(reg.status(), 0);
+
+    let mut date = ThreeByteDate {
+        day_month_year: [0; 3],
+    };
+
+    date.set_year(2000);
+
+    assert!(!date.day_month_year_is_zero());
+
+    date.clear_day_month_year();
+
+    assert!(date.day_month_year_is_zero());
+    assert_eq!(date.day(), 0);
+    assert_eq!(date.month(), 0);
+    assert_eq!(date.year(), 0);
+
+    // `Cell` members are cleared through a shared reference
+    let shared = SharedReg {
+        ctrl: Cell::new([0; 2]),
+        counter: Cell::new(0xF000),
+    };
+
+    configure(&shared);
+    shared.clear_ctrl();
+
+    assert!(shared.ctrl_is_zero());
+    assert!(!shared.counter_is_zero());
+    assert!(!shared.enable());
+    assert_eq!(shared.prescaler(), 0);
+    assert_eq!(shared.count(), 0x123);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct CfgBitfields {
+    #[bitfield(name = "always", ty = "u8", bits = "0..=3")]
+    #[bitfield(name = "only_test", ty = "u8", bits = "4..=5", cfg = "test")]
+    #[bitfield(name = "never", ty = "u8", bits = "6..=7", cfg = "any()")]
+    flags: [u8; 1],
+}
+
+#[test]
+fn test_cfg_bitfields() {
+    let mut bitfields = CfgBitfields { flags: [0xFF] };
+
+    bitfields.set_always(0);
+    bitfields.set_only_test(1);
+
+    assert_eq!(bitfields.always(), 0);
+    assert_eq!(bitfields.only_test(), 1);
+
+    // The bits of the cfg'd out field are left untouched
+    assert_eq!(bitfields.flags, [0b11010000]);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(ord_by = "priority")]
+struct Task {
+    #[bitfield(name = "id", ty = "u8", bits = "0..=3")]
+    #[bitfield(name = "priority", ty = "i8", bits = "4..=7")]
+    id_priority: [u8; 1],
+}
+
+impl PartialEq for Task {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority() == other.priority()
+    }
+}
+
+impl Eq for Task {}
+
+#[test]
+fn test_ord_by() {
+    let task = |id, priority| {
+        let mut task = Task { id_priority: [0] };
+
+        task.set_id(id);
+        task.set_priority(priority);
+        task
+    };
+    let mut tasks = vec![task(0, 3), task(1, -2), task(2, 7), task(3, 3), task(4, -8)];
+
+    tasks.sort();
+
+    let ids: Vec<_> = tasks.iter().map(Task::id).collect();
+    let priorities: Vec<_> = tasks.iter().map(Task::priority).collect();
+
+    // Priorities are compared as signed values and the sort is stable
+    assert_eq!(ids, [4, 1, 0, 3, 2]);
+    assert_eq!(priorities, [-8, -2, 3, 3, 7]);
+    assert!(task(9, 1) < task(0, 2));
+    assert!(task(9, 2) == task(0, 2));
+}
+
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(storage = "u32", try_new)]
+#[bitfield(name = "ready", ty = "bool", bits = "0..=0")]
+#[bitfield(name = "channel", ty = "u8", bits = "1..=4")]
+#[bitfield(name = "offset", ty = "i16", bits = "16..=27")]
+struct SynthesizedStorage(u32);
+
+#[test]
+fn test_synthesized_storage() {
+    let mut storage = SynthesizedStorage(0);
+
+    storage.set_ready(true);
+    storage.set_channel(9);
+    storage.set_offset(-300);
+
+    assert!(storage.ready());
+    assert_eq!(storage.channel(), 9);
+    assert_eq!(storage.offset(), -300);
+    assert_eq!(storage.0, 0x0ED4_0013);
+    assert_eq!(size_of::<SynthesizedStorage>(), size_of::<u32>());
+
+    storage.set_channel(0);
+
+    assert!(storage.ready());
+    assert_eq!(storage.channel(), 0);
+    assert_eq!(storage.offset(), -300);
+}
+
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(storage = "auto")]
+#[bitfield(name = "enabled", ty = "bool", bits = "0..=0")]
+#[bitfield(name = "mode", ty = "u8", bits = "1..=3")]
+#[bitfield(name = "count", ty = "u8", bits = "4..=11")]
+struct AutoReg(AutoRegStorage);
+
+#[test]
+fn test_auto_storage() {
+    // 12 bits of bitfields fit in a u16
+    assert_eq!(AutoReg::STORAGE_TYPE, "u16");
+    assert_eq!(size_of::<AutoReg>(), size_of::<u16>());
+
+    let mut reg = AutoReg(0);
+
+    reg.set_enabled(true);
+    reg.set_mode(5);
+    reg.set_count(200);
+
+    assert!(reg.enabled());
+    assert_eq!(reg.mode(), 5);
+    assert_eq!(reg.count(), 200);
+
+    let raw: u16 = reg.0;
+
+    assert_eq!(raw, 0x0C8B);
+}
+
+// Too wide for an integer, and for arrays to implement `Default`
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(storage = "auto", try_new)]
+#[bitfield(name = "low", ty = "u8", bits = "0..=7")]
+#[bitfield(name = "high", ty = "u16", bits = "290..=299")]
+struct AutoWideReg(AutoWideRegStorage);
+
+#[test]
+fn test_auto_storage_try_new() {
+    assert_eq!(AutoWideReg::STORAGE_TYPE, "[u8; 38]");
+
+    let reg = AutoWideReg::try_new(3, 1000).unwrap();
+
+    assert_eq!(reg.low(), 3);
+    assert_eq!(reg.high(), 1000);
+    assert!(reg.0[1..36].iter().all(|&byte| byte == 0));
+    assert!(AutoWideReg::try_new(3, 1024).is_err());
+}
+
+#[repr(i8)]
+#[derive(BitfieldEnum, Copy, Clone, PartialEq, Debug)]
+enum Level {
+    Low = -4,
+    Negative = -1,
+    Zero = 0,
+    High = 3,
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(if_changed)]
+struct SignedEnumBitfield {
+    #[bitfield(name = "level", ty = "i8", bits = "1..=3", enum_ty = "Level")]
+    #[bitfield(name = "raw_level", ty = "i8", bits = "1..=3", allow_overlap)]
+    flags: [u8; 1],
+}
+
+#[test]
+fn test_signed_enum_bitfield() {
+    let mut bitfield = SignedEnumBitfield { flags: [0] };
+
+    for &level in &[Level::Low, Level::Negative, Level::Zero, Level::High] {
+        bitfield.set_level(level);
+
+        assert_eq!(bitfield.level(), Some(level));
+        assert_eq!(bitfield.raw_level(), level as i8);
+    }
+
+    bitfield.set_level(Level::Low);
+
+    assert_eq!(bitfield.flags, [0b1000]);
+
+    // -2 is representable in the field but isn't a discriminant of `Level`
+    bitfield.set_raw_level(-2);
+
+    assert_eq!(bitfield.level(), None);
+}
+
+#[test]
+fn test_enum_if_changed_setter() {
+    // -2 isn't a discriminant of `Level`, which is fine as raw bits are compared
+    let mut bitfield = SignedEnumBitfield { flags: [0b1100] };
+
+    assert!(bitfield.set_level_if_changed(Level::Low));
+    assert_eq!(bitfield.flags, [0b1000]);
+    assert!(!bitfield.set_level_if_changed(Level::Low));
+    assert!(bitfield.set_level_if_changed(Level::High));
+    assert_eq!(bitfield.level(), Some(Level::High));
+}
+
+#[repr(u8)]
+#[derive(BitfieldEnum, Copy, Clone, PartialEq, Debug)]
+enum State {
+    Idle = 0,
+    Running = 1,
+    Paused = 2,
+    Stopped = 3,
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct StateReg {
+    #[bitfield(name = "state", ty = "u8", bits = "4..=5", enum_ty = "State")]
+    ctrl: [u8; 1],
+}
+
+#[test]
+fn test_two_bit_enum_bitfield() {
+    let mut reg = StateReg {
+        ctrl: [0b1100_1111],
+    };
+
+    // Every 2 bit pattern is a variant, so the getter never returns `None`
+    for &state in &[State::Idle, State::Running, State::Paused, State::Stopped] {
+        reg.set_state(state);
+
+        assert_eq!(reg.state(), Some(state));
+        assert_eq!(reg.ctrl, [0b1100_1111 | ((state as u8) << 4)]);
+    }
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct)]
+#[bitfield(member_zero, validate_layout)]
+struct SharedReg {
+    #[bitfield(name = "enable", ty = "bool", bits = "0..=0")]
+    #[bitfield(name = "prescaler", ty = "u8", bits = "4..=11")]
+    ctrl: Cell<[u8; 2]>,
+    #[bitfield(name = "count", ty = "u16", bits = "0..=11")]
+    counter: Cell<u16>,
+}
+
+fn configure(reg: &SharedReg) {
+    reg.set_enable(true);
+    reg.set_prescaler(0xAB);
+    reg.set_count(0x123);
+}
+
+#[test]
+fn test_cell_setters() {
+    let reg = SharedReg {
+        ctrl: Cell::new([0; 2]),
+        counter: Cell::new(0xF000),
+    };
+    let alias = &reg;
+
+    configure(&reg);
+
+    assert!(alias.enable());
+    assert_eq!(alias.prescaler(), 0xAB);
+    assert_eq!(alias.count(), 0x123);
+    assert_eq!(reg.ctrl.get(), [0xB1, 0x0A]);
+    assert_eq!(reg.counter.get(), 0xF123);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct ScanBitfields {
+    #[bitfield(name = "mask", ty = "u32", bits = "3..=14", bit_scan)]
+    #[bitfield(name = "delta", ty = "i8", bits = "15..=19", bit_scan)]
+    bits: [u8; 3],
+}
+
+#[test]
+fn test_bit_scan_helpers() {
+    let mut scan = ScanBitfields { bits: [0xFF; 3] };
+
+    scan.set_mask(0b0000_0110_0000);
+    scan.set_delta(3);
+
+    assert_eq!(scan.mask_trailing_zeros(), 5);
+    assert_eq!(scan.mask_leading_zeros(), 5);
+    assert_eq!(scan.mask_trailing_zeros(), scan.mask().trailing_zeros());
+    assert_eq!(scan.delta_trailing_zeros(), 0);
+    assert_eq!(scan.delta_leading_zeros(), 3);
+
+    // Zero counts as the bitfield's width rather than the integer type's
+    scan.set_mask(0);
+    scan.set_delta(-16);
+
+    assert_eq!(scan.mask_trailing_zeros(), 12);
+    assert_eq!(scan.mask_leading_zeros(), 12);
+    assert_eq!(scan.delta_trailing_zeros(), 4);
+    assert_eq!(scan.delta_leading_zeros(), 0);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct RawBitfields {
+    #[bitfield(name = "d", ty = "u8", bits = "0..=4", raw)]
+    #[bitfield(name = "m", ty = "u8", bits = "5..=8", raw)]
+    #[bitfield(name = "y", ty = "u16", bits = "9..=23", raw)]
+    d_m_y: [u8; 3],
+    #[bitfield(name = "sign", ty = "bool", bits = "15..=15", raw)]
+    flags: i16,
+}
+
+#[test]
+fn test_raw_getters() {
+    let mut raw = RawBitfields {
+        d_m_y: [0xFF; 3],
+        flags: 0x7FFF,
+    };
+
+    raw.set_d(18);
+    raw.set_m(7);
+    raw.set_y(2000);
+
+    assert_eq!(raw.d_raw(), 18);
+    assert_eq!(raw.m_raw(), 7 << 5);
+    assert_eq!(raw.y_raw(), 2000 << 9);
+    assert_eq!(raw.m_raw() >> 5, raw.m() as u32);
+    assert_eq!(raw.y_raw() >> 9, raw.y() as u32);
+
+    // The in place fields OR back together into the whole member
+    let whole = u32::from_le_bytes([raw.d_m_y[0], raw.d_m_y[1], raw.d_m_y[2], 0]);
+
+    assert_eq!(raw.d_raw() | raw.m_raw() | raw.y_raw(), whole);
+    assert_eq!(raw.sign_raw(), 0);
+
+    raw.set_sign(true);
+
+    assert_eq!(raw.sign_raw(), i16::MIN);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(try_new)]
+struct FullWidthBitfields {
+    #[bitfield(name = "word", ty = "u32", bits = "0..=31", raw)]
+    word: u32,
+    #[bitfield(name = "signed_word", ty = "i32", bits = "0..=31")]
+    signed_word: [u8; 4],
+    #[bitfield(name = "wide", ty = "u128", bits = "0..=127", raw)]
+    wide: [u8; 16],
+    #[bitfield(name = "signed_wide", ty = "i128", bits = "0..=127")]
+    signed_wide: [u8; 16],
+}
+
+#[test]
+fn test_full_width_bitfields() {
+    let mut full = FullWidthBitfields {
+        word: 0,
+        signed_word: [0; 4],
+        wide: [0; 16],
+        signed_wide: [0; 16],
+    };
+
+    for &value in &[0, 1, 0x8000_0000, u32::MAX] {
+        full.set_word(value);
+
+        assert_eq!(full.word(), value);
+        assert_eq!(full.word_raw(), value);
+    }
+
+    for &value in &[i32::MIN, -1, 0, i32::MAX] {
+        full.set_signed_word(value);
+
+        assert_eq!(full.signed_word(), value);
+    }
+
+    for &value in &[0, 1 << 127, u128::MAX] {
+        full.set_wide(value);
+
+        assert_eq!(full.wide(), value);
+        assert_eq!(full.wide_raw(), value);
+    }
+
+    for &value in &[i128::MIN, -1, 0, i128::MAX] {
+        full.set_signed_wide(value);
+
+        assert_eq!(full.signed_wide(), value);
+    }
+
+    assert!(FullWidthBitfields::try_new(u32::MAX, -1, u128::MAX, i128::MIN).is_ok());
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct UnionStyleReg {
+    #[bitfield(allow_overlap)]
+    #[bitfield(name = "raw", ty = "u16", bits = "0..=15")]
+    #[bitfield(name = "low", ty = "u8", bits = "0..=7")]
+    #[bitfield(name = "high", ty = "u8", bits = "8..=15")]
+    #[bitfield(name = "top_bit", ty = "bool", bits = "15..=15")]
+    reg: [u8; 2],
+}
+
+#[test]
+fn test_allow_overlap() {
+    let mut reg = UnionStyleReg { reg: [0; 2] };
+
+    reg.set_raw(0xBEEF);
+
+    assert_eq!(reg.low(), 0xEF);
+    assert_eq!(reg.high(), 0xBE);
+    assert!(reg.top_bit());
+
+    reg.set_low(0x12);
+    reg.set_top_bit(false);
+
+    assert_eq!(reg.raw(), 0x3E12);
+    assert_eq!(reg.high(), 0x3E);
+}
+
+#[cfg(feature = "unchecked")]
+#[test]
+fn test_unchecked_accessors() {
+    let mut date = ThreeByteDate {
+        day_month_year: [0; 3],
+    };
+    let mut unchecked_date = ThreeByteDate {
+        day_month_year: [0; 3],
+    };
+
+    date.set_day(21);
+    date.set_month(11);
+    date.set_year(1984);
+
+    unsafe {
+        unchecked_date.set_day_unchecked(21);
+        unchecked_date.set_month_unchecked(11);
+        unchecked_date.set_year_unchecked(1984);
+
+        assert_eq!(unchecked_date.day_unchecked(), date.day());
+        assert_eq!(unchecked_date.month_unchecked(), date.month());
+        assert_eq!(unchecked_date.year_unchecked(), date.year());
+    }
+
+    assert_eq!(unchecked_date.day_month_year, date.day_month_year);
+}
+
+#[cfg(feature = "unchecked")]
+#[test]
+fn test_unchecked_signed_and_bool_accessors() {
+    let mut storage = SynthesizedStorage(0);
+    let mut full = FullWidthBitfields {
+        word: 0,
+        signed_word: [0; 4],
+        wide: [0; 16],
+        signed_wide: [0; 16],
+    };
+
+    unsafe {
+        storage.set_ready_unchecked(true);
+        storage.set_offset_unchecked(-300);
+        full.set_signed_wide_unchecked(i128::MIN + 5);
+
+        assert!(storage.ready_unchecked());
+        assert_eq!(storage.offset_unchecked(), -300);
+        assert_eq!(full.signed_wide_unchecked(), i128::MIN + 5);
+
+        storage.set_ready_unchecked(false);
+
+        assert!(!storage.ready_unchecked());
+    }
+
+    assert_eq!(storage.0, 0x0ED4_0000);
+    assert_eq!(full.signed_wide(), i128::MIN + 5);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct Flags {
+    #[bitfield(flags = "a, b, c, d, e, f, g, h", ty = "bool")]
+    flags: u8,
+}
+
+#[test]
+fn test_flags_shorthand() {
+    let mut flags = Flags { flags: 0 };
+
+    flags.set_a(true);
+    flags.set_c(true);
+    flags.set_h(true);
+
+    assert_eq!(flags.flags, 0b1000_0101);
+    assert_eq!(
+        [
+            flags.a(),
+            flags.b(),
+            flags.c(),
+            flags.d(),
+            flags.e(),
+            flags.f(),
+            flags.g(),
+            flags.h(),
+        ],
+        [true, false, true, false, false, false, false, true]
+    );
+
+    flags.set_a(false);
+    flags.set_b(true);
+    flags.set_d(true);
+    flags.set_e(true);
+    flags.set_f(true);
+    flags.set_g(true);
+    flags.set_h(false);
+
+    assert_eq!(flags.flags, 0b0111_1110);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct NonZeroBitfields {
+    #[bitfield(name = "index", ty = "core::num::NonZeroU8", bits = "0..=3")]
+    #[bitfield(name = "offset", ty = "NonZeroI8", bits = "4..=7")]
+    index_offset: [u8; 1],
+}
+
+#[test]
+fn test_non_zero_bitfields() {
+    let mut bitfields = NonZeroBitfields { index_offset: [0] };
+
+    assert_eq!(bitfields.index(), None);
+    assert_eq!(bitfields.offset(), None);
+
+    bitfields.set_index(NonZeroU8::new(12).unwrap());
+    bitfields.set_offset(NonZeroI8::new(-3).unwrap());
+
+    assert_eq!(bitfields.index(), NonZeroU8::new(12));
+    assert_eq!(bitfields.offset(), NonZeroI8::new(-3));
+    assert_eq!(bitfields.index_offset, [0xDC]);
+
+    // A non-zero value can still truncate to zero bits
+    bitfields.set_index(NonZeroU8::new(16).unwrap());
+
+    assert_eq!(bitfields.index(), None);
+    assert_eq!(size_of::<Option<NonZeroU8>>(), size_of::<u8>());
+}
+
+#[test]
+fn test_display() {
+    let mut date = CompactDate {
+        d_m: [0; 2],
+        y: 2014,
+    };
+
+    date.set_d(31);
+    date.set_m(12);
+
+    assert_eq!(date.to_string(), "d=31 m=12 y=2014");
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(display_hex)]
+struct StatusReg {
+    #[bitfield(name = "code", ty = "u8", bits = "0..=5")]
+    #[bitfield(name = "ready", ty = "bool", bits = "6..=6")]
+    #[bitfield(name = "level", ty = "i8", enum_ty = "Level", bits = "8..=9")]
+    status: [u8; 2],
+    count: u16,
+}
+
+#[test]
+fn test_display_hex() {
+    let mut reg = StatusReg {
+        status: [0; 2],
+        count: 300,
+    };
+
+    reg.set_code(0x2a);
+    reg.set_ready(true);
+
+    assert_eq!(
+        reg.to_string(),
+        "code=0x2a ready=true level=0x0 count=0x12c"
+    );
+
+    // Two's complement bits that aren't a discriminant of the enum
+    reg.status[1] = 0b10;
+
+    assert_eq!(reg.to_string(), "code=0x2a ready=true level=? count=0x12c");
+}
+
+#[test]
+fn test_try_new() {
+    let date = CompactDate::try_new(31, 12, 2014).unwrap();
+
+    assert_eq!(date.d(), 31);
+    assert_eq!(date.m(), 12);
+    assert_eq!(date.y, 2014);
+
+    let err = CompactDate::try_new(31, 16, 2014).err().unwrap();
+
+    assert_eq!(err, BitfieldError::new("m", 4, 16));
+    assert_eq!(
+        err.to_string(),
+        "value 16 doesn't fit in the 4 bit bitfield `m`"
+    );
+
+    // Signed bitfields are checked against both ends of their range
+    let storage = SynthesizedStorage::try_new(true, 9, -2048).unwrap();
+
+    assert_eq!(storage.offset(), -2048);
+    assert_eq!(
+        SynthesizedStorage::try_new(true, 9, 2048).err(),
+        Some(BitfieldError::new("offset", 12, 2048))
+    );
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(msb0)]
+struct Msb0Reg {
+    #[bitfield(name = "mode", ty = "u8", bits = "0..=2")]
+    #[bitfield(name = "count", ty = "u16", bits = "3..=12")]
+    #[bitfield(name = "enable", ty = "bool", bits = "15..=15")]
+    reg: u16,
+    #[bitfield(name = "high_nibble", ty = "u8", bits = "0..=3")]
+    #[bitfield(name = "low_bit", ty = "bool", bits = "23..=23")]
+    bytes: [u8; 3],
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct Lsb0Reg {
+    #[bitfield(name = "mode", ty = "u8", bits = "13..=15")]
+    #[bitfield(name = "count", ty = "u16", bits = "3..=12")]
+    #[bitfield(name = "enable", ty = "bool", bits = "0..=0")]
+    reg: u16,
+    #[bitfield(name = "high_nibble", ty = "u8", bits = "20..=23")]
+    #[bitfield(name = "low_bit", ty = "bool", bits = "0..=0")]
+    bytes: [u8; 3],
+}
+
+#[test]
+fn test_msb0() {
+    let mut msb0 = Msb0Reg {
+        reg: 0,
+        bytes: [0; 3],
+    };
+    let mut lsb0 = Lsb0Reg {
+        reg: 0,
+        bytes: [0; 3],
+    };
+
+    msb0.set_mode(5);
+    msb0.set_count(700);
+    msb0.set_enable(true);
+    msb0.set_high_nibble(0xA);
+    msb0.set_low_bit(true);
+    lsb0.set_mode(5);
+    lsb0.set_count(700);
+    lsb0.set_enable(true);
+    lsb0.set_high_nibble(0xA);
+    lsb0.set_low_bit(true);
+
+    assert_eq!(msb0.reg, lsb0.reg);
+    assert_eq!(msb0.reg, 0b101_1010111100_00_1);
+    assert_eq!(msb0.bytes, lsb0.bytes);
+    // Array members are treated as little endian, so their msb0 bit 0 is
+    // the most significant bit of the last byte
+    assert_eq!(msb0.bytes, [0x01, 0x00, 0xA0]);
+    assert_eq!(msb0.mode(), 5);
+    assert_eq!(msb0.count(), 700);
+    assert!(msb0.enable());
+    assert_eq!(msb0.high_nibble(), 0xA);
+    assert!(msb0.low_bit());
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct PerFieldMsb0 {
+    #[bitfield(name = "top", ty = "bool", bits = "0..=0", msb0)]
+    #[bitfield(name = "bottom", ty = "bool", bits = "0..=0")]
+    byte: u8,
+}
+
+#[test]
+fn test_per_field_msb0() {
+    let mut bits = PerFieldMsb0 { byte: 0 };
+
+    bits.set_top(true);
+
+    assert_eq!(bits.byte, 0x80);
+    assert!(!bits.bottom());
+
+    bits.set_bottom(true);
+
+    assert_eq!(bits.byte, 0x81);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct Packet {
+    #[bitfield(name = "version", ty = "u8", bits = "0..=3")]
+    #[bitfield(name = "port", bytes = "1..=2")]
+    #[bitfield(name = "checksum", bytes = "3..=3")]
+    header: [u8; 4],
+}
+
+#[test]
+fn test_byte_spans() {
+    let mut packet = Packet {
+        header: [0x04, 0x50, 0x1F, 0xAB],
+    };
+
+    assert_eq!(packet.version(), 4);
+    assert_eq!(packet.port(), &[0x50, 0x1F]);
+    assert_eq!(
+        u16::from_le_bytes([packet.port()[0], packet.port()[1]]),
+        8016
+    );
+    assert_eq!(packet.checksum(), &[0xAB]);
+
+    packet.port_mut().copy_from_slice(&443u16.to_le_bytes());
+
+    assert_eq!(packet.header, [0x04, 0xBB, 0x01, 0xAB]);
+    assert_eq!(packet.version(), 4);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(endian = "big", byte_conversion, validate_layout)]
+struct Ipv4Start {
+    #[bitfield(name = "version", ty = "u8", bits = "4..=7")]
+    #[bitfield(name = "ihl", ty = "u8", bits = "0..=3")]
+    version_ihl: u8,
+    #[bitfield(name = "flags", ty = "u8", bits = "13..=15")]
+    #[bitfield(name = "fragment_offset", ty = "u16", bits = "0..=12")]
+    flags_fragment_offset: u16,
+    #[bitfield(name = "ttl", ty = "u8", bits = "8..=15")]
+    #[bitfield(name = "protocol", ty = "u8", bits = "0..=7")]
+    ttl_protocol: [u8; 2],
+}
+
+#[test]
+fn test_be_bytes() {
+    // The byte after `version_ihl` is padding
+    let bytes = [0x45, 0x00, 0x40, 0x10, 0x40, 0x06];
+    let header = Ipv4Start::from_be_bytes(bytes);
+
+    assert_eq!(header.version(), 4);
+    assert_eq!(header.ihl(), 5);
+    assert_eq!(header.flags(), 2);
+    assert_eq!(header.fragment_offset(), 0x10);
+    assert_eq!(header.ttl(), 64);
+    assert_eq!(header.protocol(), 6);
+    assert_eq!(header.flags_fragment_offset, 0x4010);
+    // `[u8; N]` members keep the bytes as they are on the wire
+    assert_eq!(header.ttl_protocol, [0x40, 0x06]);
+    assert_eq!(header.to_be_bytes(), bytes);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(byte_conversion)]
+struct NativeWord {
+    #[bitfield(name = "low", ty = "u8", bits = "0..=7")]
+    #[bitfield(name = "high", ty = "u8", bits = "8..=15")]
+    word: u16,
+    #[bitfield(name = "nibble", ty = "u8", bits = "4..=7")]
+    extra: [u8; 2],
+}
+
+#[test]
+fn test_ne_bytes() {
+    let mut value = NativeWord::from_ne_bytes([0x34, 0x12, 0xA0, 0x00]);
+
+    // Bitfields only support little endian targets, where native endian
+    // bytes are the in memory bytes
+    assert_eq!(value.low(), 0x34);
+    assert_eq!(value.high(), 0x12);
+    assert_eq!(value.nibble(), 0xA);
+    assert_eq!(value.word, 0x1234);
+
+    value.set_high(0x56);
+
+    assert_eq!(value.to_ne_bytes(), [0x34, 0x56, 0xA0, 0x00]);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(byte_conversion)]
+struct NativeDate {
+    #[bitfield(name = "d", ty = "u8", bits = "0..=4")]
+    #[bitfield(name = "m", ty = "u8", bits = "5..=8")]
+    d_m: [u8; 2],
+    y: u16,
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(endian = "big", byte_conversion)]
+struct TaggedLength {
+    #[bitfield(name = "version", ty = "u8", bits = "4..=7")]
+    #[bitfield(name = "kind", ty = "u8", bits = "0..=3")]
+    version_kind: u8,
+    tag: u8,
+    length: u16,
+}
+
+#[test]
+fn test_bytes_plain_members() {
+    // Members which don't back any bitfield are converted too
+    let date = NativeDate::from_ne_bytes([0x9F, 0x01, 0xE8, 0x07]);
+
+    assert_eq!(date.d(), 31);
+    assert_eq!(date.m(), 12);
+    assert_eq!(date.y, 2024);
+    assert_eq!(date.to_ne_bytes(), [0x9F, 0x01, 0xE8, 0x07]);
+
+    let header = TaggedLength::from_be_bytes([0x45, 0x07, 0x01, 0x02]);
+
+    assert_eq!(header.version(), 4);
+    assert_eq!(header.kind(), 5);
+    assert_eq!(header.tag, 7);
+    assert_eq!(header.length, 0x0102);
+    assert_eq!(header.to_be_bytes(), [0x45, 0x07, 0x01, 0x02]);
+}
+
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+struct PlatformWidths {
+    // `c_ulong` and `c_long` are only 32 bits wide on some targets, so these
+    // are the widest bitfields they can hold everywhere
+    #[bitfield(name = "count", ty = "libc::c_ulong", bits = "0..=31")]
+    #[bitfield(name = "delta", ty = "libc::c_long", bits = "32..=63")]
+    count_delta: [u8; 8],
+}
+
+#[test]
+fn test_platform_dependent_widths() {
+    use c2rust_bitfields::FieldType;
+
+    assert_eq!(
+        <c_ulong as FieldType>::TOTAL_BIT_SIZE,
+        size_of::<c_ulong>() * 8
+    );
+    assert_eq!(
+        <c_long as FieldType>::TOTAL_BIT_SIZE,
+        size_of::<c_long>() * 8
+    );
+
+    let mut value = PlatformWidths {
+        count_delta: [0; 8],
+    };
+
+    value.set_count(c_ulong::MAX & 0xFFFF_FFFF);
+    value.set_delta(-1);
+
+    assert_eq!(value.count(), c_ulong::MAX & 0xFFFF_FFFF);
+    // Sign extended to however wide `c_long` is on this target
+    assert_eq!(value.delta(), -1);
+    assert_eq!(value.count_delta, [0xFF; 8]);
+
+    value.set_delta(c_long::from(i32::MIN));
+
+    assert_eq!(value.delta(), c_long::from(i32::MIN));
+    assert_eq!(value.count(), c_ulong::MAX & 0xFFFF_FFFF);
+    assert_eq!(value.count_delta, [0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0x80]);
+}
+
+#[cfg(feature = "try_from")]
+#[test]
+fn test_try_from_bytes() {
+    use c2rust_bitfields::ByteLengthError;
+    use std::convert::TryFrom;
+
+    let packet: [u8; 7] = [0x45, 0x00, 0x40, 0x10, 0x40, 0x06, 0xAA];
+
+    assert_eq!(
+        Ipv4Start::try_from(&packet[..5]).err(),
+        Some(ByteLengthError::new(6, 5))
+    );
+    assert!(Ipv4Start::try_from(&packet[..]).is_err());
+
+    let header = Ipv4Start::try_from(&packet[..6]).unwrap();
+
+    assert_eq!(header.version(), 4);
+    assert_eq!(header.protocol(), 6);
+
+    // Views only need the slice to hold at least the struct's bytes
+    let mut bytes: [u8; 5] = [0b00011111, 0b00001100, 0b11011110, 0b00000111, 0xff];
+
+    assert_eq!(
+        CompactDateView::try_from(&mut bytes[..3]).err(),
+        Some(ByteLengthError::new(4, 3))
+    );
+
+    let view = CompactDateView::try_from(&mut bytes[..]).unwrap();
+
+    assert_eq!(view.d(), 31);
+}
+
+mod renamed_crates {
+    use c2rust_bitfields::BitfieldStruct;
+
+    // Shadows the `libc` crate, so the `libc` paths of the bitfield types
+    // below only resolve once rewritten to `libc_path`
+    mod libc {}
+
+    pub mod c_types {
+        pub use ::libc::{c_uchar, c_ushort};
+    }
+
+    pub mod my_core {
+        pub use ::core::*;
+    }
+
+    #[repr(C)]
+    #[derive(BitfieldStruct, Copy, Clone)]
+    #[bitfield(
+        core_path = "crate::renamed_crates::my_core",
+        libc_path = "crate::renamed_crates::c_types",
+        view
+    )]
+    pub struct RenamedDate {
+        #[bitfield(name = "day", ty = "libc::c_uchar", bits = "0..=4")]
+        #[bitfield(name = "year", ty = "libc::c_ushort", bits = "5..=15")]
+        pub day_year: [u8; 2],
+    }
+}
+
+#[test]
+fn test_renamed_crate_paths() {
+    use renamed_crates::{RenamedDate, RenamedDateView};
+
+    let mut date = RenamedDate { day_year: [0; 2] };
+
+    date.set_day(17);
+    date.set_year(1999);
+
+    assert_eq!(date.day(), 17);
+    assert_eq!(date.year(), 1999);
+
+    let mut bytes = date.day_year;
+    let view = RenamedDateView::new(&mut bytes).unwrap();
+
+    assert_eq!(view.day(), 17);
+    assert_eq!(view.year(), 1999);
+}
+
+mod accessor_mods {
+    use c2rust_bitfields::BitfieldStruct;
+
+    #[repr(C)]
+    #[derive(BitfieldStruct, Copy, Clone, PartialEq, Eq)]
+    #[bitfield(accessor_mod = "fields", display, ord_by = "level", with_setters)]
+    pub struct Irq {
+        #[bitfield(name = "level", ty = "libc::c_uchar", bits = "0..=2")]
+        #[bitfield(name = "enabled", ty = "bool", bits = "3..=3")]
+        pub flags: u8,
+    }
+
+    impl Irq {
+        // Would clash with the `enabled` getter were it in the inherent impl
+        pub fn enabled(&self) -> &'static str {
+            if fields::IrqFields::enabled(self) {
+                "on"
+            } else {
+                "off"
+            }
+        }
+    }
+}
+
+#[test]
+fn test_accessor_mod() {
+    use accessor_mods::fields::IrqFields;
+    use accessor_mods::Irq;
+
+    let mut irq = Irq { flags: 0 };
+
+    irq.set_level(5);
+    irq.set_enabled(true);
+
+    assert_eq!(irq.level(), 5);
+    assert_eq!(irq.flags, 0b1101);
+
+    // The inherent method takes precedence over the trait's getter
+    assert_eq!(irq.enabled(), "on");
+    assert!(IrqFields::enabled(&irq));
+
+    // Generated impls call the trait's getter rather than the inherent method
+    assert_eq!(irq.to_string(), "level=5 enabled=true");
+
+    let lower = Irq { flags: 0 }.with_level(2).with_enabled(true);
+
+    assert!(lower < irq);
+}
+
+mod accessor_traits {
+    use c2rust_bitfields::BitfieldStruct;
+
+    #[repr(C)]
+    #[derive(BitfieldStruct, Copy, Clone)]
+    #[bitfield(accessor_trait, display, validate_layout)]
+    pub struct Timer {
+        #[bitfield(name = "period", ty = "libc::c_ushort", bits = "0..=11")]
+        #[bitfield(name = "running", ty = "bool", bits = "12..=12")]
+        pub ctrl: u16,
+    }
+
+    impl Timer {
+        // Would clash with the `period` getter were it in the inherent impl
+        pub fn period(&self) -> u32 {
+            u32::from(TimerFields::period(self)) * 1000
+        }
+    }
+}
+
+#[test]
+fn test_accessor_trait() {
+    use accessor_traits::{Timer, TimerFields};
+
+    let mut timer = Timer { ctrl: 0 };
+
+    timer.set_period(250);
+    timer.set_running(true);
+
+    assert!(timer.running());
+    assert_eq!(timer.ctrl, 0x10fa);
+
+    // The inherent method takes precedence over the trait's getter
+    assert_eq!(timer.period(), 250_000);
+    assert_eq!(TimerFields::period(&timer), 250);
+
+    // Generated impls call the trait's getter rather than the inherent method
+    assert_eq!(timer.to_string(), "period=250 running=true");
+}
+
+#[cfg(feature = "trace")]
+mod traced {
+    use c2rust_bitfields::{BitfieldStruct, BitfieldTrace};
+    use std::cell::RefCell;
+
+    thread_local! {
+        pub static WRITES: RefCell<Vec<(&'static str, u64, u64)>> = RefCell::new(Vec::new());
+    }
+
+    pub struct WriteLog;
+
+    impl BitfieldTrace for WriteLog {
+        fn trace(name: &'static str, old: u64, new: u64) {
+            WRITES.with(|writes| writes.borrow_mut().push((name, old, new)));
+        }
+    }
+
+    #[repr(C)]
+    #[derive(BitfieldStruct, Copy, Clone)]
+    #[bitfield(trace = "WriteLog", view, explicit_setters)]
+    pub struct Control {
+        #[bitfield(name = "mode", ty = "libc::c_uchar", bits = "0..=2")]
+        #[bitfield(name = "enable", ty = "bool", bits = "3..=3")]
+        #[bitfield(name = "offset", ty = "libc::c_schar", bits = "4..=7")]
+        pub bits: u8,
+    }
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn test_trace() {
+    use traced::{Control, ControlView, WRITES};
+
+    let mut control = Control { bits: 0 };
+
+    control.set_mode(5);
+    control.set_enable(true);
+    // Truncated to the bitfield's width, and reported as its raw bits
+    control.set_mode(9);
+    control.set_offset(-2);
+    control.wrapping_set_enable(false);
+
+    let mut bytes = [control.bits];
+    let mut view = ControlView::new(&mut bytes).unwrap();
+
+    view.set_mode(3);
+
+    let writes = WRITES.with(|writes| writes.borrow_mut().split_off(0));
+
+    assert_eq!(
+        writes,
+        [
+            ("mode", 0, 5),
+            ("enable", 0, 1),
+            ("mode", 5, 1),
+            ("offset", 0, 0b1110),
+            ("enable", 1, 0),
+            ("mode", 1, 3),
+        ]
+    );
+}
+
+// The offsets and sizes below are those clang reports for the equivalent C
+// structs, so a mismatch fails to compile
+#[repr(C)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(offsets = "kind = 0, len = 4, bits = 8", size = "12")]
+struct LaidOut {
+    kind: u8,
+    len: u32,
+    #[bitfield(name = "a", ty = "libc::c_uchar", bits = "0..=3")]
+    #[bitfield(name = "b", ty = "libc::c_uchar", bits = "4..=11")]
+    bits: u16,
+}
+
+#[repr(C, packed)]
+#[derive(BitfieldStruct, Copy, Clone)]
+#[bitfield(offsets = "len = 1, bits = 5", size = "7")]
+struct PackedLaidOut {
+    kind: u8,
+    len: u32,
+    #[bitfield(name = "a", ty = "libc::c_uchar", bits = "0..=3")]
+    bits: u16,
+}
+
+#[test]
+fn test_layout_assertions() {
+    let mut laid_out = LaidOut {
+        kind: 1,
+        len: 2,
+        bits: 0,
+    };
+
+    laid_out.set_a(9);
+    laid_out.set_b(200);
+
+    assert_eq!((laid_out.a(), laid_out.b()), (9, 200));
+    assert_eq!(size_of::<LaidOut>(), 12);
+
+    let mut packed = PackedLaidOut {
+        kind: 1,
+        len: 2,
+        bits: 0,
+    };
+
+    packed.set_a(5);
+
+    assert_eq!(packed.a(), 5);
+    assert_eq!(size_of::<PackedLaidOut>(), 7);
+}
@@ -0,0 +1,59 @@
+#![no_main]
+
+use c2rust_bitfields::{extract_bits, insert_bits};
+use libfuzzer_sys::fuzz_target;
+
+/// Reads bit `n` of `bytes`, numbered as in `extract_bits`
+fn bit(bytes: &[u8], n: usize) -> bool {
+    bytes[n / 8] & (1 << (n % 8)) != 0
+}
+
+// The input is a little endian `u64` value, then bytes choosing the start
+// and width of the bits, followed by the buffer the bits are written to
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 11 {
+        return;
+    }
+
+    let (value, data) = data.split_at(8);
+    let value = u64::from_le_bytes(value.try_into().unwrap());
+    let bytes = &data[2..];
+    let bit_len = bytes.len() * 8;
+
+    if bit_len == 0 {
+        return;
+    }
+
+    let start = usize::from(data[0]) % bit_len;
+    let width = (usize::from(data[1]) % 65).min(bit_len - start);
+    let mask = match width {
+        64 => u64::MAX,
+        _ => (1 << width) - 1,
+    };
+
+    let original = bytes.to_vec();
+    let mut bytes = bytes.to_vec();
+
+    insert_bits(&mut bytes, start as u32, width as u32, value);
+
+    // Only the low `width` bits of the value are kept
+    assert_eq!(
+        extract_bits(&bytes, start as u32, width as u32),
+        value & mask
+    );
+
+    for n in 0..bit_len {
+        if (start..start + width).contains(&n) {
+            assert_eq!(bit(&bytes, n), value >> (n - start) & 1 != 0);
+        } else {
+            assert_eq!(bit(&bytes, n), bit(&original, n), "bit {} changed", n);
+        }
+    }
+
+    // Writing back the bits that were there restores the original bytes
+    let old_value = extract_bits(&original, start as u32, width as u32);
+
+    insert_bits(&mut bytes, start as u32, width as u32, old_value);
+
+    assert_eq!(bytes, original);
+});
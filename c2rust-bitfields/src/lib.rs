@@ -1,6 +1,6 @@
 #![cfg_attr(feature = "no_std", no_std)]
 
-pub use c2rust_bitfields_derive::BitfieldStruct;
+pub use c2rust_bitfields_derive::{BitfieldEnum, BitfieldStruct};
 
 pub trait FieldType: Sized {
     const IS_SIGNED: bool;
@@ -40,9 +40,192 @@ pub trait FieldType: Sized {
     }
 
     fn get_field(field: &[u8], bit_range: (usize, usize)) -> Self;
+
+    /// Whether the value can be written to a bitfield `bit_width` bits wide
+    /// and read back unchanged
+    fn fits_in_bits(&self, bit_width: usize) -> bool;
+
+    /// The value clamped to the range a bitfield `bit_width` bits wide can
+    /// hold, so that writing it to the bitfield doesn't truncate it
+    fn saturate_to_bits(&self, bit_width: usize) -> Self;
+
+    /// Like `set_field`, but without bounds checking the bytes of `field`
+    /// it writes. Types of this crate write through `get_unchecked_mut`;
+    /// others fall back to `set_field`.
+    ///
+    /// # Safety
+    ///
+    /// Every bit in `bit_range` must lie within `field`
+    #[cfg(feature = "unchecked")]
+    unsafe fn set_field_unchecked(&self, field: &mut [u8], bit_range: (usize, usize)) {
+        self.set_field(field, bit_range)
+    }
+
+    /// Like `get_field`, but without bounds checking the bytes of `field`
+    /// it reads. Types of this crate read through `get_unchecked`; others
+    /// fall back to `get_field`.
+    ///
+    /// # Safety
+    ///
+    /// Every bit in `bit_range` must lie within `field`
+    #[cfg(feature = "unchecked")]
+    unsafe fn get_field_unchecked(field: &[u8], bit_range: (usize, usize)) -> Self {
+        Self::get_field(field, bit_range)
+    }
+}
+
+/// Reads the `width` bits of `bytes` starting at bit `start` as an unsigned
+/// integer. Bit `n` is bit `n % 8` of byte `n / 8`, counting from the least
+/// significant bit, as in a bitfield's backing member. Being a `const fn`,
+/// it also backs the `const` getters generated for integer and `bool`
+/// bitfields.
+///
+/// # Panics
+///
+/// Panics if `width` is more than 64 or the bits don't lie within `bytes`
+pub const fn extract_bits(bytes: &[u8], start: u32, width: u32) -> u64 {
+    assert!(width <= 64, "can't extract more than 64 bits at once");
+
+    let mut value = 0;
+    let mut done = 0;
+
+    // Whole runs of bits are taken from each byte rather than bit by bit.
+    // `Ord::min` and `u64::from` aren't const, hence the spelled out versions.
+    while done < width {
+        let bit = start + done;
+        let offset = bit % 8;
+        let take = if 8 - offset < width - done {
+            8 - offset
+        } else {
+            width - done
+        };
+        let byte = bytes[(bit / 8) as usize] >> offset;
+        let mask = (1u64 << take) - 1;
+
+        value |= (byte as u64 & mask) << done;
+        done += take;
+    }
+
+    value
+}
+
+/// Writes the low `width` bits of `value` to the `width` bits of `bytes`
+/// starting at bit `start`, leaving every other bit of `bytes` unchanged.
+/// Bits are numbered as in [`extract_bits`].
+///
+/// # Panics
+///
+/// Panics if `width` is more than 64 or the bits don't lie within `bytes`
+pub fn insert_bits(bytes: &mut [u8], start: u32, width: u32, value: u64) {
+    assert!(width <= 64, "can't insert more than 64 bits at once");
+
+    let mut done = 0;
+
+    while done < width {
+        let bit = start + done;
+        let offset = bit % 8;
+        let take = (8 - offset).min(width - done);
+        let byte = &mut bytes[(bit / 8) as usize];
+        let mask = (((1u16 << take) - 1) << offset) as u8;
+        let chunk = ((value >> done) as u8) << offset;
+
+        *byte = (*byte & !mask) | (chunk & mask);
+        done += take;
+    }
+}
+
+/// Like [`extract_bits`], but reads `bytes` with `get_unchecked`
+///
+/// # Safety
+///
+/// `width` must be at most 64 and the bits must lie within `bytes`
+#[cfg(feature = "unchecked")]
+unsafe fn extract_bits_unchecked(bytes: &[u8], start: u32, width: u32) -> u64 {
+    let mut value = 0;
+    let mut done = 0;
+
+    while done < width {
+        let bit = start + done;
+        let offset = bit % 8;
+        let take = (8 - offset).min(width - done);
+        let byte = *bytes.get_unchecked((bit / 8) as usize) >> offset;
+        let mask = (1u64 << take) - 1;
+
+        value |= (u64::from(byte) & mask) << done;
+        done += take;
+    }
+
+    value
+}
+
+/// Like [`insert_bits`], but writes `bytes` with `get_unchecked_mut`
+///
+/// # Safety
+///
+/// `width` must be at most 64 and the bits must lie within `bytes`
+#[cfg(feature = "unchecked")]
+unsafe fn insert_bits_unchecked(bytes: &mut [u8], start: u32, width: u32, value: u64) {
+    let mut done = 0;
+
+    while done < width {
+        let bit = start + done;
+        let offset = bit % 8;
+        let take = (8 - offset).min(width - done);
+        let byte = bytes.get_unchecked_mut((bit / 8) as usize);
+        let mask = (((1u16 << take) - 1) << offset) as u8;
+        let chunk = ((value >> done) as u8) << offset;
+
+        *byte = (*byte & !mask) | (chunk & mask);
+        done += take;
+    }
 }
 
 macro_rules! impl_int {
+    // `insert` is `insert_bits` or `insert_bits_unchecked`
+    (@set_field $self: ident, $field: ident, $bit_range: ident, $insert: ident) => {{
+        let (lhs_bit, rhs_bit) = $bit_range;
+        let width = (rhs_bit - lhs_bit + 1) as u32;
+        // Signed ints are sign extended, but only their low bits are written
+        let int = *$self as u128;
+        let mut done = 0;
+
+        while done < width {
+            let chunk = (width - done).min(64);
+
+            $insert($field, lhs_bit as u32 + done, chunk, (int >> done) as u64);
+            done += chunk;
+        }
+    }};
+    // `extract` is `extract_bits` or `extract_bits_unchecked`
+    (@get_field $field: ident, $bit_range: ident, $extract: ident) => {{
+        let (lhs_bit, rhs_bit) = $bit_range;
+        let width = (rhs_bit - lhs_bit + 1) as u32;
+        let mut int = 0u128;
+        let mut done = 0;
+
+        // Only 128 bit ints have fields too wide to extract at once
+        while done < width {
+            let chunk = (width - done).min(64);
+
+            int |= u128::from($extract($field, lhs_bit as u32 + done, chunk)) << done;
+            done += chunk;
+        }
+
+        let mut val = int as Self;
+
+        // If the int type is signed, sign extend unconditionally. Fields
+        // as wide as the int type already hold their sign bit.
+        let bit_width = rhs_bit - lhs_bit + 1;
+
+        if Self::IS_SIGNED && bit_width < Self::TOTAL_BIT_SIZE {
+            let unused_bits = Self::TOTAL_BIT_SIZE - bit_width;
+
+            val <<= unused_bits;
+            val >>= unused_bits;
+        }
+
+        val
+    }};
     ($($typ: ident),+) => {
         $(
             impl FieldType for $typ {
@@ -52,33 +235,48 @@ macro_rules! impl_int {
                     ((*self >> bit) & 1) == 1
                 }
 
+                fn set_field(&self, field: &mut [u8], bit_range: (usize, usize)) {
+                    impl_int!(@set_field self, field, bit_range, insert_bits)
+                }
+
                 fn get_field(field: &[u8], bit_range: (usize, usize)) -> Self {
-                    let (lhs_bit, rhs_bit) = bit_range;
-                    let mut val = 0;
+                    impl_int!(@get_field field, bit_range, extract_bits)
+                }
 
-                    for (i, bit_index) in (lhs_bit..=rhs_bit).enumerate() {
-                        let byte_index = bit_index / 8;
-                        let byte = field[byte_index];
-                        let bit = 1 << (bit_index % 8);
-                        let read_bit = byte & bit;
+                #[cfg(feature = "unchecked")]
+                unsafe fn set_field_unchecked(&self, field: &mut [u8], bit_range: (usize, usize)) {
+                    impl_int!(@set_field self, field, bit_range, insert_bits_unchecked)
+                }
 
-                        if read_bit != 0 {
-                            let write_bit = 1 << i;
+                #[cfg(feature = "unchecked")]
+                unsafe fn get_field_unchecked(field: &[u8], bit_range: (usize, usize)) -> Self {
+                    impl_int!(@get_field field, bit_range, extract_bits_unchecked)
+                }
 
-                            val |= write_bit;
-                        }
+                fn fits_in_bits(&self, bit_width: usize) -> bool {
+                    if bit_width >= Self::TOTAL_BIT_SIZE {
+                        return true;
                     }
 
-                    // If the int type is signed, sign extend unconditionally
-                    if Self::IS_SIGNED {
-                        let bit_width = rhs_bit - lhs_bit + 1;
-                        let unused_bits = Self::TOTAL_BIT_SIZE - bit_width;
+                    // Shifting back down sign extends signed ints, so this
+                    // also checks the sign bit of the narrower field
+                    let unused_bits = Self::TOTAL_BIT_SIZE - bit_width;
+
+                    (*self << unused_bits) >> unused_bits == *self
+                }
 
-                        val <<= unused_bits;
-                        val >>= unused_bits;
+                fn saturate_to_bits(&self, bit_width: usize) -> Self {
+                    if bit_width >= Self::TOTAL_BIT_SIZE {
+                        return *self;
                     }
 
-                    val
+                    // Shifting the extremes down sign extends signed ints,
+                    // leaving the field's own extremes
+                    let unused_bits = Self::TOTAL_BIT_SIZE - bit_width;
+                    let min = $typ::min_value() >> unused_bits;
+                    let max = $typ::max_value() >> unused_bits;
+
+                    (*self).clamp(min, max)
                 }
             }
         )+
@@ -111,4 +309,448 @@ impl FieldType for bool {
 
         val
     }
+
+    // A `bool` writes every bit of its field, and reads as `true` if any
+    // bit is set
+    #[cfg(feature = "unchecked")]
+    unsafe fn set_field_unchecked(&self, field: &mut [u8], bit_range: (usize, usize)) {
+        let (lhs_bit, rhs_bit) = bit_range;
+        let width = (rhs_bit - lhs_bit + 1) as u32;
+        let bits = if *self { u64::MAX } else { 0 };
+        let mut done = 0;
+
+        while done < width {
+            let chunk = (width - done).min(64);
+
+            insert_bits_unchecked(field, lhs_bit as u32 + done, chunk, bits);
+            done += chunk;
+        }
+    }
+
+    #[cfg(feature = "unchecked")]
+    unsafe fn get_field_unchecked(field: &[u8], bit_range: (usize, usize)) -> Self {
+        let (lhs_bit, rhs_bit) = bit_range;
+        let width = (rhs_bit - lhs_bit + 1) as u32;
+        let mut done = 0;
+
+        while done < width {
+            let chunk = (width - done).min(64);
+
+            if extract_bits_unchecked(field, lhs_bit as u32 + done, chunk) != 0 {
+                return true;
+            }
+            done += chunk;
+        }
+
+        false
+    }
+
+    fn fits_in_bits(&self, bit_width: usize) -> bool {
+        bit_width != 0
+    }
+
+    fn saturate_to_bits(&self, _bit_width: usize) -> Self {
+        *self
+    }
+}
+
+/// Fieldless enums which can be stored in a bitfield via its `enum_ty` param.
+/// This is normally implemented with `#[derive(BitfieldEnum)]`.
+pub trait BitfieldEnum: Copy {
+    /// Whether any of the enum's discriminants is negative, in which case it
+    /// can only be stored in a bitfield with a signed `ty`
+    const HAS_NEGATIVE_DISCRIMINANT: bool;
+
+    fn to_discriminant(self) -> i128;
+
+    fn from_discriminant(discriminant: i128) -> Option<Self>;
+}
+
+/// Bitfield structs which can report which of their bitfields changed
+/// between two values. This is implemented by `#[derive(BitfieldStruct)]`
+/// when the struct has the `diff` param.
+pub trait BitfieldDiff {
+    /// Calls `f` with the name of each bitfield whose value differs between
+    /// `self` and `other`, in declaration order
+    fn for_each_diff<F: FnMut(&'static str)>(&self, other: &Self, f: F);
+
+    /// The names of the bitfields whose values differ between `self` and
+    /// `other`, in declaration order
+    #[cfg(not(feature = "no_std"))]
+    fn diff(&self, other: &Self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+
+        self.for_each_diff(other, |name| names.push(name));
+
+        names
+    }
+}
+
+/// A hook told about every write to the bitfields of a struct which names it
+/// with the `trace` param, such as `#[bitfield(trace = "RegisterLog")]`.
+/// Writes are only reported when the `trace` feature is enabled; otherwise
+/// the setters don't call the hook at all.
+pub trait BitfieldTrace {
+    /// Called after `name` is written, with the bitfield's raw bits before
+    /// and after the write. Only the low 64 bits of wider bitfields are
+    /// reported.
+    fn trace(name: &'static str, old: u64, new: u64);
+}
+
+/// The error returned when a value doesn't fit in the bitfield it's meant
+/// to be written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitfieldError {
+    name: &'static str,
+    bit_width: usize,
+    value: i128,
+}
+
+impl BitfieldError {
+    pub fn new(name: &'static str, bit_width: usize, value: i128) -> Self {
+        BitfieldError {
+            name,
+            bit_width,
+            value,
+        }
+    }
+
+    /// The name of the offending bitfield
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The width of the offending bitfield
+    pub fn bit_width(&self) -> usize {
+        self.bit_width
+    }
+
+    /// The value which didn't fit
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+}
+
+impl ::core::fmt::Display for BitfieldError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        write!(
+            f,
+            "value {} doesn't fit in the {} bit bitfield `{}`",
+            self.value, self.bit_width, self.name
+        )
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl ::std::error::Error for BitfieldError {}
+
+/// The error returned when converting a byte slice with the wrong length
+/// into a bitfield struct or view, by the `TryFrom` impls generated with the
+/// `try_from` feature, or when a slice is too short for the bitfield accessed
+/// by an `at_offset` accessor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteLengthError {
+    expected: usize,
+    actual: usize,
+}
+
+impl ByteLengthError {
+    pub fn new(expected: usize, actual: usize) -> Self {
+        ByteLengthError { expected, actual }
+    }
+
+    /// The number of bytes the slice needed to hold
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+
+    /// The length of the offending slice
+    pub fn actual(&self) -> usize {
+        self.actual
+    }
+}
+
+impl ::core::fmt::Display for ByteLengthError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        write!(
+            f,
+            "expected {} bytes but got a slice of {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl ::std::error::Error for ByteLengthError {}
+
+/// The error returned by the `validate_layout` fns generated by
+/// `#[derive(BitfieldStruct)]` when a bitfield's setter and getter disagree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutError {
+    name: &'static str,
+    clobbered: Option<(&'static str, usize)>,
+}
+
+impl LayoutError {
+    pub fn new(name: &'static str, clobbered: Option<(&'static str, usize)>) -> Self {
+        LayoutError { name, clobbered }
+    }
+
+    /// The name of the offending bitfield
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The backing member holding the bit outside the bitfield which writing
+    /// to it changed, if any
+    pub fn clobbered_member(&self) -> Option<&'static str> {
+        self.clobbered.map(|(member, _)| member)
+    }
+
+    /// The bit, counted within `clobbered_member`, outside the bitfield which
+    /// writing to it changed, if any. Otherwise the value read back differed
+    /// from the one written.
+    pub fn clobbered_bit(&self) -> Option<usize> {
+        self.clobbered.map(|(_, bit)| bit)
+    }
+}
+
+impl ::core::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self.clobbered {
+            Some((member, bit)) => write!(
+                f,
+                "writing bitfield `{}` changed bit {} of member `{}`, which lies outside it",
+                self.name, bit, member
+            ),
+            None => write!(
+                f,
+                "bitfield `{}` didn't read back the value written to it",
+                self.name
+            ),
+        }
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl ::std::error::Error for LayoutError {}
+
+/// Checks that writing to bitfield `name` left every bit of backing member
+/// `member` outside `bit_range` alone, given the member's bytes from `before`
+/// and `after` the write. Members other than the bitfield's own have no
+/// `bit_range`, so none of their bits may change. The `validate_layout` fns
+/// generated by `#[derive(BitfieldStruct)]` call this for each backing member.
+pub fn check_untouched_bits(
+    name: &'static str,
+    member: &'static str,
+    before: &[u8],
+    after: &[u8],
+    bit_range: Option<(usize, usize)>,
+) -> Result<(), LayoutError> {
+    let in_range = |bit: usize| match bit_range {
+        Some((lhs_bit, rhs_bit)) => bit >= lhs_bit && bit <= rhs_bit,
+        None => false,
+    };
+
+    for bit in (0..before.len() * 8).filter(|bit| !in_range(*bit)) {
+        if ((before[bit / 8] ^ after[bit / 8]) >> (bit % 8)) & 1 != 0 {
+            return Err(LayoutError::new(name, Some((member, bit))));
+        }
+    }
+
+    Ok(())
+}
+
+/// Asserts that writing each of the given values to a bitfield with its
+/// setter and reading it back with its getter returns the same value. Being
+/// a macro, it costs nothing in builds which don't invoke it.
+///
+/// ```
+/// use c2rust_bitfields::{assert_round_trip, BitfieldStruct};
+///
+/// #[repr(C, align(1))]
+/// #[derive(BitfieldStruct)]
+/// struct Date {
+///     #[bitfield(name = "day", ty = "u8", bits = "0..=4")]
+///     #[bitfield(name = "month", ty = "u8", bits = "5..=8")]
+///     #[bitfield(name = "year", ty = "u16", bits = "9..=23")]
+///     day_month_year: [u8; 3],
+/// }
+///
+/// let mut date = Date {
+///     day_month_year: [0; 3],
+/// };
+///
+/// assert_round_trip!(date, day, set_day, [0, 18, 31]);
+/// assert_round_trip!(date, month, set_month, [1, 7, 12]);
+/// assert_round_trip!(date, year, set_year, [0, 2000, 0x7FFF]);
+/// ```
+#[macro_export]
+macro_rules! assert_round_trip {
+    ($value:expr, $getter:ident, $setter:ident, [$($field_value:expr),+ $(,)?]) => {
+        $(
+            $value.$setter($field_value);
+            assert_eq!(
+                $value.$getter(),
+                $field_value,
+                concat!("`", stringify!($getter), "` didn't round trip `", stringify!($field_value), "`"),
+            );
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_untouched_bits, extract_bits, insert_bits, FieldType, LayoutError};
+
+    /// Reads bits one at a time, as a reference for `extract_bits`
+    fn naive_extract(bytes: &[u8], start: u32, width: u32) -> u64 {
+        (0..width).fold(0, |value, i| {
+            let bit = start + i;
+            let set = (bytes[(bit / 8) as usize] >> (bit % 8)) & 1;
+
+            value | (u64::from(set) << i)
+        })
+    }
+
+    fn pattern() -> [u8; 12] {
+        [
+            0xA5, 0x3C, 0xFF, 0x00, 0x81, 0x7E, 0x12, 0xED, 0x55, 0xAA, 0x0F, 0xF0,
+        ]
+    }
+
+    #[test]
+    fn extract_matches_bit_by_bit() {
+        let bytes = pattern();
+
+        for start in 0..32 {
+            for width in 0..=64 {
+                assert_eq!(
+                    extract_bits(&bytes, start, width),
+                    naive_extract(&bytes, start, width),
+                    "start {} width {}",
+                    start,
+                    width
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn insert_round_trips_and_preserves_neighbours() {
+        for start in 0..32 {
+            for width in 0..=64 {
+                for &value in &[0, u64::MAX, 0x0123_4567_89AB_CDEF, 1 << 63, 0x5555] {
+                    let original = pattern();
+                    let mut bytes = original;
+                    let mask = if width == 64 {
+                        u64::MAX
+                    } else {
+                        (1 << width) - 1
+                    };
+
+                    insert_bits(&mut bytes, start, width, value);
+
+                    assert_eq!(extract_bits(&bytes, start, width), value & mask);
+
+                    // Every bit outside the field is left as it was
+                    for bit in (0..start).chain(start + width..96) {
+                        assert_eq!(
+                            naive_extract(&bytes, bit, 1),
+                            naive_extract(&original, bit, 1),
+                            "start {} width {} bit {}",
+                            start,
+                            width,
+                            bit
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn single_bytes() {
+        let mut bytes = [0u8; 2];
+
+        insert_bits(&mut bytes, 4, 8, 0xAB);
+
+        assert_eq!(bytes, [0xB0, 0x0A]);
+        assert_eq!(extract_bits(&bytes, 4, 8), 0xAB);
+        assert_eq!(extract_bits(&bytes, 0, 4), 0);
+        assert_eq!(extract_bits(&bytes, 12, 4), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        extract_bits(&[0; 2], 12, 8);
+    }
+
+    #[test]
+    fn signed_bounds() {
+        // A 4 bit signed bitfield holds -8..=7
+        for value in -8i8..=7 {
+            assert!(value.fits_in_bits(4), "{}", value);
+            assert_eq!(value.saturate_to_bits(4), value);
+        }
+
+        assert!(!(-9i8).fits_in_bits(4));
+        assert!(!8i8.fits_in_bits(4));
+        assert_eq!((-9i8).saturate_to_bits(4), -8);
+        assert_eq!(8i8.saturate_to_bits(4), 7);
+        assert_eq!(i8::MIN.saturate_to_bits(4), -8);
+        assert_eq!(i8::MAX.saturate_to_bits(4), 7);
+
+        // Whereas a 4 bit unsigned bitfield holds 0..=15
+        assert!(15u8.fits_in_bits(4));
+        assert!(!16u8.fits_in_bits(4));
+        assert_eq!(16u8.saturate_to_bits(4), 15);
+
+        // Fields as wide as their type hold every value
+        assert!(i128::MIN.fits_in_bits(128));
+        assert_eq!(i128::MIN.saturate_to_bits(128), i128::MIN);
+        assert_eq!(i128::MIN.saturate_to_bits(127), i128::MIN >> 1);
+    }
+
+    #[test]
+    fn signed_get_field_sign_extends() {
+        let mut bytes = [0u8; 1];
+
+        for value in -8i8..=7 {
+            value.set_field(&mut bytes, (2, 5));
+
+            assert_eq!(i8::get_field(&bytes, (2, 5)), value);
+            // Only the field's bits are written
+            assert_eq!(bytes[0] & 0b1100_0011, 0);
+        }
+
+        // Out of range values are truncated to their low bits
+        8i8.set_field(&mut bytes, (2, 5));
+
+        assert_eq!(i8::get_field(&bytes, (2, 5)), -8);
+
+        (-9i8).set_field(&mut bytes, (2, 5));
+
+        assert_eq!(i8::get_field(&bytes, (2, 5)), 7);
+    }
+
+    #[test]
+    fn untouched_bits() {
+        let before = [0b1010_0000, 0xFF];
+
+        // Bits within the written bitfield may change
+        assert_eq!(
+            check_untouched_bits("x", "x_y", &before, &[0b1010_1111, 0xFF], Some((0, 3))),
+            Ok(())
+        );
+        assert_eq!(
+            check_untouched_bits("x", "x_y", &before, &[0b1011_0000, 0xFF], Some((0, 3))),
+            Err(LayoutError::new("x", Some(("x_y", 4))))
+        );
+        assert_eq!(
+            check_untouched_bits("x", "z", &before, &[0b1010_0000, 0x7F], None),
+            Err(LayoutError::new("x", Some(("z", 15))))
+        );
+    }
 }
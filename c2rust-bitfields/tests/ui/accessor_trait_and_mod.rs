@@ -0,0 +1,10 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+#[bitfield(accessor_mod = "fields", accessor_trait)]
+struct Foo {
+    #[bitfield(name = "x", ty = "u8", bits = "0..=3")]
+    x: [u8; 1],
+}
+
+fn main() {}
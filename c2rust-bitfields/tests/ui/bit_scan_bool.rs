@@ -0,0 +1,9 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+struct Foo {
+    #[bitfield(name = "flag", ty = "bool", bits = "0..=0", bit_scan)]
+    flag: [u8; 1],
+}
+
+fn main() {}
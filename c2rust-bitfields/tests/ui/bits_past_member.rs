@@ -0,0 +1,10 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+struct CompactDate {
+    #[bitfield(name = "x", ty = "u8", bits = "0..=20")]
+    d_m: [u8; 2],
+    y: u16,
+}
+
+fn main() {}
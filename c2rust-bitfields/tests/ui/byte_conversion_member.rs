@@ -0,0 +1,11 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+#[bitfield(byte_conversion)]
+struct Foo {
+    #[bitfield(name = "x", ty = "u8", bits = "0..=3")]
+    x: [u8; 1],
+    scale: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+struct Foo {
+    #[bitfield(name = "tail", bytes = "2..=4")]
+    x: [u8; 4],
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+#[bitfield(endian = "big")]
+struct Foo {
+    #[bitfield(name = "x", ty = "u16", bits = "0..=11", endian = "little")]
+    x: [u8; 2],
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+struct Foo {
+    #[bitfield(name = "x", ty = "u16", bits = "0..=11", endian = "big")]
+    x: u16,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+#[bitfield(exhaustive)]
+struct Foo {
+    #[bitfield(name = "enable", ty = "bool", bits = "0..=0")]
+    #[bitfield(name = "mode", ty = "u8", bits = "4..=6")]
+    #[bitfield(reserved = "8..=11")]
+    #[bitfield(name = "count", ty = "u8", bits = "12..=14")]
+    ctrl: [u8; 2],
+}
+
+fn main() {}
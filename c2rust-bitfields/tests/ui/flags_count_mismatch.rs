@@ -0,0 +1,9 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+struct Foo {
+    #[bitfield(flags = "a, b, c", ty = "bool")]
+    x: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+struct Foo {
+    #[bitfield(name = "x", ty = "u8 + u16", bits = "0..=3")]
+    x: [u8; 1],
+}
+
+fn main() {}
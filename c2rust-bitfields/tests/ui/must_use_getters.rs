@@ -0,0 +1,26 @@
+#![deny(unused_must_use)]
+
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+struct Foo {
+    #[bitfield(name = "a", ty = "u8", bits = "0..=3", must_use)]
+    #[bitfield(name = "b", ty = "u8", bits = "4..=7")]
+    x: [u8; 1],
+}
+
+#[derive(BitfieldStruct)]
+#[bitfield(must_use)]
+struct Bar {
+    #[bitfield(name = "c", ty = "u8", bits = "0..=7")]
+    y: [u8; 1],
+}
+
+fn main() {
+    let foo = Foo { x: [0] };
+    let bar = Bar { y: [0] };
+
+    foo.a();
+    foo.b();
+    bar.c();
+}
@@ -0,0 +1,11 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+struct CompactDate {
+    #[bitfield(name = "d", ty = "u8", bits = "0..=4")]
+    #[bitfield(name = "m", ty = "u8", bits = "4..=7")]
+    d_m: [u8; 2],
+    y: u16,
+}
+
+fn main() {}
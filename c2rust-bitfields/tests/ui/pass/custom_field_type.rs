@@ -0,0 +1,45 @@
+use c2rust_bitfields::{BitfieldStruct, FieldType};
+
+// A ty which can't be cast from the extracted bits, so its getter goes
+// through `FieldType` rather than being a `const fn`
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Nibble(u8);
+
+impl FieldType for Nibble {
+    const IS_SIGNED: bool = false;
+
+    fn get_bit(&self, bit: usize) -> bool {
+        self.0.get_bit(bit)
+    }
+
+    fn get_field(field: &[u8], bit_range: (usize, usize)) -> Self {
+        Nibble(u8::get_field(field, bit_range))
+    }
+
+    fn fits_in_bits(&self, bit_width: usize) -> bool {
+        self.0.fits_in_bits(bit_width)
+    }
+
+    fn saturate_to_bits(&self, bit_width: usize) -> Self {
+        Nibble(self.0.saturate_to_bits(bit_width))
+    }
+}
+
+#[derive(BitfieldStruct)]
+struct Reg {
+    #[bitfield(name = "high", ty = "Nibble", bits = "4..=7")]
+    #[bitfield(name = "low", ty = "libc::c_uchar", bits = "0..=3")]
+    value: [u8; 1],
+}
+
+// Primitive tys, including C type aliases, still get const getters
+const LOW: libc::c_uchar = Reg { value: [0xA5] }.low();
+
+fn main() {
+    let mut reg = Reg { value: [0] };
+
+    reg.set_high(Nibble(0xA));
+
+    assert_eq!(reg.high(), Nibble(0xA));
+    assert_eq!(LOW, 0x5);
+}
@@ -0,0 +1,35 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+#[bitfield(exhaustive)]
+struct Foo {
+    #[bitfield(name = "enable", ty = "bool", bits = "0..=0")]
+    #[bitfield(reserved = "1..=3")]
+    #[bitfield(name = "mode", ty = "u8", bits = "4..=7")]
+    ctrl: [u8; 1],
+    #[bitfield(padding)]
+    _pad: [u8; 1],
+    status: u16,
+}
+
+#[derive(BitfieldStruct)]
+#[bitfield(storage = "u16", exhaustive, msb0)]
+#[bitfield(name = "version", ty = "u8", bits = "0..=3")]
+#[bitfield(reserved = "4..=7")]
+#[bitfield(name = "length", ty = "u8", bits = "8..=15")]
+struct Header(u16);
+
+fn main() {
+    let mut foo = Foo {
+        ctrl: [0],
+        _pad: [0],
+        status: 0,
+    };
+    let mut header = Header(0);
+
+    foo.set_mode(3);
+    header.set_length(20);
+
+    assert_eq!(foo.mode(), 3);
+    assert_eq!(header.length(), 20);
+}
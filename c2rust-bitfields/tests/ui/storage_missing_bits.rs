@@ -0,0 +1,8 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+#[bitfield(storage = "u8")]
+#[bitfield(name = "x", ty = "u8")]
+struct Foo(u8);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+#[bitfield(storage = "u32")]
+#[bitfield(name = "a", ty = "u8", bits = "0..=3")]
+struct Foo {
+    x: u32,
+}
+
+fn main() {}
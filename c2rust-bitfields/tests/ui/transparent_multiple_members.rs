@@ -0,0 +1,11 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[repr(transparent)]
+#[derive(BitfieldStruct)]
+struct Foo {
+    #[bitfield(name = "x", ty = "u8", bits = "0..=3")]
+    x: u32,
+    y: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use c2rust_bitfields::BitfieldStruct;
+
+#[derive(BitfieldStruct)]
+#[bitfield(ord_by = "prio")]
+struct Foo {
+    #[bitfield(name = "priority", ty = "u8", bits = "0..=3")]
+    x: [u8; 1],
+}
+
+fn main() {}
@@ -128,3 +128,122 @@ pub fn find_llvm_config() -> Option<PathBuf> {
             .next()
         })
 }
+
+/// Parses linker flags as printed by `llvm-config --libs` or
+/// `llvm-config --system-libs` into values for `cargo:rustc-link-lib=`.
+///
+/// `-lfoo` becomes `foo`, and the `-framework Foo` pairs emitted on macOS
+/// become `framework=Foo`.
+pub fn parse_link_libs(flags: &str) -> Vec<String> {
+    let mut libs = Vec::new();
+    let mut tokens = flags.split_whitespace();
+
+    while let Some(token) = tokens.next() {
+        if token == "-framework" {
+            if let Some(framework) = tokens.next() {
+                libs.push(format!("framework={framework}"));
+            }
+        } else {
+            libs.push(token.trim_start_matches("-l").to_owned());
+        }
+    }
+
+    libs
+}
+
+/// Returns where the rust toolchain in `sysroot` would keep its own copy of
+/// the LLVM shared library `lib`, as named by `llvm-config --libs --link-shared`.
+///
+/// Unix toolchains keep it in `<sysroot>/lib/rustlib/<target>/lib/` as
+/// `lib<lib>.so` or `lib<lib>.dylib`, whereas Windows toolchains keep DLLs
+/// next to `rustc.exe` in `<sysroot>/bin/` without the `lib` prefix.
+pub fn rustlib_dylib_path(sysroot: &Path, target: &str, lib: &str) -> PathBuf {
+    let lib = lib.trim().trim_start_matches("-l");
+
+    if target.contains("windows") {
+        let lib = lib.trim_end_matches(".lib");
+        let lib = Path::new(lib).file_name().unwrap_or_else(|| lib.as_ref());
+        let mut dylib_file = lib.to_owned();
+        dylib_file.push(".dll");
+
+        return [sysroot, Path::new("bin"), Path::new(&dylib_file)]
+            .iter()
+            .collect();
+    }
+
+    let dylib_suffix = if target.contains("apple") {
+        "dylib"
+    } else {
+        "so"
+    };
+    let dylib_file = format!("lib{lib}.{dylib_suffix}");
+
+    [
+        sysroot,
+        Path::new("lib"),
+        Path::new("rustlib"),
+        Path::new(target),
+        Path::new("lib"),
+        Path::new(&dylib_file),
+    ]
+    .iter()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_link_libs, rustlib_dylib_path};
+    use std::path::Path;
+
+    #[test]
+    fn parse_plain_libs() {
+        assert_eq!(
+            parse_link_libs("-lLLVMCore -lLLVMSupport\n"),
+            ["LLVMCore", "LLVMSupport"]
+        );
+        assert!(parse_link_libs("").is_empty());
+    }
+
+    #[test]
+    fn parse_frameworks() {
+        assert_eq!(
+            parse_link_libs("-lm -lz -framework CoreFoundation -lcurses -framework Security"),
+            [
+                "m",
+                "z",
+                "framework=CoreFoundation",
+                "curses",
+                "framework=Security",
+            ]
+        );
+    }
+
+    #[test]
+    fn unix_rustlib_dylib_paths() {
+        let sysroot = Path::new("/sysroot");
+
+        assert_eq!(
+            rustlib_dylib_path(sysroot, "x86_64-unknown-linux-gnu", "-lLLVM-14\n"),
+            Path::new("/sysroot/lib/rustlib/x86_64-unknown-linux-gnu/lib/libLLVM-14.so")
+        );
+        assert_eq!(
+            rustlib_dylib_path(sysroot, "aarch64-apple-darwin", "-lLLVM"),
+            Path::new("/sysroot/lib/rustlib/aarch64-apple-darwin/lib/libLLVM.dylib")
+        );
+    }
+
+    #[test]
+    fn windows_rustlib_dylib_paths() {
+        let sysroot = Path::new("sysroot");
+        let expected: std::path::PathBuf = ["sysroot", "bin", "LLVM-C.dll"].iter().collect();
+
+        assert_eq!(
+            rustlib_dylib_path(sysroot, "x86_64-pc-windows-msvc", "-lLLVM-C"),
+            expected
+        );
+        assert_eq!(
+            rustlib_dylib_path(sysroot, "x86_64-pc-windows-msvc", "LLVM-C.lib"),
+            expected
+        );
+    }
+}
@@ -183,15 +183,27 @@ fn parse_attributes(attributes: Vec<Value>) -> IndexSet<Attribute> {
             "cold" => {
                 attrs.insert(Attribute::Cold);
             }
+            "constructor" => {
+                attrs.insert(Attribute::Constructor);
+            }
             "gnu_inline" => {
                 attrs.insert(Attribute::GnuInline);
             }
             "noinline" => {
                 attrs.insert(Attribute::NoInline);
             }
+            "noreturn" | "_Noreturn" => {
+                attrs.insert(Attribute::NoReturn);
+            }
+            "register" => {
+                attrs.insert(Attribute::Register);
+            }
             "used" => {
                 attrs.insert(Attribute::Used);
             }
+            "weak" => {
+                attrs.insert(Attribute::Weak);
+            }
             "visibility" => expect_visibility_value = true,
             "section" => expect_section_value = true,
             s if expect_section_value => {
@@ -240,6 +252,17 @@ fn display_loc(ctx: &AstContext, loc: &Option<SrcSpan>) -> Option<DisplaySrcSpan
     })
 }
 
+/// The alignment specified by `_Alignas` or the aligned attribute on a field
+/// decl which may not have been converted yet
+fn field_manual_alignment(untyped_context: &AstContext, field: ClangId) -> Option<u64> {
+    let field = untyped_context.ast_nodes.get(&field)?;
+
+    match field.tag {
+        ASTEntryTag::TagFieldDecl => expect_opt_u64(field.extras.get(4)?)?,
+        _ => None,
+    }
+}
+
 fn has_packed_attribute(attrs: Vec<Value>) -> bool {
     attrs
         .into_iter()
@@ -427,7 +450,19 @@ impl ConversionContext {
     ///
     /// This populates the `typed_context` of the `ConversionContext` it is called on.
     fn convert(&mut self, untyped_context: &AstContext) {
+        let is_doc_comment = |raw_comment: &CommentNode| {
+            untyped_context
+                .doc_comments
+                .values()
+                .any(|doc_comment| doc_comment.loc == raw_comment.loc)
+        };
+
+        // Doc comments are attached to the decl they document below instead
         for raw_comment in &untyped_context.comments {
+            if is_doc_comment(raw_comment) {
+                continue;
+            }
+
             let comment = Located {
                 loc: Some(raw_comment.loc.into()),
                 kind: raw_comment.string.clone(),
@@ -475,6 +510,52 @@ impl ConversionContext {
             }
         }
 
+        for (&decl_id, doc_comment) in &untyped_context.doc_comments {
+            match self.id_mapper.get_new(decl_id) {
+                Some(new_id) => {
+                    self.typed_context
+                        .doc_comments
+                        .insert(CDeclId(new_id), doc_comment.string.clone());
+                }
+                // Keep the comments of decls that weren't converted as plain comments
+                None if untyped_context
+                    .comments
+                    .iter()
+                    .any(|raw_comment| raw_comment.loc == doc_comment.loc) =>
+                {
+                    self.typed_context.comments.push(Located {
+                        loc: Some(doc_comment.loc.into()),
+                        kind: doc_comment.string.clone(),
+                    });
+                }
+                None => {}
+            }
+        }
+
+        for (&decl_id, annotations) in &untyped_context.annotations {
+            if let Some(new_id) = self.id_mapper.get_new(decl_id) {
+                self.typed_context
+                    .annotations
+                    .insert(CDeclId(new_id), annotations.clone());
+            }
+        }
+
+        for (&expr_id, source_text) in &untyped_context.source_texts {
+            if let Some(new_id) = self.id_mapper.get_new(expr_id) {
+                self.typed_context
+                    .source_texts
+                    .insert(CExprId(new_id), source_text.clone());
+            }
+        }
+
+        for (node_id, presumed) in &untyped_context.presumed_locs {
+            if let Some(node) = untyped_context.ast_nodes.get(node_id) {
+                self.typed_context
+                    .presumed_locs
+                    .insert(node.loc.begin(), presumed.clone());
+            }
+        }
+
         self.typed_context.va_list_kind = untyped_context.va_list_kind;
         self.typed_context.target = untyped_context.target.clone();
     }
@@ -597,6 +678,20 @@ impl ConversionContext {
                     self.processed_nodes.insert(new_id, OTHER_TYPE);
                 }
 
+                TypeTag::TagBitIntType if expected_ty & OTHER_TYPE != 0 => {
+                    let is_unsigned: bool =
+                        from_value(ty_node.extras[0].clone()).expect("BitInt signedness");
+                    let width: usize = from_value(ty_node.extras[1].clone()).expect("BitInt width");
+
+                    let bit_int_ty = if is_unsigned {
+                        CTypeKind::UBitInt(width)
+                    } else {
+                        CTypeKind::BitInt(width)
+                    };
+                    self.add_type(new_id, not_located(bit_int_ty));
+                    self.processed_nodes.insert(new_id, OTHER_TYPE);
+                }
+
                 TypeTag::TagPointer if expected_ty & OTHER_TYPE != 0 => {
                     let pointed =
                         from_value(ty_node.extras[0].clone()).expect("Pointer child not found");
@@ -699,6 +794,13 @@ impl ConversionContext {
                     let typedef_ty = CTypeKind::Typedef(decl_new);
                     self.add_type(new_id, not_located(typedef_ty));
                     self.processed_nodes.insert(new_id, expected_ty);
+
+                    let canonical = from_value(ty_node.extras[1].clone())
+                        .expect("Typedef canonical type not found");
+                    let canonical = self.visit_qualified_type(canonical);
+                    self.typed_context
+                        .canonical_types
+                        .insert(CTypeId(new_id), canonical);
                 }
 
                 TypeTag::TagEnumType if expected_ty & OTHER_TYPE != 0 => {
@@ -715,7 +817,11 @@ impl ConversionContext {
                         .expect("Decayed type child not found");
                     let decayed = self.visit_type(decayed_id);
 
-                    let decayed_ty = CTypeKind::Decayed(decayed);
+                    let original_id = from_value(ty_node.extras[1].clone())
+                        .expect("Decayed type original not found");
+                    let original = self.visit_type(original_id);
+
+                    let decayed_ty = CTypeKind::Decayed(decayed, original);
                     self.add_type(new_id, not_located(decayed_ty));
                     self.processed_nodes.insert(new_id, OTHER_TYPE);
                 }
@@ -1050,16 +1156,24 @@ impl ConversionContext {
 
                     let is_signed = from_value(node.extras[0].clone())
                         .expect("Case constant is_signed not found");
-                    let cie = match is_signed {
+                    let const_int_expr = |value: &Value| match is_signed {
                         false => ConstIntExpr::U(
-                            from_value(node.extras[1].clone()).expect("Case constant not found"),
+                            from_value(value.clone()).expect("Case constant not found"),
                         ),
                         true => ConstIntExpr::I(
-                            from_value(node.extras[1].clone()).expect("Case constant not found"),
+                            from_value(value.clone()).expect("Case constant not found"),
                         ),
                     };
+                    let cie = const_int_expr(&node.extras[1]);
+
+                    // GNU case ranges, `case lo ... hi:`, also have an upper bound
+                    let range_end = node.children.get(2).copied().flatten().map(|rhs_old| {
+                        let rhs = self.visit_expr(rhs_old);
+
+                        (rhs, const_int_expr(&node.extras[2]))
+                    });
 
-                    let case_stmt = CStmtKind::Case(expr, substmt, cie);
+                    let case_stmt = CStmtKind::Case(expr, substmt, cie, range_end);
 
                     self.add_stmt(new_id, located(node, case_stmt));
                     self.processed_nodes.insert(new_id, OTHER_STMT);
@@ -1332,6 +1446,17 @@ impl ConversionContext {
                     let ty_old = node.type_id.expect("Expected expression to have type");
                     let ty = self.visit_qualified_type(ty_old);
 
+                    let builtin = node
+                        .extras
+                        .get(0)
+                        .and_then(|extra| from_value::<Option<String>>(extra.clone()).ok())
+                        .flatten();
+                    if let Some(builtin) = builtin {
+                        self.typed_context
+                            .builtin_calls
+                            .insert(CExprId(new_id), builtin);
+                    }
+
                     let call = CExprKind::Call(ty, func, args);
 
                     self.expr_possibly_as_stmt(expected_ty, new_id, node, call);
@@ -1795,6 +1920,8 @@ impl ConversionContext {
                     let attributes = from_value::<Vec<Value>>(node.extras[7].clone())
                         .expect("Expected to find attributes");
                     let attrs = parse_attributes(attributes);
+                    let has_prototype = from_value(node.extras[8].clone())
+                        .expect("Expected to find whether function has a prototype");
 
                     // The always_inline attribute implies inline even if the
                     // inline keyword is not present.
@@ -1828,6 +1955,7 @@ impl ConversionContext {
                         is_implicit,
                         is_inline,
                         is_inline_externally_visible,
+                        has_prototype,
                         name,
                         parameters,
                         typ,
@@ -1918,6 +2046,8 @@ impl ConversionContext {
                         .expect("Expected to find whether decl is definition");
                     let attributes = from_value::<Vec<Value>>(node.extras[5].clone())
                         .expect("Expected attribute array on var decl");
+                    let manual_alignment =
+                        expect_opt_u64(&node.extras[6]).expect("Expected variable alignment");
 
                     assert!(
                         has_static_duration || has_thread_duration || !is_externally_visible,
@@ -1949,6 +2079,7 @@ impl ConversionContext {
                         initializer,
                         typ,
                         attrs,
+                        manual_alignment,
                     };
 
                     self.add_decl(new_id, located(node, variable_decl));
@@ -1987,6 +2118,14 @@ impl ConversionContext {
                     };
 
                     let is_packed = has_packed_attribute(attrs);
+                    // Rust can't align individual fields, so the record carries their
+                    // alignment and the translator pads each one out to its offset
+                    let manual_alignment = node
+                        .children
+                        .iter()
+                        .flatten()
+                        .map(|&field| field_manual_alignment(untyped_context, field))
+                        .fold(manual_alignment, Option::max);
 
                     let record = CDeclKind::Struct {
                         name,
@@ -2008,6 +2147,10 @@ impl ConversionContext {
                         .expect("Expected has_def flag on struct");
                     let attrs = from_value::<Vec<Value>>(node.extras[2].clone())
                         .expect("Expected attribute array on record");
+                    let manual_alignment =
+                        expect_opt_u64(&node.extras[3]).expect("Expected union alignment");
+                    let max_field_alignment =
+                        expect_opt_u64(&node.extras[4]).expect("Expected union field align");
                     let fields: Option<Vec<CDeclId>> = if has_def {
                         Some(
                             node.children
@@ -2025,11 +2168,21 @@ impl ConversionContext {
                     };
 
                     let is_packed = has_packed_attribute(attrs);
+                    // Rust can't align individual fields, but they all sit at offset 0
+                    // in a union, so aligning the union itself is enough
+                    let manual_alignment = node
+                        .children
+                        .iter()
+                        .flatten()
+                        .map(|&field| field_manual_alignment(untyped_context, field))
+                        .fold(manual_alignment, Option::max);
 
                     let record = CDeclKind::Union {
                         name,
                         fields,
                         is_packed,
+                        manual_alignment,
+                        max_field_alignment,
                     };
 
                     self.add_decl(new_id, located(node, record));
@@ -2048,12 +2201,18 @@ impl ConversionContext {
                         from_value(node.extras[2].clone()).expect("Did not find field bit offset");
                     let platform_type_bitwidth =
                         from_value(node.extras[3].clone()).expect("Did not find field bitwidth");
+                    let manual_alignment =
+                        expect_opt_u64(&node.extras[4]).expect("Expected field alignment");
+                    let is_anonymous =
+                        from_value(node.extras[5].clone()).expect("Expected field anonymity");
                     let field = CDeclKind::Field {
                         name,
                         typ,
                         bitfield_width,
                         platform_bit_offset,
                         platform_type_bitwidth,
+                        manual_alignment,
+                        is_anonymous,
                     };
                     self.add_decl(new_id, located(node, field));
                     self.processed_nodes.insert(new_id, FIELD_DECL);
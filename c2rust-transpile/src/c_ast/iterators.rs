@@ -202,7 +202,13 @@ fn immediate_stmt_children(kind: &CStmtKind) -> Vec<SomeId> {
     match *kind {
         Expr(e) => intos![e],
         Label(s) => intos![s],
-        Case(e, s, _) => intos![e, s],
+        Case(e, s, _, range_end) => {
+            let mut res = intos![e, s];
+            if let Some((hi, _)) = range_end {
+                res.push(hi.into())
+            }
+            res
+        }
         Default(s) => intos![s],
 
         // Compound statements (6.8.2)
@@ -284,8 +290,8 @@ fn immediate_type_children(kind: &CTypeKind) -> Vec<SomeId> {
         Elaborated(_) => vec![], // These are references to previous definitions
         TypeOfExpr(e) => intos![e],
         Void | Bool | Short | Int | Long | LongLong | UShort | UInt | ULong | ULongLong | SChar
-        | UChar | Char | Double | LongDouble | Float | Int128 | UInt128 | BuiltinFn | Half
-        | BFloat16 => {
+        | UChar | Char | Double | LongDouble | Float | Int128 | UInt128 | BitInt(_)
+        | UBitInt(_) | BuiltinFn | Half | BFloat16 => {
             vec![]
         }
 
@@ -297,7 +303,7 @@ fn immediate_type_children(kind: &CTypeKind) -> Vec<SomeId> {
             intos![qtype.ctype]
         }
 
-        Decayed(ctype)
+        Decayed(ctype, _)
         | Paren(ctype)
         | TypeOf(ctype)
         | Complex(ctype)
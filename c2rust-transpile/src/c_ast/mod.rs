@@ -9,7 +9,9 @@ use std::ops::Index;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-pub use c2rust_ast_exporter::clang_ast::{BuiltinVaListKind, SrcFile, SrcLoc, SrcSpan};
+pub use c2rust_ast_exporter::clang_ast::{
+    BuiltinVaListKind, PresumedLoc, SrcFile, SrcLoc, SrcSpan,
+};
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
 pub struct CTypeId(pub u64);
@@ -80,8 +82,29 @@ pub struct TypedAstContext {
     // if any
     pub macro_expansion_text: HashMap<CExprId, String>,
 
+    // map calls to builtin functions to the name of the builtin being called
+    pub builtin_calls: HashMap<CExprId, String>,
+
     pub comments: Vec<Located<String>>,
 
+    // map file scope decls to the raw text of their doc comments
+    pub doc_comments: HashMap<CDeclId, String>,
+
+    // map decls to the annotations attached to them by C2RUST_EXPORT_ANNOTATIONS
+    pub annotations: HashMap<CDeclId, Vec<String>>,
+
+    // map expressions to their source spelling, recorded with
+    // C2RUST_EXPORT_SOURCE_TEXT
+    pub source_texts: HashMap<CExprId, String>,
+
+    // map typedef types to the canonical type they name, with the
+    // qualifiers of every typedef along the way
+    pub canonical_types: HashMap<CTypeId, CQualTypeId>,
+
+    // map the spelling locations of nodes to where `#line` directives or line
+    // markers claim they are, for the nodes where the two differ
+    pub presumed_locs: HashMap<SrcLoc, PresumedLoc>,
+
     // The key is the typedef decl being squashed away,
     // and the value is the decl id to the corresponding structure
     pub prenamed_decls: IndexMap<CDeclId, CDeclId>,
@@ -181,20 +204,47 @@ impl TypedAstContext {
             macro_invocations: HashMap::new(),
             macro_expansions: HashMap::new(),
             macro_expansion_text: HashMap::new(),
+            builtin_calls: HashMap::new(),
             label_names: Default::default(),
 
             comments: Vec::new(),
+            doc_comments: HashMap::new(),
+            annotations: HashMap::new(),
+            source_texts: HashMap::new(),
+            canonical_types: HashMap::new(),
+            presumed_locs: HashMap::new(),
             prenamed_decls: IndexMap::new(),
             va_list_kind: BuiltinVaListKind::CharPtrBuiltinVaList,
             target: String::new(),
         }
     }
 
+    /// Displays a location for diagnostics, preferring where `#line`
+    /// directives or line markers claim it is over where it's spelled
     pub fn display_loc(&self, loc: &Option<SrcSpan>) -> Option<DisplaySrcSpan> {
-        loc.as_ref().map(|loc| DisplaySrcSpan {
-            file: self.files[self.file_map[loc.fileid as usize]].path.clone(),
-            loc: *loc,
-        })
+        loc.as_ref()
+            .map(|loc| match self.presumed_locs.get(&loc.begin()) {
+                Some(presumed) => DisplaySrcSpan {
+                    file: Some(PathBuf::from(&presumed.file)),
+                    // The end is shifted by however far the begin moved, since
+                    // only the begin's presumed location is exported
+                    loc: SrcSpan {
+                        begin_line: presumed.line,
+                        begin_column: presumed.column,
+                        end_line: presumed.line + loc.end_line.saturating_sub(loc.begin_line),
+                        end_column: if loc.end_line == loc.begin_line {
+                            presumed.column + loc.end_column.saturating_sub(loc.begin_column)
+                        } else {
+                            loc.end_column
+                        },
+                        ..*loc
+                    },
+                },
+                None => DisplaySrcSpan {
+                    file: self.files[self.file_map[loc.fileid as usize]].path.clone(),
+                    loc: *loc,
+                },
+            })
     }
 
     pub fn get_source_path<'a, T>(&'a self, node: &Located<T>) -> Option<&'a Path> {
@@ -458,7 +508,7 @@ impl TypedAstContext {
         let ty = match self.index(typ).kind {
             Attributed(ty, _) => ty.ctype,
             Elaborated(ty) => ty,
-            Decayed(ty) => ty,
+            Decayed(ty, _) => ty,
             TypeOf(ty) => ty,
             Paren(ty) => ty,
             Typedef(decl) => match self.index(decl).kind {
@@ -470,6 +520,13 @@ impl TypedAstContext {
         self.resolve_type_id(ty)
     }
 
+    /// The canonical type a typedef type ultimately names, or `None` for any
+    /// other kind of type. Unlike `resolve_type_id`, this keeps the
+    /// qualifiers of the typedefs along the way.
+    pub fn canonical_type(&self, typ: CTypeId) -> Option<CQualTypeId> {
+        self.canonical_types.get(&typ).copied()
+    }
+
     pub fn resolve_type(&self, typ: CTypeId) -> &CType {
         let resolved_typ_id = self.resolve_type_id(typ);
         self.index(resolved_typ_id)
@@ -581,6 +638,14 @@ impl TypedAstContext {
                 } => true,
                 Variable { ref attrs, .. } | Function { ref attrs, .. }
                     if attrs.contains(&Attribute::Used) => true,
+                // Constructors are never referenced but still run
+                Function {
+                    body: Some(_),
+                    ref attrs,
+                    ..
+                } if attrs.contains(&Attribute::Constructor) => true,
+                // Static asserts are translated to `const` assertions
+                StaticAssert { .. } => true,
                 _ => false,
             };
 
@@ -898,6 +963,8 @@ pub enum CDeclKind {
         is_implicit: bool,
         is_extern: bool,
         is_inline_externally_visible: bool,
+        // false for K&R style declarations such as `void f();`
+        has_prototype: bool,
         typ: CFuncTypeId,
         name: String,
         parameters: Vec<CParamId>,
@@ -915,6 +982,7 @@ pub enum CDeclKind {
         initializer: Option<CExprId>,
         typ: CQualTypeId,
         attrs: IndexSet<Attribute>,
+        manual_alignment: Option<u64>,
     },
 
     // Enum (http://clang.llvm.org/doxygen/classclang_1_1EnumDecl.html)
@@ -952,6 +1020,8 @@ pub enum CDeclKind {
         name: Option<String>,
         fields: Option<Vec<CFieldId>>,
         is_packed: bool,
+        manual_alignment: Option<u64>,
+        max_field_alignment: Option<u64>,
     },
 
     // Field
@@ -961,6 +1031,10 @@ pub enum CDeclKind {
         bitfield_width: Option<u64>,
         platform_bit_offset: u64,
         platform_type_bitwidth: u64,
+        manual_alignment: Option<u64>,
+        // C11 anonymous struct or union member, whose fields are accessed
+        // through an implicit access of the member itself
+        is_anonymous: bool,
     },
 
     MacroObject {
@@ -1450,7 +1524,14 @@ pub enum CStmtKind {
     //
     // All of these have a `CStmtId` to represent the substatement that comes after them
     Label(CStmtId),
-    Case(CExprId, CStmtId, ConstIntExpr),
+    // The optional expression and constant are the upper bound of a GNU case
+    // range, `case lo ... hi:`
+    Case(
+        CExprId,
+        CStmtId,
+        ConstIntExpr,
+        Option<(CExprId, ConstIntExpr)>,
+    ),
     Default(CStmtId),
 
     // Compound statements (6.8.2)
@@ -1613,6 +1694,10 @@ pub enum CTypeKind {
     Int128,
     UInt128,
 
+    // Bit-precise integer types (C23), holding their width in bits
+    BitInt(usize),
+    UBitInt(usize),
+
     Complex(CTypeId),
 
     // Pointer types (6.7.5.1)
@@ -1646,8 +1731,9 @@ pub enum CTypeKind {
     // Type definition type (6.7.7)
     Typedef(CTypedefId),
 
-    // Represents a pointer type decayed from an array or function type.
-    Decayed(CTypeId),
+    // Represents a pointer type decayed from an array or function type, along with the type as
+    // written, such as the variable length array of an `int a[n]` parameter.
+    Decayed(CTypeId, CTypeId),
     Elaborated(CTypeId),
 
     // Type wrapped in parentheses
@@ -1725,19 +1811,26 @@ pub enum Attribute {
     AlwaysInline,
     /// __attribute__((cold, __cold__))
     Cold,
+    /// __attribute__((constructor, __constructor__))
+    Constructor,
     /// __attribute__((gnu_inline, __gnu_inline__))
     GnuInline,
     /// __attribute__((no_inline, __no_inline__))
     NoInline,
+    /// __attribute__((noreturn, __noreturn__)), _Noreturn
     NoReturn,
     NotNull,
     Nullable,
+    /// register storage class specifier
+    Register,
     /// __attribute__((section("foo"), __section__("foo")))
     Section(String),
     /// __attribute__((used, __used__))
     Used,
     /// __attribute((visibility("hidden")))
     Visibility(String),
+    /// __attribute__((weak, __weak__))
+    Weak,
     /// __attribute__((fallthrough, __fallthrough__))
     Fallthrough,
 }
@@ -1763,14 +1856,17 @@ impl CTypeKind {
         use CTypeKind::*;
         matches!(
             self,
-            Bool | UChar | UInt | UShort | ULong | ULongLong | UInt128
+            Bool | UChar | UInt | UShort | ULong | ULongLong | UInt128 | UBitInt(_)
         )
     }
 
     pub fn is_signed_integral_type(&self) -> bool {
         use CTypeKind::*;
         // `Char` is true on the platforms we handle
-        matches!(self, Char | SChar | Int | Short | Long | LongLong | Int128)
+        matches!(
+            self,
+            Char | SChar | Int | Short | Long | LongLong | Int128 | BitInt(_)
+        )
     }
 
     pub fn is_floating_type(&self) -> bool {
@@ -83,8 +83,24 @@ impl<W: Write> Printer<W> {
             BadExpr => {
                 self.writer.write_all(b"BAD")?;
             }
-            DesignatedInitExpr(..) => {
-                self.writer.write_all(b"DESIGNATED INIT EXPR")?;
+            DesignatedInitExpr(_, ref designators, init) => {
+                for designator in designators {
+                    match *designator {
+                        Designator::Index(index) => {
+                            self.writer.write_fmt(format_args!("[{}]", index))?;
+                        }
+                        Designator::Range(start, end) => {
+                            self.writer
+                                .write_fmt(format_args!("[{} ... {}]", start, end))?;
+                        }
+                        Designator::Field(field) => {
+                            self.writer.write_all(b".")?;
+                            self.print_decl_name(field, context)?;
+                        }
+                    }
+                }
+                self.writer.write_all(b" = ")?;
+                self.print_expr(*init, context)?;
             }
             ShuffleVector(..) => {
                 self.writer.write_all(b"SHUFFLE")?;
@@ -506,6 +522,7 @@ impl<W: Write> Printer<W> {
         match decl {
             &Function {
                 is_global,
+                is_inline,
                 ref name,
                 ref parameters,
                 body,
@@ -514,6 +531,9 @@ impl<W: Write> Printer<W> {
                 if !is_global {
                     self.writer.write_all(b"static ")?;
                 }
+                if is_inline {
+                    self.writer.write_all(b"inline ")?;
+                }
                 // TODO typ
                 self.writer.write_fmt(format_args!("{}", name))?;
                 self.writer.write_all(b"(\n")?;
@@ -545,16 +565,23 @@ impl<W: Write> Printer<W> {
                 ref ident,
                 initializer,
                 typ,
-                ..
+                ref attrs,
+                manual_alignment,
             } => {
                 if is_externally_visible && !is_defn {
                     self.writer.write_all(b"extern ")?;
                 } else if !is_externally_visible && (has_static_duration || has_thread_duration) {
                     self.writer.write_all(b"static ")?;
+                } else if attrs.contains(&Attribute::Register) {
+                    self.writer.write_all(b"register ")?;
                 }
                 if has_thread_duration {
                     self.writer.write_all(b"__thread ")?;
                 }
+                if let Some(alignment) = manual_alignment {
+                    self.writer
+                        .write_fmt(format_args!("_Alignas({}) ", alignment))?;
+                }
                 self.print_qtype(typ, Some(ident.as_str()), context)?;
                 if let Some(init) = initializer {
                     self.writer.write_all(b" = ")?;
@@ -720,7 +747,7 @@ impl<W: Write> Printer<W> {
             &Elaborated(ctype) => {
                 self.print_type(ctype, ident, context)?;
             }
-            &Decayed(ctype) => {
+            &Decayed(ctype, _) => {
                 self.print_type(ctype, ident, context)?;
             }
             &Paren(ctype) => {
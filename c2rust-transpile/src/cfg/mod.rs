@@ -1821,31 +1821,40 @@ impl CfgBuilder {
                 Ok(None)
             }
 
-            CStmtKind::Case(case_expr, sub_stmt, cie) => {
+            CStmtKind::Case(case_expr, sub_stmt, cie, range_end) => {
                 self.last_per_stmt_mut().saw_unmatched_case = true;
                 let this_label = Label::FromC(stmt_id, None);
                 self.add_wip_block(wip, Jump(this_label.clone()));
 
                 // Case
-                let resolved = translator.ast_context.resolve_expr(case_expr);
-                let branch = match resolved.1 {
-                    CExprKind::Literal(..) | CExprKind::ConstantExpr(_, _, Some(_)) => {
-                        match translator
-                            .convert_expr(ctx.used(), resolved.0)?
-                            .to_pure_expr()
-                        {
-                            Some(expr) => match *expr {
-                                Expr::Lit(..) | Expr::Path(..) => Some(expr),
+                let convert_case = |case_expr: CExprId, cie: ConstIntExpr| {
+                    let resolved = translator.ast_context.resolve_expr(case_expr);
+                    let branch = match resolved.1 {
+                        CExprKind::Literal(..) | CExprKind::ConstantExpr(_, _, Some(_)) => {
+                            match translator
+                                .convert_expr(ctx.used(), resolved.0)?
+                                .to_pure_expr()
+                            {
+                                Some(expr) => match *expr {
+                                    Expr::Lit(..) | Expr::Path(..) => Some(expr),
+                                    _ => None,
+                                },
                                 _ => None,
-                            },
-                            _ => None,
+                            }
                         }
+                        _ => None,
+                    };
+                    match branch {
+                        Some(expr) => Ok(expr),
+                        None => translator.convert_constant(cie),
                     }
-                    _ => None,
                 };
-                let branch = match branch {
-                    Some(expr) => expr,
-                    None => translator.convert_constant(cie)?,
+                let branch = convert_case(case_expr, cie)?;
+                let pat = match range_end {
+                    Some((hi_expr, hi_cie)) => {
+                        mk().range_pat(branch, convert_case(hi_expr, hi_cie)?)
+                    }
+                    None => mk().lit_pat(branch),
                 };
                 self.switch_expr_cases
                     .last_mut()
@@ -1856,7 +1865,7 @@ impl CfgBuilder {
                         )
                     })?
                     .cases
-                    .push((mk().lit_pat(branch), this_label.clone()));
+                    .push((pat, this_label.clone()));
 
                 // Sub stmt
                 let sub_stmt_next =
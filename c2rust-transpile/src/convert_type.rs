@@ -329,12 +329,16 @@ impl TypeConverter {
             CTypeKind::Float => Ok(mk().path_ty(mk().path(vec!["libc", "c_float"]))),
             CTypeKind::Int128 => Ok(mk().path_ty(mk().path(vec!["i128"]))),
             CTypeKind::UInt128 => Ok(mk().path_ty(mk().path(vec!["u128"]))),
+            CTypeKind::BitInt(width) => Ok(mk().path_ty(mk().path(vec![bit_int_ty(true, width)?]))),
+            CTypeKind::UBitInt(width) => {
+                Ok(mk().path_ty(mk().path(vec![bit_int_ty(false, width)?])))
+            }
             CTypeKind::BFloat16 => Ok(mk().path_ty(mk().path(vec!["bf16"]))),
 
             CTypeKind::Pointer(qtype) => self.convert_pointer(ctxt, qtype),
 
             CTypeKind::Elaborated(ref ctype) => self.convert(ctxt, *ctype),
-            CTypeKind::Decayed(ref ctype) => self.convert(ctxt, *ctype),
+            CTypeKind::Decayed(ref ctype, _) => self.convert(ctxt, *ctype),
             CTypeKind::Paren(ref ctype) => self.convert(ctxt, *ctype),
 
             CTypeKind::Struct(decl_id) => {
@@ -435,7 +439,7 @@ impl TypeConverter {
             CTypeKind::Elaborated(ref ctype) => {
                 self.knr_function_type_with_parameters(ctxt, *ctype, params)
             }
-            CTypeKind::Decayed(ref ctype) => {
+            CTypeKind::Decayed(ref ctype, _) => {
                 self.knr_function_type_with_parameters(ctxt, *ctype, params)
             }
             CTypeKind::Paren(ref ctype) => {
@@ -454,3 +458,30 @@ impl TypeConverter {
         }
     }
 }
+
+/// The Rust integer type of a `_BitInt(width)`. Only the widths of Rust's
+/// own integer types are accepted, since any other width would wrap at the
+/// wrong bit in translated arithmetic.
+fn bit_int_ty(is_signed: bool, width: usize) -> TranslationResult<&'static str> {
+    let ty = match (is_signed, width) {
+        (true, 8) => "i8",
+        (true, 16) => "i16",
+        (true, 32) => "i32",
+        (true, 64) => "i64",
+        (true, 128) => "i128",
+        (false, 8) => "u8",
+        (false, 16) => "u16",
+        (false, 32) => "u32",
+        (false, 64) => "u64",
+        (false, 128) => "u128",
+        _ => {
+            return Err(format_err!(
+                "Unsupported _BitInt width {}, which has no matching Rust integer type",
+                width
+            )
+            .into())
+        }
+    };
+
+    Ok(ty)
+}
@@ -21,26 +21,35 @@ impl<'c> Translation<'c> {
     pub fn convert_builtin(
         &self,
         ctx: ExprContext,
+        call_id: CExprId,
         fexp: CExprId,
         args: &[CExprId],
     ) -> TranslationResult<WithStmts<Box<Expr>>> {
         let expr = &self.ast_context[fexp];
         let src_loc = &expr.loc;
-        let decl_id = match expr.kind {
-            CExprKind::DeclRef(_, decl_id, _) => decl_id,
-            _ => {
-                return Err(TranslationError::generic(
-                    "Expected declref when processing builtin",
-                ))
-            }
-        };
 
-        let builtin_name: &str = match self.ast_context[decl_id].kind {
-            CDeclKind::Function { ref name, .. } => name,
-            _ => {
-                return Err(TranslationError::generic(
-                    "Expected function when processing builtin",
-                ))
+        // Prefer the builtin identity clang recorded for the call, falling
+        // back to the name of the callee for ASTs exported without it
+        let builtin_name: &str = match self.ast_context.builtin_calls.get(&call_id) {
+            Some(name) => name,
+            None => {
+                let decl_id = match expr.kind {
+                    CExprKind::DeclRef(_, decl_id, _) => decl_id,
+                    _ => {
+                        return Err(TranslationError::generic(
+                            "Expected declref when processing builtin",
+                        ))
+                    }
+                };
+
+                match self.ast_context[decl_id].kind {
+                    CDeclKind::Function { ref name, .. } => name,
+                    _ => {
+                        return Err(TranslationError::generic(
+                            "Expected function when processing builtin",
+                        ))
+                    }
+                }
             }
         };
 
@@ -649,11 +658,19 @@ impl<'c> Translation<'c> {
                 })
             }
 
-            _ => Err(format_translation_err!(
-                self.ast_context.display_loc(src_loc),
-                "Unimplemented builtin {}",
-                builtin_name
-            )),
+            _ => match self.ast_context.source_texts.get(&call_id) {
+                Some(text) => Err(format_translation_err!(
+                    self.ast_context.display_loc(src_loc),
+                    "Unimplemented builtin {} in `{}`",
+                    builtin_name,
+                    text,
+                )),
+                None => Err(format_translation_err!(
+                    self.ast_context.display_loc(src_loc),
+                    "Unimplemented builtin {}",
+                    builtin_name
+                )),
+            },
         }
     }
 
@@ -13,6 +13,7 @@ use indexmap::indexmap;
 use indexmap::{IndexMap, IndexSet};
 use log::{error, info, trace, warn};
 use proc_macro2::{Punct, Spacing::*, Span, TokenStream, TokenTree};
+use syn::parse::Parser as _;
 use syn::spanned::Spanned as _;
 use syn::*;
 use syn::{BinOp, UnOp}; // To override c_ast::{BinOp,UnOp} from glob import
@@ -398,6 +399,54 @@ fn mk_linkage(in_extern_block: bool, new_name: &str, old_name: &str) -> Builder
     }
 }
 
+/// Generate a static which registers the `unsafe extern "C" fn()` named `fn_name` to run before
+/// `main`, the way C toolchains run static initializers and `__attribute__((constructor))`s.
+fn mk_init_array_static(static_name: &str, fn_name: &str) -> Box<Item> {
+    let static_attributes = mk()
+        .single_attr("used")
+        .meta_item_attr(
+            AttrStyle::Outer,
+            mk().meta_list(
+                "cfg_attr",
+                vec![
+                    mk().nested_meta_item(mk().meta_namevalue("target_os", "linux")),
+                    mk().nested_meta_item(mk().meta_namevalue("link_section", ".init_array")),
+                ],
+            ),
+        )
+        .meta_item_attr(
+            AttrStyle::Outer,
+            mk().meta_list(
+                "cfg_attr",
+                vec![
+                    mk().nested_meta_item(mk().meta_namevalue("target_os", "windows")),
+                    mk().nested_meta_item(mk().meta_namevalue("link_section", ".CRT$XIB")),
+                ],
+            ),
+        )
+        .meta_item_attr(
+            AttrStyle::Outer,
+            mk().meta_list(
+                "cfg_attr",
+                vec![
+                    mk().nested_meta_item(mk().meta_namevalue("target_os", "macos")),
+                    mk().nested_meta_item(
+                        mk().meta_namevalue("link_section", "__DATA,__mod_init_func"),
+                    ),
+                ],
+            ),
+        );
+    let static_array_size = mk().lit_expr(mk().int_unsuffixed_lit(1));
+    let fn_bare_decl = (vec![], None, ReturnType::Default);
+    let static_ty = mk().array_ty(
+        mk().unsafe_().extern_("C").barefn_ty(fn_bare_decl),
+        static_array_size,
+    );
+    let static_val = mk().array_expr(vec![mk().path_expr(vec![fn_name])]);
+
+    static_attributes.static_item(static_name, static_ty, static_val)
+}
+
 pub fn signed_int_expr(value: i64) -> Box<Expr> {
     if value < 0 {
         mk().unary_expr(
@@ -648,7 +697,9 @@ pub fn translate(
                         let msg = format!("Skipping declaration {:?} due to error: {}", k, e);
                         translate_failure(t.tcfg, &msg);
                     }
-                    Ok(converted_decl) => {
+                    Ok(mut converted_decl) => {
+                        t.add_doc_comment(decl_id, &mut converted_decl);
+                        t.add_annotations(decl_id, &mut converted_decl);
                         use ConvertedDecl::*;
                         match converted_decl {
                             Item(item) => {
@@ -704,6 +755,7 @@ pub fn translate(
                 Variable { .. } => true,
                 MacroObject { .. } => tcfg.translate_const_macros,
                 MacroFunction { .. } => tcfg.translate_fn_macros,
+                StaticAssert { .. } => true,
                 _ => false,
             };
             if needs_export {
@@ -735,7 +787,9 @@ pub fn translate(
                         };
                         translate_failure(t.tcfg, &msg);
                     }
-                    Ok(converted_decl) => {
+                    Ok(mut converted_decl) => {
+                        t.add_doc_comment(*top_id, &mut converted_decl);
+                        t.add_annotations(*top_id, &mut converted_decl);
                         use ConvertedDecl::*;
                         match converted_decl {
                             Item(item) => {
@@ -1105,6 +1159,50 @@ fn add_src_loc_attr(attrs: &mut Vec<syn::Attribute>, src_loc: &Option<SrcLoc>) {
     }
 }
 
+/// Split the raw text of a C doc comment into the lines of a Rust doc comment,
+/// dropping the comment markers and any blank lines around the text
+fn doc_comment_lines(raw: &str) -> Vec<String> {
+    let mut lines: Vec<String> = raw
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            let line = ["///", "//!", "/**", "/*!"]
+                .iter()
+                .find_map(|opener| line.strip_prefix(opener))
+                .or_else(|| line.strip_prefix('*'))
+                .unwrap_or(line);
+            let line = line.trim_end();
+            let line = line.strip_suffix("*/").unwrap_or(line).trim_end();
+            let line = line.strip_prefix(' ').unwrap_or(line);
+
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", line)
+            }
+        })
+        .collect();
+
+    while lines.last().map_or(false, String::is_empty) {
+        lines.pop();
+    }
+    let leading_blanks = lines.iter().take_while(|line| line.is_empty()).count();
+    lines.drain(..leading_blanks);
+
+    lines
+}
+
+/// Add `#[doc = "..."]` attributes for a C doc comment in front of the other
+/// attributes of an item/foreign_item
+fn add_doc_attrs(attrs: &mut Vec<syn::Attribute>, raw: &str) {
+    let doc_attrs = doc_comment_lines(raw).into_iter().map(|line| {
+        let meta = mk().meta_namevalue(vec!["doc"], line);
+        let prepared = mk().prepare_meta(meta);
+        mk().attribute(AttrStyle::Outer, prepared.path, prepared.tokens)
+    });
+    attrs.splice(0..0, doc_attrs);
+}
+
 /// Get a mutable reference to the attributes of a ForeignItem
 fn foreign_item_attrs(item: &mut ForeignItem) -> Option<&mut Vec<syn::Attribute>> {
     use ForeignItem::*;
@@ -1487,6 +1585,7 @@ impl<'c> Translation<'c> {
 
     fn add_static_initializer_to_section(
         &self,
+        decl_id: CDeclId,
         name: &str,
         typ: CQualTypeId,
         init: &mut Box<Expr>,
@@ -1495,7 +1594,10 @@ impl<'c> Translation<'c> {
 
         std::mem::swap(init, &mut default_init);
 
-        let root_lhs_expr = mk().path_expr(vec![name]);
+        let mut root_lhs_expr = mk().path_expr(vec![name]);
+        if self.variable_alignment(decl_id).is_some() {
+            root_lhs_expr = mk().anon_field_expr(root_lhs_expr, 0);
+        }
         let assign_expr = mk().assign_expr(root_lhs_expr, default_init);
         let stmt = mk().expr_stmt(assign_expr);
 
@@ -1513,52 +1615,11 @@ impl<'c> Translation<'c> {
             .borrow_mut()
             .pick_name("run_static_initializers");
         let fn_ty = ReturnType::Default;
-        let fn_decl = mk().fn_decl(fn_name.clone(), vec![], None, fn_ty.clone());
-        let fn_bare_decl = (vec![], None, fn_ty);
+        let fn_decl = mk().fn_decl(fn_name.clone(), vec![], None, fn_ty);
         let fn_block = mk().block(sectioned_static_initializers);
         let fn_item = mk().unsafe_().extern_("C").fn_item(fn_decl, fn_block);
 
-        let static_attributes = mk()
-            .single_attr("used")
-            .meta_item_attr(
-                AttrStyle::Outer,
-                mk().meta_list(
-                    "cfg_attr",
-                    vec![
-                        mk().nested_meta_item(mk().meta_namevalue("target_os", "linux")),
-                        mk().nested_meta_item(mk().meta_namevalue("link_section", ".init_array")),
-                    ],
-                ),
-            )
-            .meta_item_attr(
-                AttrStyle::Outer,
-                mk().meta_list(
-                    "cfg_attr",
-                    vec![
-                        mk().nested_meta_item(mk().meta_namevalue("target_os", "windows")),
-                        mk().nested_meta_item(mk().meta_namevalue("link_section", ".CRT$XIB")),
-                    ],
-                ),
-            )
-            .meta_item_attr(
-                AttrStyle::Outer,
-                mk().meta_list(
-                    "cfg_attr",
-                    vec![
-                        mk().nested_meta_item(mk().meta_namevalue("target_os", "macos")),
-                        mk().nested_meta_item(
-                            mk().meta_namevalue("link_section", "__DATA,__mod_init_func"),
-                        ),
-                    ],
-                ),
-            );
-        let static_array_size = mk().lit_expr(mk().int_unsuffixed_lit(1));
-        let static_ty = mk().array_ty(
-            mk().unsafe_().extern_("C").barefn_ty(fn_bare_decl),
-            static_array_size,
-        );
-        let static_val = mk().array_expr(vec![mk().path_expr(vec![fn_name])]);
-        let static_item = static_attributes.static_item("INIT_ARRAY", static_ty, static_val);
+        let static_item = mk_init_array_static("INIT_ARRAY", &fn_name);
 
         (fn_item, static_item)
     }
@@ -1747,6 +1808,8 @@ impl<'c> Translation<'c> {
             Union {
                 fields: Some(ref fields),
                 is_packed,
+                manual_alignment,
+                max_field_alignment,
                 ..
             } => {
                 let name = self
@@ -1775,10 +1838,26 @@ impl<'c> Translation<'c> {
                     }
                 }
 
-                let mut repr = vec!["C"];
-                if is_packed {
-                    repr.push("packed");
+                let mut reprs = vec![simple_metaitem("C")];
+                let max_field_alignment = if is_packed {
+                    Some(1)
+                } else {
+                    max_field_alignment
+                };
+                match max_field_alignment {
+                    Some(1) => reprs.push(simple_metaitem("packed")),
+                    Some(mf) if mf > 1 => reprs.push(int_arg_metaitem("packed", mf as u128)),
+                    // Rust rejects `packed` and `align` on the same type, and
+                    // unions can't be split into an inner and outer type like
+                    // structs, so manual alignment is only honored when the
+                    // union isn't packed
+                    _ => {
+                        if let Some(alignment) = manual_alignment {
+                            reprs.push(int_arg_metaitem("align", alignment as u128));
+                        }
+                    }
                 }
+                let repr_attr = mk().meta_list("repr", reprs);
 
                 Ok(if field_syns.is_empty() {
                     // Empty unions are a GNU extension, but Rust doesn't allow empty unions.
@@ -1786,7 +1865,7 @@ impl<'c> Translation<'c> {
                         mk().span(span)
                             .pub_()
                             .call_attr("derive", vec!["Copy", "Clone"])
-                            .call_attr("repr", repr)
+                            .meta_item_attr(AttrStyle::Outer, repr_attr)
                             .struct_item(name, vec![], false),
                     )
                 } else {
@@ -1794,7 +1873,7 @@ impl<'c> Translation<'c> {
                         mk().span(span)
                             .pub_()
                             .call_attr("derive", vec!["Copy", "Clone"])
-                            .call_attr("repr", repr)
+                            .meta_item_attr(AttrStyle::Outer, repr_attr)
                             .union_item(name, field_syns),
                     )
                 })
@@ -2070,7 +2149,7 @@ impl<'c> Translation<'c> {
                         .map(pos_to_span)
                         .unwrap_or(span);
 
-                    self.add_static_initializer_to_section(new_name, typ, &mut init)?;
+                    self.add_static_initializer_to_section(decl_id, new_name, typ, &mut init)?;
 
                     (ty, init)
                 } else {
@@ -2089,6 +2168,17 @@ impl<'c> Translation<'c> {
                     (ty, init)
                 };
 
+                let (ty, init, wrapper) = match self.variable_alignment(decl_id) {
+                    Some(alignment) => {
+                        let (wrapper, wrapper_name) =
+                            self.aligned_variable_wrapper(new_name, alignment, ty);
+                        let init = mk().call_expr(mk().path_expr(vec![&wrapper_name]), vec![init]);
+
+                        (mk().path_ty(vec![wrapper_name]), init, Some(wrapper))
+                    }
+                    None => (ty, init, None),
+                };
+
                 let static_def = if is_externally_visible {
                     mk_linkage(false, new_name, ident).pub_().extern_("C")
                 } else if self.cur_file.borrow().is_some() {
@@ -2111,13 +2201,19 @@ impl<'c> Translation<'c> {
                         c_ast::Attribute::Section(name) => {
                             static_def.str_attr("link_section", name)
                         }
+                        c_ast::Attribute::Weak if is_externally_visible => {
+                            self.use_feature("linkage");
+                            static_def.str_attr("linkage", "weak")
+                        }
                         _ => continue,
                     }
                 }
 
-                Ok(ConvertedDecl::Item(
-                    static_def.static_item(new_name, ty, init),
-                ))
+                let static_item = static_def.static_item(new_name, ty, init);
+                match wrapper {
+                    Some(wrapper) => Ok(ConvertedDecl::Items(vec![static_item, wrapper])),
+                    None => Ok(ConvertedDecl::Item(static_item)),
+                }
             }
 
             Variable { .. } => Err(TranslationError::generic(
@@ -2174,9 +2270,50 @@ impl<'c> Translation<'c> {
             // their canonical declaration.
             NonCanonicalDecl { .. } => Ok(ConvertedDecl::NoItem),
 
-            StaticAssert { .. } => {
-                warn!("ignoring static assert during translation");
-                Ok(ConvertedDecl::NoItem)
+            StaticAssert {
+                assert_expr,
+                message,
+            } => {
+                let cond = match self.convert_condition(ctx.set_const(true), true, assert_expr) {
+                    Ok(cond) => cond.to_pure_expr(),
+                    Err(e) => {
+                        warn!("ignoring untranslatable static assert: {}", e);
+                        return Ok(ConvertedDecl::NoItem);
+                    }
+                };
+                let cond = match cond {
+                    Some(cond) => cond,
+                    None => {
+                        warn!("ignoring static assert with side effects");
+                        return Ok(ConvertedDecl::NoItem);
+                    }
+                };
+
+                use syn::__private::ToTokens;
+                let mut args = cond.to_token_stream().into_iter().collect::<Vec<_>>();
+                if let Some(message) = message {
+                    if let CExprKind::Literal(_, CLiteral::String(ref bytes, 1)) =
+                        self.ast_context[message].kind
+                    {
+                        // `assert!` treats its message as a format string
+                        let msg = String::from_utf8_lossy(bytes)
+                            .replace('{', "{{")
+                            .replace('}', "}}");
+                        args.push(TokenTree::Punct(Punct::new(',', Alone)));
+                        args.push(TokenTree::Literal(proc_macro2::Literal::string(&msg)));
+                    }
+                }
+
+                let assertion = mk().mac_expr(mk().mac(
+                    mk().path(vec!["assert"]),
+                    args.into_iter().collect::<TokenStream>(),
+                    MacroDelimiter::Paren(Default::default()),
+                ));
+                Ok(ConvertedDecl::Item(mk().span(span).const_item(
+                    "_",
+                    mk().tuple_ty(vec![]),
+                    assertion,
+                )))
             }
         }
     }
@@ -2342,6 +2479,7 @@ impl<'c> Translation<'c> {
 
                 let mut body_stmts = vec![];
                 for &(_, _, typ) in arguments {
+                    body_stmts.append(&mut self.evaluate_decayed_array_size(ctx, typ.ctype)?);
                     body_stmts.append(&mut self.compute_variable_array_sizes(ctx, typ.ctype)?);
                 }
 
@@ -2376,6 +2514,10 @@ impl<'c> Translation<'c> {
                         c_ast::Attribute::AlwaysInline => mk_.call_attr("inline", vec!["always"]),
                         c_ast::Attribute::Cold => mk_.single_attr("cold"),
                         c_ast::Attribute::NoInline => mk_.call_attr("inline", vec!["never"]),
+                        c_ast::Attribute::Weak if is_global && !is_inline => {
+                            self.use_feature("linkage");
+                            mk_.str_attr("linkage", "weak")
+                        }
                         _ => continue,
                     };
                 }
@@ -2406,9 +2548,17 @@ impl<'c> Translation<'c> {
                     // specifies internal linkage in all other cases due to name mangling by rustc.
                 }
 
-                Ok(ConvertedDecl::Item(
-                    mk_.span(span).unsafe_().fn_item(decl, block),
-                ))
+                let fn_item = mk_.span(span).unsafe_().fn_item(decl, block);
+
+                // Constructors run before `main` like static initializers do
+                if attrs.contains(&c_ast::Attribute::Constructor) {
+                    let static_name = self.renamer.borrow_mut().pick_name_root("CONSTRUCTOR");
+                    let static_item = mk_init_array_static(&static_name, new_name);
+
+                    return Ok(ConvertedDecl::Items(vec![fn_item, static_item]));
+                }
+
+                Ok(ConvertedDecl::Item(fn_item))
             } else {
                 // Translating an extern function declaration
 
@@ -2650,7 +2800,19 @@ impl<'c> Translation<'c> {
                     })?;
                 let ConvertedVariable { ty, mutbl: _, init } =
                     self.convert_variable(ctx.static_(), initializer, typ)?;
-                let default_init = self.implicit_default_expr(typ.ctype, true)?.to_expr();
+                let mut default_init = self.implicit_default_expr(typ.ctype, true)?.to_expr();
+                let ty = match self.variable_alignment(decl_id) {
+                    Some(alignment) => {
+                        let (wrapper, wrapper_name) =
+                            self.aligned_variable_wrapper(&ident2, alignment, ty);
+                        default_init =
+                            mk().call_expr(mk().path_expr(vec![&wrapper_name]), vec![default_init]);
+                        self.items.borrow_mut()[&self.main_file].add_item(wrapper);
+
+                        mk().path_ty(vec![wrapper_name])
+                    }
+                    None => ty,
+                };
                 let comment = String::from("// Initialized in run_static_initializers");
                 let span = self
                     .comment_store
@@ -2666,7 +2828,7 @@ impl<'c> Translation<'c> {
                 init.set_unsafe();
                 let mut init = init.to_expr();
 
-                self.add_static_initializer_to_section(&ident2, typ, &mut init)?;
+                self.add_static_initializer_to_section(decl_id, &ident2, typ, &mut init)?;
                 self.items.borrow_mut()[&self.main_file].add_item(static_item);
 
                 return Ok(cfg::DeclStmtInfo::empty());
@@ -2733,25 +2895,51 @@ impl<'c> Translation<'c> {
                     zeroed.to_pure_expr()
                 }
                 .expect("Expected decl initializer to not have any statements");
+
+                // Variables with an alignment are stored in an aligned wrapper
+                let mut lhs = mk().ident_expr(&rust_name);
+                let mut decl_stmts = vec![];
+                let (ty, zeroed, local_init) = match self.variable_alignment(decl_id) {
+                    Some(alignment) => {
+                        let (wrapper, wrapper_name) =
+                            self.aligned_variable_wrapper(&rust_name, alignment, ty);
+                        let wrap =
+                            |expr| mk().call_expr(mk().path_expr(vec![&wrapper_name]), vec![expr]);
+
+                        lhs = mk().anon_field_expr(lhs, 0);
+                        decl_stmts.push(mk().item_stmt(wrapper));
+
+                        (
+                            mk().path_ty(vec![&wrapper_name]),
+                            wrap(zeroed),
+                            wrap(init.clone()),
+                        )
+                    }
+                    None => (ty, zeroed, init.clone()),
+                };
+
                 let pat_mut = mk().set_mutbl("mut").ident_pat(rust_name.clone());
                 let local_mut = mk().local(pat_mut, Some(ty.clone()), Some(zeroed));
                 if has_self_reference {
-                    let assign = mk().assign_expr(mk().ident_expr(rust_name), init);
+                    let assign = mk().assign_expr(lhs, init);
 
                     let mut assign_stmts = stmts.clone();
                     assign_stmts.push(mk().semi_stmt(assign.clone()));
 
-                    let mut decl_and_assign = vec![mk().local_stmt(Box::new(local_mut.clone()))];
+                    let mut decl_and_assign = decl_stmts.clone();
+                    decl_and_assign.push(mk().local_stmt(Box::new(local_mut.clone())));
                     decl_and_assign.append(&mut stmts);
                     decl_and_assign.push(mk().expr_stmt(assign));
 
+                    decl_stmts.push(mk().local_stmt(Box::new(local_mut)));
+
                     Ok(cfg::DeclStmtInfo::new(
-                        vec![mk().local_stmt(Box::new(local_mut))],
+                        decl_stmts,
                         assign_stmts,
                         decl_and_assign,
                     ))
                 } else {
-                    let pat = mk().set_mutbl(mutbl).ident_pat(rust_name.clone());
+                    let pat = mk().set_mutbl(mutbl).ident_pat(rust_name);
 
                     let type_annotation = if self.tcfg.reduce_type_annotations
                         && !self.should_assign_type_annotation(typ.ctype, initializer)
@@ -2761,17 +2949,20 @@ impl<'c> Translation<'c> {
                         Some(ty)
                     };
 
-                    let local = mk().local(pat, type_annotation, Some(init.clone()));
-                    let assign = mk().assign_expr(mk().ident_expr(rust_name), init);
+                    let local = mk().local(pat, type_annotation, Some(local_init));
+                    let assign = mk().assign_expr(lhs, init);
 
                     let mut assign_stmts = stmts.clone();
                     assign_stmts.push(mk().semi_stmt(assign));
 
-                    let mut decl_and_assign = stmts;
+                    let mut decl_and_assign = decl_stmts.clone();
+                    decl_and_assign.append(&mut stmts);
                     decl_and_assign.push(mk().local_stmt(Box::new(local)));
 
+                    decl_stmts.push(mk().local_stmt(Box::new(local_mut)));
+
                     Ok(cfg::DeclStmtInfo::new(
-                        vec![mk().local_stmt(Box::new(local_mut))],
+                        decl_stmts,
                         assign_stmts,
                         decl_and_assign,
                     ))
@@ -2891,7 +3082,9 @@ impl<'c> Translation<'c> {
             // so type annotation is need for 0-init ints and floats at the moment, but
             // they could be simplified in favor of type suffixes
             Bool | Char | SChar | Short | Int | Long | LongLong | UChar | UShort | UInt | ULong
-            | ULongLong | LongDouble | Int128 | UInt128 => initializer.is_none(),
+            | ULongLong | LongDouble | Int128 | UInt128 | BitInt(_) | UBitInt(_) => {
+                initializer.is_none()
+            }
             Float | Double => initializer.is_none(),
             Struct(_) | Union(_) | Enum(_) => false,
             Function(..) => unreachable!("Can't have a function directly as a type"),
@@ -3123,6 +3316,29 @@ impl<'c> Translation<'c> {
         Ok(stmts)
     }
 
+    /// Array parameters decay to pointers, which drops the size expression of their outermost
+    /// variable length array from their type. C still evaluates it on entry to the function, so
+    /// this generates statements evaluating it when it has side effects, as in `int a[n++]`.
+    fn evaluate_decayed_array_size(
+        &self,
+        ctx: ExprContext,
+        type_id: CTypeId,
+    ) -> TranslationResult<Vec<Stmt>> {
+        let original = match self.ast_context[type_id].kind {
+            CTypeKind::Decayed(_, original) => original,
+            _ => return Ok(vec![]),
+        };
+
+        match self.ast_context.resolve_type(original).kind {
+            CTypeKind::VariableArray(_, Some(expr_id))
+                if !self.ast_context.is_expr_pure(expr_id) =>
+            {
+                Ok(self.convert_expr(ctx.unused(), expr_id)?.into_stmts())
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
     // Compute the size of a type
     // Rust type: usize
     pub fn compute_size_of_type(
@@ -3323,6 +3539,11 @@ impl<'c> Translation<'c> {
 
                 let mut val = mk().path_expr(vec![rustname]);
 
+                // Variables with an alignment are stored in an aligned wrapper
+                if self.variable_alignment(decl_id).is_some() {
+                    val = mk().anon_field_expr(val, 0);
+                }
+
                 // If the variable is volatile and used as something that isn't an LValue, this
                 // constitutes a volatile read.
                 if lrvalue.is_rvalue() && qual_ty.qualifiers.is_volatile {
@@ -3731,12 +3952,20 @@ impl<'c> Translation<'c> {
                     // callee is a declref
                     if matches!(self.ast_context[fexp].kind, CExprKind::DeclRef(..)) =>
                         {
-                            self.convert_expr(ctx.used(), fexp)?
+                            let callee = self.convert_expr(ctx.used(), fexp)?;
+
+                            match self.unprototyped_callee_tys(fexp, args.len())? {
+                                Some((source_ty, target_ty)) => callee.map(|callee| {
+                                    let fn_ptr = mk().cast_expr(callee, source_ty.clone());
+                                    transmute_expr(source_ty, target_ty, fn_ptr)
+                                }),
+                                None => callee,
+                            }
                         }
 
                     // Builtin function call
                     CExprKind::ImplicitCast(_, fexp, CastKind::BuiltinFnToFnPtr, _, _) => {
-                        return self.convert_builtin(ctx, fexp, args);
+                        return self.convert_builtin(ctx, expr_id, fexp, args);
                     }
 
                     // Function pointer call
@@ -4793,6 +5022,135 @@ impl<'c> Translation<'c> {
         result
     }
 
+    /// A function declared without a prototype, such as `int f();`, is declared
+    /// without parameters in Rust, so calls passing it arguments need to go
+    /// through a function pointer taking them. Returns the types to transmute
+    /// the function between, or `None` if the call can use it directly.
+    fn unprototyped_callee_tys(
+        &self,
+        callee: CExprId,
+        arg_count: usize,
+    ) -> TranslationResult<Option<(Box<Type>, Box<Type>)>> {
+        let decl_id = match self.ast_context[callee].kind {
+            CExprKind::DeclRef(_, decl_id, _) => decl_id,
+            _ => return Ok(None),
+        };
+        let typ = match self.ast_context[decl_id].kind {
+            CDeclKind::Function {
+                has_prototype: false,
+                typ,
+                ref parameters,
+                ..
+            } if parameters.len() != arg_count => typ,
+            _ => return Ok(None),
+        };
+        let source_ty = self.convert_type(typ)?;
+        let target_ty = match *source_ty.clone() {
+            Type::BareFn(mut bare_fn) => {
+                bare_fn.inputs = (0..arg_count)
+                    .map(|_| mk().bare_arg(mk().infer_ty(), None::<Box<Ident>>))
+                    .collect();
+                Box::new(Type::BareFn(bare_fn))
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some((source_ty, target_ty)))
+    }
+
+    /// The alignment a variable definition specifies with `_Alignas` or the aligned
+    /// attribute. Rust can only align types, so such a variable is stored in the
+    /// tuple struct made by `aligned_variable_wrapper` and referred to through its field.
+    fn variable_alignment(&self, decl_id: CDeclId) -> Option<u64> {
+        match self.ast_context.index(decl_id).kind {
+            CDeclKind::Variable {
+                is_defn: true,
+                manual_alignment,
+                ..
+            } => manual_alignment,
+            _ => None,
+        }
+    }
+
+    /// Make a tuple struct with the given alignment wrapping a variable's type,
+    /// returning it along with its name
+    fn aligned_variable_wrapper(
+        &self,
+        var_name: &str,
+        alignment: u64,
+        ty: Box<Type>,
+    ) -> (Box<Item>, String) {
+        let name = self
+            .renamer
+            .borrow_mut()
+            .pick_name_root(&format!("{}_Aligned", var_name));
+        let reprs = vec![
+            simple_metaitem("C"),
+            int_arg_metaitem("align", alignment as u128),
+        ];
+        let repr_attr = mk().meta_list("repr", reprs);
+        let field = mk().pub_().enum_field(ty);
+        let wrapper = mk()
+            .pub_()
+            .meta_item_attr(AttrStyle::Outer, repr_attr)
+            .struct_item(name.clone(), vec![field], true);
+
+        (wrapper, name)
+    }
+
+    /// Document the item a decl was converted to with the decl's C doc comment
+    fn add_doc_comment(&self, decl_id: CDeclId, converted_decl: &mut ConvertedDecl) {
+        let doc_comment = match self.ast_context.doc_comments.get(&decl_id) {
+            Some(doc_comment) => doc_comment,
+            None => return,
+        };
+
+        use ConvertedDecl::*;
+        let attrs = match converted_decl {
+            Item(item) => item_attrs(item),
+            ForeignItem(item) => foreign_item_attrs(item),
+            Items(items) => items.first_mut().and_then(|item| item_attrs(item)),
+            NoItem => None,
+        };
+        if let Some(attrs) = attrs {
+            add_doc_attrs(attrs, doc_comment);
+        }
+    }
+
+    /// Add the annotations attached to a decl by `C2RUST_EXPORT_ANNOTATIONS`
+    /// which are Rust attributes, such as `#[cold]`, to the item it was
+    /// converted to. Any other annotations are left for downstream tools to
+    /// find in the `TypedAstContext`.
+    fn add_annotations(&self, decl_id: CDeclId, converted_decl: &mut ConvertedDecl) {
+        let annotations = match self.ast_context.annotations.get(&decl_id) {
+            Some(annotations) => annotations,
+            None => return,
+        };
+
+        use ConvertedDecl::*;
+        let attrs = match converted_decl {
+            Item(item) => item_attrs(item),
+            ForeignItem(item) => foreign_item_attrs(item),
+            Items(items) => items.first_mut().and_then(|item| item_attrs(item)),
+            NoItem => None,
+        };
+        let attrs = match attrs {
+            Some(attrs) => attrs,
+            None => return,
+        };
+
+        for annotation in annotations {
+            if !annotation.starts_with("#[") {
+                continue;
+            }
+
+            match syn::Attribute::parse_outer.parse_str(annotation) {
+                Ok(parsed) => attrs.extend(parsed),
+                Err(e) => warn!("ignoring malformed annotation `{}`: {}", annotation, e),
+            }
+        }
+    }
+
     /// If we're trying to organize item definitions into submodules, add them to a module
     /// scoped "namespace" if we have a path available, otherwise add it to the global "namespace"
     fn insert_item(&self, mut item: Box<Item>, decl: &CDecl) {
@@ -4874,11 +5232,12 @@ impl<'c> Translation<'c> {
         match type_kind {
             // libc can be accessed from anywhere as of Rust 2019 by full path
             Void | Char | SChar | UChar | Short | UShort | Int | UInt | Long | ULong | LongLong
-            | ULongLong | Int128 | UInt128 | Half | BFloat16 | Float | Double | LongDouble => {}
+            | ULongLong | Int128 | UInt128 | BitInt(_) | UBitInt(_) | Half | BFloat16 | Float
+            | Double | LongDouble => {}
             // Bool uses the bool type, so no dependency on libc
             Bool => {}
             Paren(ctype)
-            | Decayed(ctype)
+            | Decayed(ctype, _)
             | IncompleteArray(ctype)
             | ConstantArray(ctype, _)
             | Elaborated(ctype)
@@ -96,6 +96,7 @@ impl<'a> Translation<'a> {
                 bitfield_width,
                 platform_bit_offset,
                 platform_type_bitwidth,
+                manual_alignment,
                 ..
             } = self.ast_context.index(*field_id).kind
             {
@@ -135,14 +136,21 @@ impl<'a> Translation<'a> {
                     }
                     None => {
                         // Hit non bitfield group so existing one is all set
-                        if let Some(field_group) = last_bitfield_group.take() {
-                            reorganized_fields.push(field_group);
-
-                            // Need to add padding first
-                            if (platform_bit_offset / 8) > next_byte_pos {
-                                let bytes = (platform_bit_offset / 8) - next_byte_pos;
-                                reorganized_fields.push(FieldType::Padding { bytes });
+                        let follows_bitfields = match last_bitfield_group.take() {
+                            Some(field_group) => {
+                                reorganized_fields.push(field_group);
+                                true
                             }
+                            None => false,
+                        };
+
+                        // Need to add padding first. Rust can't align individual fields,
+                        // so over-aligned ones are also placed at their offset this way
+                        if (follows_bitfields || manual_alignment.is_some())
+                            && (platform_bit_offset / 8) > next_byte_pos
+                        {
+                            let bytes = (platform_bit_offset / 8) - next_byte_pos;
+                            reorganized_fields.push(FieldType::Padding { bytes });
                         }
 
                         let mut use_inner_type = false;
@@ -190,6 +198,8 @@ impl<'a> Translation<'a> {
 
                         continue;
                     }
+                    // Clang records whether a plain `int` bitfield is signed in the
+                    // field's type, so the accessors' ty already follows its choice
                     Some(bw) => bw,
                 };
 
@@ -306,6 +316,11 @@ impl<'a> Translation<'a> {
             FieldType::Regular { is_va_list, .. } => *is_va_list,
             _ => false,
         });
+        // Padding before an over-aligned field may be all there is to reorganize,
+        // in which case the struct doesn't derive `BitfieldStruct`
+        let has_bitfield_groups = reorganized_fields
+            .iter()
+            .any(|field| matches!(field, FieldType::BitfieldGroup { .. }));
 
         let mut padding_count = 0;
         let mut next_padding_field = || {
@@ -357,14 +372,17 @@ impl<'a> Translation<'a> {
                         mk().lit_expr(mk().int_unsuffixed_lit(bytes.into())),
                     );
 
+                    let mut field = mk();
+
                     // Mark it with `#[bitfield(padding)]`
-                    let field_padding_inner = NestedMeta::Meta(mk().meta_path("padding"));
-                    let field_padding_inner = vec![mk().nested_meta_item(field_padding_inner)];
-                    let field_padding_outer = mk().meta_list("bitfield", field_padding_inner);
-                    let field = mk()
-                        .meta_item_attr(AttrStyle::Outer, field_padding_outer)
-                        .pub_()
-                        .struct_field(field_name, ty);
+                    if has_bitfield_groups {
+                        let field_padding_inner = NestedMeta::Meta(mk().meta_path("padding"));
+                        let field_padding_inner = vec![mk().nested_meta_item(field_padding_inner)];
+                        let field_padding_outer = mk().meta_list("bitfield", field_padding_inner);
+                        field = field.meta_item_attr(AttrStyle::Outer, field_padding_outer);
+                    }
+
+                    let field = field.pub_().struct_field(field_name, ty);
 
                     field_entries.push(field);
                 }
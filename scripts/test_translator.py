@@ -72,6 +72,15 @@ class CFile:
         self.translate_const_macros = "translate_const_macros" in flags
         self.reorganize_definitions = "reorganize_definitions" in flags
         self.emit_build_files = "emit_build_files" in flags
+        self.export_only = [flag[len("export_only="):] for flag in flags
+                            if flag.startswith("export_only=")]
+        # Annotation rules files are named relative to the C file
+        self.export_annotations = next(
+            (os.path.join(os.path.dirname(path), flag[len("export_annotations="):])
+             for flag in flags if flag.startswith("export_annotations=")),
+            None)
+        self.export_source_text = "export_source_text" in flags
+        self.save_stderr = "save_stderr" in flags
 
     def translate(self, cc_db: str, ld_lib_path: str, extra_args: List[str] = []) -> RustFile:
         extensionless_file, _ = os.path.splitext(self.path)
@@ -103,9 +112,25 @@ class CFile:
         args.append("--")
         args.extend(extra_args)
 
-        with pb.local.env(RUST_BACKTRACE='1', LD_LIBRARY_PATH=ld_lib_path):
+        env = dict(RUST_BACKTRACE='1', LD_LIBRARY_PATH=ld_lib_path)
+        if self.export_only:
+            env['C2RUST_EXPORT_ONLY'] = ",".join(sorted(self.export_only))
+        if self.export_annotations:
+            env['C2RUST_EXPORT_ANNOTATIONS'] = os.path.abspath(self.export_annotations)
+        if self.export_source_text:
+            env['C2RUST_EXPORT_SOURCE_TEXT'] = '1'
+
+        with pb.local.env(**env):
             # log the command in a format that's easy to re-run
             translation_cmd = "LD_LIBRARY_PATH=" + ld_lib_path + " \\\n"
+            if self.export_only:
+                translation_cmd += "C2RUST_EXPORT_ONLY=" + \
+                    env['C2RUST_EXPORT_ONLY'] + " \\\n"
+            if self.export_annotations:
+                translation_cmd += "C2RUST_EXPORT_ANNOTATIONS=" + \
+                    env['C2RUST_EXPORT_ANNOTATIONS'] + " \\\n"
+            if self.export_source_text:
+                translation_cmd += "C2RUST_EXPORT_SOURCE_TEXT=1 \\\n"
             translation_cmd += str(transpiler[args])
             logging.debug("translation command:\n %s", translation_cmd)
             retcode, stdout, stderr = (transpiler[args]).run(
@@ -117,6 +142,11 @@ class CFile:
         if retcode != 0:
             raise NonZeroReturn(stderr)
 
+        # Keep the diagnostics around for the test to include
+        if self.save_stderr:
+            with open(extensionless_file + ".stderr", 'w', encoding="utf-8") as file:
+                file.write(stderr)
+
         return RustFile(extensionless_file + ".rs")
 
 
@@ -433,6 +463,9 @@ class TestDirectory:
                 continue
 
             self.generated_files["rust_src"].append(translated_rust_file)
+            if c_file.save_stderr:
+                extensionless_c_file, _ = os.path.splitext(c_file.path)
+                self.generated_files["rust_src"].append(extensionless_c_file + ".stderr")
             if c_file.emit_build_files:
                 self.generated_files["rust_src"].append(self.full_path + "/src/Cargo.toml")
                 self.generated_files["rust_src"].append(self.full_path + "/src/build.rs")
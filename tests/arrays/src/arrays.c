@@ -1,4 +1,5 @@
 #include <stdlib.h>
+#include <uchar.h>
 
 static char simple[] = "mystring";
 static char *foo = "mystring";
@@ -82,6 +83,22 @@ void entry(const unsigned buffer_size, int buffer[const])
     buffer[i++] = foo[6];
     buffer[i++] = foo[7];
 
+    char16_t utf16[] = u"abc";
+    buffer[i++] = utf16[0];
+    buffer[i++] = utf16[1];
+    buffer[i++] = utf16[2];
+    buffer[i++] = utf16[3];
+    buffer[i++] = sizeof(utf16) / sizeof(utf16[0]);
+    buffer[i++] = sizeof(utf16[0]);
+
+    char32_t utf32[] = U"abc";
+    buffer[i++] = utf32[0];
+    buffer[i++] = utf32[1];
+    buffer[i++] = utf32[2];
+    buffer[i++] = utf32[3];
+    buffer[i++] = sizeof(utf32) / sizeof(utf32[0]);
+    buffer[i++] = sizeof(utf32[0]);
+
     // Test that we can get the address of the element past the end of the array
     char *past_end = &simple[sizeof(simple)];
     past_end = &foo[8];
@@ -0,0 +1,22 @@
+#define ROWS 3
+
+enum { COLS = 4 };
+
+// Each array's size is an integer constant expression clang evaluates
+// for us, so the translation gets a plain array length
+static int table[ROWS + COLS];
+
+void computed_sizes(int *out) {
+    char shifted[1 << 3];
+    long from_enum[COLS * 2 - 1];
+    int i = 0;
+
+    for (int j = 0; j < ROWS + COLS; j++) {
+        table[j] = j * j;
+    }
+
+    out[i++] = sizeof(table) / sizeof(table[0]);
+    out[i++] = sizeof(shifted);
+    out[i++] = sizeof(from_enum) / sizeof(from_enum[0]);
+    out[i++] = table[ROWS + COLS - 1];
+}
@@ -0,0 +1,29 @@
+// GNU range designators initialize every element from the start to the
+// end index inclusive
+static int global_ranges[10] = { [0 ... 3] = 1, [6 ... 8] = 2 };
+
+struct point {
+    int x, y;
+};
+
+void range_designators(int buffer[20]) {
+    int i = 0;
+
+    for (int j = 0; j < 10; j++) {
+        buffer[i++] = global_ranges[j];
+    }
+
+    // Later designators override elements of earlier ranges
+    int local_ranges[6] = { [1 ... 4] = 7, [2] = 3, [5 ... 5] = 9 };
+
+    for (int j = 0; j < 6; j++) {
+        buffer[i++] = local_ranges[j];
+    }
+
+    struct point points[2] = { [0 ... 1] = { .x = 4, .y = 5 } };
+
+    buffer[i++] = points[0].x;
+    buffer[i++] = points[0].y;
+    buffer[i++] = points[1].x;
+    buffer[i++] = points[1].y;
+}
@@ -1,6 +1,10 @@
 use crate::arrays::rust_entry;
+use crate::computed_sizes::rust_computed_sizes;
 use crate::incomplete_arrays::{rust_check_some_ints, rust_entry2, rust_test_sized_array};
-use crate::variable_arrays::{rust_alloca_arrays, rust_variable_arrays};
+use crate::range_designators::rust_range_designators;
+use crate::variable_arrays::{
+    rust_alloca_arrays, rust_variable_array_param_size, rust_variable_arrays,
+};
 use libc::{c_int, c_uint};
 
 #[link(name = "test")]
@@ -15,6 +19,12 @@ extern "C" {
 
     fn alloca_arrays(_: *mut c_int);
 
+    fn variable_array_param_size(_: c_int) -> c_int;
+
+    fn range_designators(_: *mut c_int);
+
+    fn computed_sizes(_: *mut c_int);
+
     fn check_some_ints() -> bool;
 }
 
@@ -23,9 +33,11 @@ pub static SOME_INTS: [u32; 4] = [2, 0, 1, 8];
 #[no_mangle]
 pub static rust_SOME_INTS: [u32; 4] = [2, 0, 1, 8];
 
-const BUFFER_SIZE: usize = 49;
+const BUFFER_SIZE: usize = 61;
 const BUFFER_SIZE2: usize = 2;
-const BUFFER_SIZEV: usize = 88;
+const BUFFER_SIZEV: usize = 89;
+const BUFFER_SIZER: usize = 20;
+const BUFFER_SIZEC: usize = 4;
 
 pub fn test_sized_array_impls() {
     unsafe {
@@ -45,7 +57,7 @@ pub fn test_buffer() {
     let expected_buffer = [
         97, 98, 99, 0, 100, 101, 102, 1, 0, 97, 98, 99, 0, 97, 98, 99, 100, 97, 98, 99, 97, 98, 99,
         0, 0, 0, 0, 120, 0, 120, 0, 0, 120, 109, 121, 115, 116, 114, 105, 110, 103, 109, 121, 115,
-        116, 114, 105, 110, 103,
+        116, 114, 105, 110, 103, 97, 98, 99, 0, 4, 2, 97, 98, 99, 0, 4, 4,
     ];
 
     unsafe {
@@ -80,7 +92,7 @@ pub fn test_variable_arrays() {
         1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
         26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
         11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33,
-        34, 35, 36, 37, 38, 39, 40, 0, 3, 6, 9, 12, 15, 18, 21,
+        34, 35, 36, 37, 38, 39, 40, 0, 3, 6, 9, 12, 15, 18, 21, 84,
     ];
     unsafe {
         variable_arrays(buffer.as_mut_ptr());
@@ -100,7 +112,7 @@ pub fn test_alloca_arrays() {
         1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
         26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
         11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33,
-        34, 35, 36, 37, 38, 39, 40, 0, 3, 6, 9, 12, 15, 18, 21,
+        34, 35, 36, 37, 38, 39, 40, 0, 3, 6, 9, 12, 15, 18, 21, 84,
     ];
     unsafe {
         alloca_arrays(buffer.as_mut_ptr());
@@ -112,3 +124,50 @@ pub fn test_alloca_arrays() {
         assert_eq!(buffer[index], rust_buffer[index], "index: {}", index);
     }
 }
+
+pub fn test_range_designators() {
+    let mut buffer = [0; BUFFER_SIZER];
+    let mut rust_buffer = [0; BUFFER_SIZER];
+    let expected_buffer = [1, 1, 1, 1, 0, 0, 2, 2, 2, 0, 0, 7, 3, 7, 7, 9, 4, 5, 4, 5];
+
+    unsafe {
+        range_designators(buffer.as_mut_ptr());
+        rust_range_designators(rust_buffer.as_mut_ptr());
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+}
+
+pub fn test_computed_sizes() {
+    let mut buffer = [0; BUFFER_SIZEC];
+    let mut rust_buffer = [0; BUFFER_SIZEC];
+    let expected_buffer = [7, 8, 7, 36];
+
+    unsafe {
+        computed_sizes(buffer.as_mut_ptr());
+        rust_computed_sizes(rust_buffer.as_mut_ptr());
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+
+    // The evaluated sizes are used as the array lengths
+    let src = include_str!("computed_sizes.rs");
+
+    assert!(src.contains("[libc::c_int; 7]"));
+    assert!(src.contains("[libc::c_char; 8]"));
+    assert!(src.contains("[libc::c_long; 7]"));
+}
+
+pub fn test_variable_array_param_size() {
+    unsafe {
+        assert_eq!(variable_array_param_size(4), 105);
+        assert_eq!(rust_variable_array_param_size(4), 105);
+    }
+
+    // `a` decays to a pointer, but its size expression is still translated
+    let src = include_str!("variable_arrays.rs");
+
+    assert!(src.contains("n += 1;"));
+}
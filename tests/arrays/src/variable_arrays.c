@@ -32,6 +32,31 @@ void use_arrays2(const int n, const int m, int a[const][n*n][m]) {
 
 }
 
+static int sum_variable_array(int n, int a[n]) {
+
+        int sum = 0;
+
+        for (int i = 0; i < n; i++) {
+                sum += a[i];
+        }
+
+        return sum;
+}
+
+/* The parameter decays to a pointer, but its size expression is still
+ * evaluated on entry */
+static int decayed_size_effect(int n, int a[n++]) {
+
+        return n + a[0];
+}
+
+int variable_array_param_size(int n) {
+
+        int a[1] = { 100 };
+
+        return decayed_size_effect(n, a);
+}
+
 void variable_arrays(int buf[const]) {
 
         int grid[4][4][5];
@@ -57,6 +82,8 @@ void variable_arrays(int buf[const]) {
                 buf[counter++] = var1[i];
         }
 
+        buf[counter++] = sum_variable_array(n, var1);
+
         // Test that we can get the address of the element past the end of the
         // array
         int *past_end = &grid[4][4][5];
@@ -89,6 +116,8 @@ void alloca_arrays(int buf[const]) {
                 buf[counter++] = var1[i];
         }
 
+        buf[counter++] = sum_variable_array(n, var1);
+
         // Test that we can get the address of the element past the end of the
         // array
         int *past_end = &grid[4][4][5];
@@ -0,0 +1,19 @@
+int sum_clobbered(int a, int b) {
+    int out;
+    asm volatile("movl %1, %%edx\n\t"
+                 "addl %2, %%edx\n\t"
+                 "movl %%edx, %0"
+                 : "=r"(out)
+                 : "r"(a), "r"(b)
+                 : "edx", "cc");
+    return out;
+}
+
+int swap_halves(int x) {
+    int out;
+    asm("roll $16, %0"
+        : "=r"(out)
+        : "0"(x)
+        : "cc");
+    return out;
+}
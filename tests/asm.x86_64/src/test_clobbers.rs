@@ -0,0 +1,35 @@
+//! extern_crate_c2rust_asm_casts
+
+use crate::clobbers::{rust_sum_clobbered, rust_swap_halves};
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn sum_clobbered(_: c_int, _: c_int) -> c_int;
+    fn swap_halves(_: c_int) -> c_int;
+}
+
+pub fn test_clobbers() {
+    for (a, b) in [(0, 0), (3, 4), (-7, 12), (c_int::MAX, 1)] {
+        let expected = unsafe { sum_clobbered(a, b) };
+        let actual = unsafe { rust_sum_clobbered(a, b) };
+        assert_eq!(expected, actual, "sum_clobbered({}, {})", a, b);
+    }
+
+    for x in [0, 1, 0x1234_5678, -2] {
+        let expected = unsafe { swap_halves(x) };
+        let actual = unsafe { rust_swap_halves(x) };
+        assert_eq!(expected, actual, "swap_halves({:#x})", x);
+    }
+}
+
+pub fn test_clobbers_exported() {
+    let src = include_str!("clobbers.rs");
+
+    // The templates, constraints and clobbers survive into the `asm!` calls
+    assert_eq!(src.matches("asm!").count(), 2);
+    assert!(src.contains("roll $16"));
+    assert!(src.contains("\"dx\""));
+    // The `cc` clobbers drop `preserves_flags`, which neither call keeps
+    assert!(!src.contains("preserves_flags"));
+}
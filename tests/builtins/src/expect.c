@@ -0,0 +1,17 @@
+#define likely(x) __builtin_expect(!!(x), 1)
+#define unlikely(x) __builtin_expect(!!(x), 0)
+
+int clamp_expect(int x, int lo, int hi) {
+    if (unlikely(x < lo)) {
+        return lo;
+    }
+    if (unlikely(x > hi)) {
+        return hi;
+    }
+    return x;
+}
+
+long expect_value(long x) {
+    // The value of __builtin_expect is its first argument
+    return __builtin_expect(x * 2, 0) + likely(x);
+}
@@ -1,6 +1,7 @@
 //! feature_core_intrinsics
 
 use crate::atomics::{rust_atomics_entry, rust_new_atomics};
+use crate::expect::{rust_clamp_expect, rust_expect_value};
 use crate::math::{rust_ffs, rust_ffsl, rust_ffsll, rust_isfinite, rust_isinf_sign, rust_isnan};
 use crate::mem_x_fns::{rust_assume_aligned, rust_mem_x};
 use libc::{c_char, c_double, c_int, c_long, c_longlong, c_uint};
@@ -8,6 +9,8 @@ use libc::{c_char, c_double, c_int, c_long, c_longlong, c_uint};
 #[link(name = "test")]
 extern "C" {
     fn atomics_entry(_: c_uint, _: *mut c_int);
+    fn clamp_expect(_: c_int, _: c_int, _: c_int) -> c_int;
+    fn expect_value(_: c_long) -> c_long;
     fn new_atomics(_: c_uint, _: *mut c_int);
     fn mem_x(_: *const c_char, _: *mut c_char);
     fn ffs(_: c_int) -> c_int;
@@ -128,3 +131,23 @@ pub fn test_assume_aligned() {
         assert_eq!(rust_assume_aligned(null), null);
     }
 }
+
+pub fn test_expect() {
+    for x in -5..15 {
+        let clamped = unsafe { clamp_expect(x, 0, 10) };
+        let rust_clamped = unsafe { rust_clamp_expect(x, 0, 10) };
+
+        assert_eq!(clamped, rust_clamped);
+
+        let value = unsafe { expect_value(x as c_long) };
+        let rust_value = unsafe { rust_expect_value(x as c_long) };
+
+        assert_eq!(value, rust_value);
+    }
+
+    // Calls recognized as the `__builtin_expect` builtin are replaced by
+    // their first argument rather than declared as an extern function
+    let src = include_str!("expect.rs");
+
+    assert!(!src.contains("__builtin_expect"));
+}
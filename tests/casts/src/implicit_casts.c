@@ -0,0 +1,24 @@
+// The sums below only differ by whether the narrowing back to unsigned char
+// is an implicit conversion on assignment or an explicit cast, while both
+// rely on the operands being implicitly promoted to int first.
+void implicit_and_explicit_casts(unsigned buffer_size, int buffer[]) {
+    unsigned char a = 200;
+    unsigned char b = 100;
+    signed char c = -1;
+    unsigned char narrowed;
+
+    if (buffer_size < 5)
+        return;
+
+    // Integer promotions keep the sum from wrapping
+    buffer[0] = a + b;
+    // Explicit cast back to the narrow type wraps
+    buffer[1] = (unsigned char)(a + b);
+    // Implicit conversion on assignment wraps the same way
+    narrowed = a + b;
+    buffer[2] = narrowed;
+    // Promoting a signed char sign extends it
+    buffer[3] = c + 0;
+    // Whereas an explicit cast to unsigned char doesn't
+    buffer[4] = (unsigned char)c;
+}
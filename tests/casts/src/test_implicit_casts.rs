@@ -0,0 +1,24 @@
+use crate::implicit_casts::rust_implicit_and_explicit_casts;
+
+use libc::{c_int, c_uint};
+
+#[link(name = "test")]
+extern "C" {
+    fn implicit_and_explicit_casts(_: c_uint, _: *mut c_int);
+}
+
+const BUFFER_SIZE: usize = 5;
+
+pub fn test_implicit_and_explicit_casts() {
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut rust_buffer = [0; BUFFER_SIZE];
+    let expected_buffer = [300, 44, 44, -1, 255];
+
+    unsafe {
+        implicit_and_explicit_casts(BUFFER_SIZE as u32, buffer.as_mut_ptr());
+        rust_implicit_and_explicit_casts(BUFFER_SIZE as u32, rust_buffer.as_mut_ptr());
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+}
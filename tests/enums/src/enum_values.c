@@ -0,0 +1,29 @@
+#include <stddef.h>
+
+enum status {
+    STATUS_OK,                  // implicit, 0
+    STATUS_WARN = 4,            // explicit, leaving a gap
+    STATUS_NOTICE,              // implicit, follows the gap
+    STATUS_ERROR = -2,          // explicit and negative
+    STATUS_FATAL,               // implicit, -1
+    STATUS_ALIAS = STATUS_WARN, // duplicates an earlier value
+};
+
+// Doesn't fit in an int, so its underlying type is wider
+enum wide {
+    WIDE_SMALL = 1,
+    WIDE_BIG = 0x100000000,
+};
+
+void entry6(const unsigned int sz, int buffer[const]) {
+    int i = 0;
+
+    buffer[i++] = STATUS_OK;
+    buffer[i++] = STATUS_WARN;
+    buffer[i++] = STATUS_NOTICE;
+    buffer[i++] = STATUS_ERROR;
+    buffer[i++] = STATUS_FATAL;
+    buffer[i++] = STATUS_ALIAS;
+    buffer[i++] = sizeof(enum status);
+    buffer[i++] = sizeof(enum wide);
+}
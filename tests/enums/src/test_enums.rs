@@ -3,12 +3,17 @@ use crate::enum_as_int::{rust_entry, A, B, E};
 use crate::enum_duplicate::{e, rust_entry3};
 use crate::enum_fwd_decl::rust_foo;
 use crate::enum_ret::{rust_entry2, Color};
+use crate::enum_values::{
+    rust_entry6, status, wide, STATUS_ALIAS, STATUS_ERROR, STATUS_FATAL, STATUS_NOTICE, STATUS_OK,
+    STATUS_WARN, WIDE_BIG, WIDE_SMALL,
+};
 use crate::non_canonical_enum_def::{
     hrtimer_restart, rust_abc, HRTIMER_NORESTART, HRTIMER_RESTART,
 };
 use crate::top_enum::{rust_entry4, E as otherE};
 
 use libc::{c_int, c_uint};
+use std::mem::size_of;
 
 #[link(name = "test")]
 extern "C" {
@@ -21,6 +26,8 @@ extern "C" {
     fn entry4(_: c_uint, _: *mut c_int);
 
     fn entry5(_: c_uint, _: *mut c_int);
+
+    fn entry6(_: c_uint, _: *mut c_int);
 }
 
 const BUFFER_SIZE: usize = 10;
@@ -28,6 +35,7 @@ const BUFFER_SIZE2: usize = 7;
 const BUFFER_SIZE3: usize = 4;
 const BUFFER_SIZE4: usize = 1;
 const BUFFER_SIZE5: usize = 6;
+const BUFFER_SIZE6: usize = 8;
 
 pub fn test_variants() {
     assert_eq!(A as u32, 0);
@@ -103,3 +111,32 @@ pub fn test_buffer5() {
     assert_eq!(buffer, rust_buffer);
     assert_eq!(buffer, expected_buffer);
 }
+
+pub fn test_enum_values() {
+    assert_eq!(STATUS_OK, 0);
+    assert_eq!(STATUS_WARN, 4);
+    assert_eq!(STATUS_NOTICE, 5);
+    assert_eq!(STATUS_ERROR, -2);
+    assert_eq!(STATUS_FATAL, -1);
+    assert_eq!(STATUS_ALIAS, STATUS_WARN);
+    assert_eq!(WIDE_SMALL, 1);
+    assert_eq!(WIDE_BIG, 0x100000000);
+
+    // The underlying types were chosen by clang to fit every value
+    assert_eq!(size_of::<status>(), size_of::<c_int>());
+    assert_eq!(size_of::<wide>(), 8);
+}
+
+pub fn test_buffer6() {
+    let mut buffer = [0; BUFFER_SIZE6];
+    let mut rust_buffer = [0; BUFFER_SIZE6];
+    let expected_buffer = [0, 4, 5, -2, -1, 4, 4, 8];
+
+    unsafe {
+        entry6(BUFFER_SIZE6 as u32, buffer.as_mut_ptr());
+        rust_entry6(BUFFER_SIZE6 as u32, rust_buffer.as_mut_ptr());
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+}
@@ -0,0 +1,41 @@
+// Both functions use labels named `again` and `done`; each goto must still
+// be matched to the label in its own function
+
+// Counts the steps of the Collatz sequence from n, jumping backward to loop
+// and forward to finish
+int collatz_steps(int n) {
+    int steps = 0;
+
+again:
+    if (n <= 1)
+        goto done;
+    if (n % 2 == 0)
+        n /= 2;
+    else
+        n = 3 * n + 1;
+    steps++;
+    goto again;
+
+done:
+    return steps;
+}
+
+// Returns the index of the first negative value in xs, or -1
+int first_negative(const int *xs, int len) {
+    int i = 0;
+
+    if (len <= 0)
+        goto missing;
+
+again:
+    if (xs[i] < 0)
+        goto done;
+    if (++i < len)
+        goto again;
+
+missing:
+    return -1;
+
+done:
+    return i;
+}
@@ -0,0 +1,34 @@
+use crate::goto_labels::{rust_collatz_steps, rust_first_negative};
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn collatz_steps(_: c_int) -> c_int;
+
+    fn first_negative(_: *const c_int, _: c_int) -> c_int;
+}
+
+pub fn test_collatz_steps() {
+    for n in 0..64 {
+        let steps = unsafe { collatz_steps(n) };
+        let rust_steps = unsafe { rust_collatz_steps(n) };
+
+        assert_eq!(steps, rust_steps);
+    }
+
+    assert_eq!(unsafe { rust_collatz_steps(27) }, 111);
+}
+
+pub fn test_first_negative() {
+    let inputs: [&[c_int]; 4] = [&[], &[1, 2, 3], &[-1, 2], &[4, 5, -6, -7]];
+
+    for xs in inputs.iter() {
+        let len = xs.len() as c_int;
+        let index = unsafe { first_negative(xs.as_ptr(), len) };
+        let rust_index = unsafe { rust_first_negative(xs.as_ptr(), len) };
+
+        assert_eq!(index, rust_index);
+    }
+
+    assert_eq!(unsafe { rust_first_negative([4, 5, -6].as_ptr(), 3) }, 2);
+}
@@ -0,0 +1,18 @@
+// `_BitInt(N)` is C23, which clang accepts as an extension in earlier modes
+typedef _BitInt(16) int16_bit_t;
+typedef unsigned _BitInt(8) uint8_bit_t;
+
+unsigned bit_int_size(void) {
+    return sizeof(int16_bit_t);
+}
+
+int16_bit_t bit_int_add(int16_bit_t a, int16_bit_t b) {
+    return a + b;
+}
+
+// `_BitInt` operands aren't promoted to `int`, so this wraps at 8 bits
+uint8_bit_t bit_int_overflow(void) {
+    uint8_bit_t max = 255, one = 1;
+
+    return max + one;
+}
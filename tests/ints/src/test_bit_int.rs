@@ -0,0 +1,27 @@
+use crate::bit_int::{rust_bit_int_add, rust_bit_int_overflow, rust_bit_int_size};
+
+#[link(name = "test")]
+extern "C" {
+    fn bit_int_size() -> u32;
+
+    fn bit_int_add(_: i16, _: i16) -> i16;
+
+    fn bit_int_overflow() -> u8;
+}
+
+pub fn test_bit_int() {
+    // `_BitInt(N)` is exported with its width, which selects the Rust type
+    let src = include_str!("bit_int.rs");
+
+    assert!(src.contains("pub type int16_bit_t = i16;"));
+    assert!(src.contains("pub type uint8_bit_t = u8;"));
+
+    unsafe {
+        assert_eq!(bit_int_size(), 2);
+        assert_eq!(rust_bit_int_size(), 2);
+        assert_eq!(bit_int_add(-600, 200), -400);
+        assert_eq!(rust_bit_int_add(-600, 200), -400);
+        assert_eq!(bit_int_overflow(), 0);
+        assert_eq!(rust_bit_int_overflow(), 0);
+    }
+}
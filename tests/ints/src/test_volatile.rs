@@ -1,4 +1,4 @@
-use crate::volatile::rust_entry3;
+use crate::volatile::{rust_entry3, rust_read_const_volatile};
 use libc::{c_int, c_uint};
 
 #[link(name = "test")]
@@ -6,12 +6,12 @@ extern "C" {
     fn entry3(_: c_uint, _: *mut c_int);
 }
 
-const BUFFER_SIZE: usize = 9;
+const BUFFER_SIZE: usize = 11;
 
 pub fn test_buffer() {
     let mut buffer = [0; BUFFER_SIZE];
     let mut rust_buffer = [0; BUFFER_SIZE];
-    let expected_buffer = [5, 11, 5, 9, 99, 116, 101, 115, 116];
+    let expected_buffer = [5, 11, 5, 9, 99, 116, 101, 115, 116, 12, 12];
 
     unsafe {
         entry3(BUFFER_SIZE as u32, buffer.as_mut_ptr());
@@ -21,3 +21,11 @@ pub fn test_buffer() {
     assert_eq!(buffer, rust_buffer);
     assert_eq!(buffer, expected_buffer);
 }
+
+pub fn test_const_volatile_pointer() {
+    // A pointer to a const qualified pointee must not become `*mut`
+    let read: unsafe extern "C" fn(*const c_int) -> c_int = rust_read_const_volatile;
+    let value = 3;
+
+    assert_eq!(unsafe { read(&value) }, 3);
+}
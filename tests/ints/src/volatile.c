@@ -11,6 +11,10 @@ void mutate_buffer(volatile char *dest, volatile const char *src, int size) {
         *(dest++) = *(src++);
 }
 
+int read_const_volatile(const volatile int *p) {
+    return *p;
+}
+
 void entry3(const unsigned buffer_size, int buffer[])
 {
     if (buffer_size < 5) { return; }
@@ -42,6 +46,12 @@ void entry3(const unsigned buffer_size, int buffer[])
     buffer[6] = s.buffer[1];
     buffer[7] = s.buffer[2];
     buffer[8] = s.buffer[3];
+
+    // both qualifiers on the pointee
+    const volatile int cv = 12;
+    const volatile int *cvp = &cv;
+    buffer[9] = *cvp;
+    buffer[10] = read_const_volatile(cvp);
 }
 
 
@@ -0,0 +1,10 @@
+//! export_annotations=annotations.rules
+
+// annotations.rules marks this `#[cold]` and records who owns it
+int rarely_called(int x) {
+    return x * 3;
+}
+
+int often_called(int x) {
+    return x + rarely_called(x);
+}
@@ -0,0 +1,15 @@
+/// A point on the plane
+struct point {
+    int x;
+    int y;
+};
+
+/**
+ * Adds the coordinates of a point.
+ *
+ * Both coordinates count.
+ */
+int sum_point(struct point p) {
+    // Not documentation, so this stays a plain comment
+    return p.x + p.y;
+}
@@ -0,0 +1,35 @@
+//! export_only=export_only_entry
+
+struct pair {
+    int first;
+    int second;
+};
+
+// Only declared up front, so these canonical decls have no body or initializer
+static int scaled_diff(struct pair p);
+extern int diff_scale;
+
+static int pair_sum(struct pair p) {
+    return p.first + p.second;
+}
+
+void export_only_entry(unsigned buffer_size, int buffer[]) {
+    struct pair p = { 3, 4 };
+
+    if (buffer_size < 2)
+        return;
+
+    buffer[0] = pair_sum(p);
+    buffer[1] = scaled_diff(p);
+}
+
+int diff_scale = 2;
+
+static int scaled_diff(struct pair p) {
+    return (p.second - p.first) * diff_scale;
+}
+
+// Nothing reachable from export_only_entry refers to this, so it is pruned
+int export_only_unused(void) {
+    return 42;
+}
@@ -22,6 +22,14 @@ extern void inline __attribute__((gnu_inline)) gnu_inline_non_canonical_definiti
 void __attribute__((alias("inline_extern"))) aliased_fn(void);
 #endif // __APPLE__
 
+void abort(void);
+
+void __attribute__((noreturn)) noreturn_nonstatic(void) { abort(); }
+_Noreturn void c11_noreturn_nonstatic(void) { abort(); }
+
+int rust_constructed = 0;
+static void __attribute__((constructor)) constructor_static(void) { rust_constructed = 42; }
+
 void ensure_use(void) {
     always_inline_static();
     always_inline_nonstatic();
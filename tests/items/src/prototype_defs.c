@@ -0,0 +1,11 @@
+int add_unprototyped(int a, int b) {
+    return a + b;
+}
+
+int add_prototyped(int a, int b) {
+    return a + b;
+}
+
+int forty_two(void) {
+    return 42;
+}
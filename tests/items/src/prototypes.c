@@ -0,0 +1,8 @@
+// Defined in prototype_defs.c
+int add_unprototyped();
+int add_prototyped(int a, int b);
+int forty_two(void);
+
+int call_both(void) {
+    return add_unprototyped(1, 2) + add_prototyped(3, 4) + forty_two();
+}
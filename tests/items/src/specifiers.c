@@ -0,0 +1,9 @@
+static inline int twice(int x) {
+    return x * 2;
+}
+
+void copy_twice(unsigned n, int *restrict dst, const int *restrict src) {
+    for (register unsigned i = 0; i < n; i++) {
+        dst[i] = twice(src[i]);
+    }
+}
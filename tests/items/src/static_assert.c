@@ -0,0 +1,9 @@
+enum { BUFFER_LEN = 4 };
+
+_Static_assert(sizeof(int) >= 2, "int must be at least 16 bits");
+_Static_assert(BUFFER_LEN * 2 == 8, "{BUFFER_LEN} is doubled");
+
+int static_assert_sum(int a, int b) {
+    _Static_assert(sizeof(a) == sizeof(b), "operands must have the same size");
+    return a + b;
+}
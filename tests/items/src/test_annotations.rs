@@ -0,0 +1,26 @@
+use crate::annotations::{rust_often_called, rust_rarely_called};
+
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn rarely_called(_: c_int) -> c_int;
+
+    fn often_called(_: c_int) -> c_int;
+}
+
+pub fn test_annotations() {
+    let src = include_str!("annotations.rs");
+
+    // Only annotations which are Rust attributes are added to the item
+    assert!(src.contains("#[cold]\npub unsafe extern \"C\" fn rust_rarely_called"));
+    assert!(!src.contains("owner=io"));
+    assert_eq!(src.matches("#[cold]").count(), 1);
+
+    for x in 0..5 {
+        unsafe {
+            assert_eq!(rust_rarely_called(x), rarely_called(x));
+            assert_eq!(rust_often_called(x), often_called(x));
+        }
+    }
+}
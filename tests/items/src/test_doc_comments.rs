@@ -0,0 +1,27 @@
+use crate::doc_comments::{point, rust_sum_point};
+
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn sum_point(_: point) -> c_int;
+}
+
+pub fn test_doc_comments() {
+    let p = point { x: 3, y: 39 };
+
+    unsafe {
+        assert_eq!(sum_point(p), 42);
+        assert_eq!(rust_sum_point(p), 42);
+    }
+
+    // Doc comments become Rust doc comments on the items they document,
+    // without their C comment markers
+    let translated = include_str!("doc_comments.rs");
+
+    assert!(translated.contains("/// A point on the plane\n"));
+    assert!(translated
+        .contains("/// Adds the coordinates of a point.\n///\n/// Both coordinates count.\n"));
+    assert!(!translated.contains("/**"));
+    assert!(translated.contains("// Not documentation, so this stays a plain comment"));
+}
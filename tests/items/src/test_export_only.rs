@@ -0,0 +1,40 @@
+use crate::export_only::rust_export_only_entry;
+
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn export_only_entry(_: u32, _: *mut c_int);
+}
+
+// If `export_only_unused` had been translated as well, the glob import of the
+// translated module would make `rust_export_only_unused` ambiguous.
+use self::pruned::*;
+#[allow(unused_imports)]
+use crate::export_only::*;
+
+mod pruned {
+    pub fn rust_export_only_unused() -> i32 {
+        0
+    }
+}
+
+const BUFFER_SIZE: usize = 2;
+
+pub fn test_export_only_keeps_reachable() {
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut rust_buffer = [0; BUFFER_SIZE];
+    let expected_buffer = [7, 2];
+
+    unsafe {
+        export_only_entry(BUFFER_SIZE as u32, buffer.as_mut_ptr());
+        rust_export_only_entry(BUFFER_SIZE as u32, rust_buffer.as_mut_ptr());
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+}
+
+pub fn test_export_only_drops_unreachable() {
+    assert_eq!(rust_export_only_unused(), 0);
+}
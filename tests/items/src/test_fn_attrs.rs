@@ -1,4 +1,6 @@
-use crate::fn_attrs::{rust_ensure_use, rust_inline_extern, rust_noinline_nonstatic};
+use crate::fn_attrs::{
+    rust_constructed, rust_ensure_use, rust_inline_extern, rust_noinline_nonstatic,
+};
 
 pub fn test_fn_attrs() {
     // There's no way to directly test that a function is inlined or not
@@ -54,6 +56,20 @@ pub fn test_fn_attrs() {
         "#[inline]\nunsafe extern \"C\" fn rust_gnu_inline_non_canonical_definition_extern"
     ));
 
+    // void __attribute__((noreturn)) noreturn_nonstatic(void) { abort(); }
+    // _Noreturn void c11_noreturn_nonstatic(void) { abort(); }
+    assert!(src.contains("pub unsafe extern \"C\" fn rust_noreturn_nonstatic() -> !"));
+    assert!(src.contains("pub unsafe extern \"C\" fn rust_c11_noreturn_nonstatic() -> !"));
+
+    // static void __attribute__((constructor)) constructor_static(void) { rust_constructed = 42; }
+    assert!(src.contains("unsafe extern \"C\" fn rust_constructor_static()"));
+    assert!(
+        src.contains("#[used]\n#[cfg_attr(target_os = \"linux\", link_section = \".init_array\")]")
+    );
+    assert!(src.contains("= [rust_constructor_static];"));
+    // The constructor ran before the test did
+    assert_eq!(unsafe { rust_constructed }, 42);
+
     if cfg!(not(target_os = "macos")) {
         // aliased_fn is aliased to the inline_extern function
         assert!(src.contains(
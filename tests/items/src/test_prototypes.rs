@@ -0,0 +1,25 @@
+use crate::prototypes::rust_call_both;
+
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn call_both() -> c_int;
+}
+
+pub fn test_prototypes() {
+    let expected = unsafe { call_both() };
+
+    assert_eq!(unsafe { rust_call_both() }, expected);
+    assert_eq!(expected, 52);
+
+    // The function declared without a prototype is only called through a
+    // pointer taking the call's arguments, while the prototyped ones are
+    // called directly
+    let src = include_str!("prototypes.rs");
+
+    assert!(src.contains("fn add_unprototyped() -> libc::c_int;"));
+    assert!(src.contains("fn forty_two() -> libc::c_int;"));
+    assert!(src.contains("unsafe extern \"C\" fn(_, _) -> libc::c_int"));
+    assert!(src.contains("add_prototyped(3 as libc::c_int, 4 as libc::c_int)"));
+}
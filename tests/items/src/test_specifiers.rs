@@ -0,0 +1,37 @@
+use crate::specifiers::rust_copy_twice;
+
+use libc::{c_int, c_uint};
+
+#[link(name = "test")]
+extern "C" {
+    fn copy_twice(_: c_uint, _: *mut c_int, _: *const c_int);
+}
+
+const BUFFER_SIZE: usize = 4;
+
+pub fn test_specifiers() {
+    let src = [1, -2, 3, 40];
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut rust_buffer = [0; BUFFER_SIZE];
+    let expected_buffer = [2, -4, 6, 80];
+
+    unsafe {
+        copy_twice(BUFFER_SIZE as c_uint, buffer.as_mut_ptr(), src.as_ptr());
+        rust_copy_twice(
+            BUFFER_SIZE as c_uint,
+            rust_buffer.as_mut_ptr(),
+            src.as_ptr(),
+        );
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+
+    // `inline` carries over as an attribute, while the `restrict` qualifiers
+    // don't change the pointer types they're translated to
+    let translated = include_str!("specifiers.rs");
+
+    assert!(translated.contains("#[inline]\nunsafe extern \"C\" fn rust_twice"));
+    assert!(translated.contains("mut dst: *mut libc::c_int"));
+    assert!(translated.contains("mut src: *const libc::c_int"));
+}
@@ -0,0 +1,24 @@
+use crate::static_assert::rust_static_assert_sum;
+
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn static_assert_sum(_: c_int, _: c_int) -> c_int;
+}
+
+pub fn test_static_assert() {
+    let ret = unsafe { static_assert_sum(2, 3) };
+    let rust_ret = unsafe { rust_static_assert_sum(2, 3) };
+
+    assert_eq!(ret, 5);
+    assert_eq!(rust_ret, 5);
+
+    // The assertions only exist at compile time, so check the source itself
+    let src = include_str!("static_assert.rs");
+
+    assert!(src.contains("const _: () = assert!("));
+    assert!(src.contains("\"int must be at least 16 bits\""));
+    assert!(src.contains("\"{{BUFFER_LEN}} is doubled\""));
+    assert!(src.contains("\"operands must have the same size\""));
+}
@@ -0,0 +1,38 @@
+//! feature_c_variadic,
+
+use crate::va_sum::{rust_count_until_zero, rust_weighted_sum};
+
+use libc::{c_double, c_int, c_long};
+
+#[link(name = "test")]
+extern "C" {
+    fn weighted_sum(count: c_int, ...) -> c_double;
+
+    fn count_until_zero(first: c_int, ...) -> c_int;
+}
+
+pub fn test_weighted_sum() {
+    let src = include_str!("va_sum.rs");
+
+    // Both functions stay variadic and read their arguments with `arg`
+    assert_eq!(src.matches("...)").count(), 2);
+    assert_eq!(src.matches(".arg::<").count(), 3);
+
+    unsafe {
+        assert_eq!(weighted_sum(0), rust_weighted_sum(0));
+        assert_eq!(
+            weighted_sum(2, 3 as c_int, 0.5, -4 as c_int, 2.0),
+            rust_weighted_sum(2, 3 as c_int, 0.5, -4 as c_int, 2.0),
+        );
+    }
+}
+
+pub fn test_count_until_zero() {
+    unsafe {
+        assert_eq!(count_until_zero(0), rust_count_until_zero(0));
+        assert_eq!(
+            count_until_zero(1, 2 as c_long, 3 as c_long, 0 as c_long),
+            rust_count_until_zero(1, 2 as c_long, 3 as c_long, 0 as c_long),
+        );
+    }
+}
@@ -0,0 +1,31 @@
+#include <stdarg.h>
+
+// Sums `count` ints, each scaled by the `double` that follows it
+double weighted_sum(int count, ...) {
+    va_list ap;
+    double total = 0;
+
+    va_start(ap, count);
+    for (int i = 0; i < count; i++) {
+        int value = va_arg(ap, int);
+        double weight = va_arg(ap, double);
+        total += value * weight;
+    }
+    va_end(ap);
+
+    return total;
+}
+
+// Counts the longs before the terminating zero
+int count_until_zero(int first, ...) {
+    va_list ap;
+    int count = 0;
+
+    va_start(ap, first);
+    for (long x = first; x != 0; x = va_arg(ap, long)) {
+        count++;
+    }
+    va_end(ap);
+
+    return count;
+}
@@ -0,0 +1,36 @@
+// GNU case ranges, whose bounds are both inclusive
+int classify_char(int c) {
+    switch (c) {
+    case '0' ... '9':
+        return 1;
+    case 'a' ... 'z':
+    case 'A' ... 'Z':
+        return 2;
+    case ' ':
+        return 3;
+    default:
+        return 0;
+    }
+}
+
+int classify_signed(int x) {
+    int res = 0;
+
+    switch (x) {
+    case -10 ... -1:
+        res = -1;
+        break;
+    case 0:
+        break;
+    case 1 ... 10:
+        res = 1;
+        // Falls through into the next range
+    case 11 ... 20:
+        res += 10;
+        break;
+    default:
+        res = 100;
+    }
+
+    return res;
+}
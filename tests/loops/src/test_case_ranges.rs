@@ -0,0 +1,45 @@
+use crate::case_ranges::{rust_classify_char, rust_classify_signed};
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn classify_char(_: c_int) -> c_int;
+
+    fn classify_signed(_: c_int) -> c_int;
+}
+
+pub fn test_case_ranges() {
+    for c in 0..128 {
+        let class = unsafe { classify_char(c) };
+        let rust_class = unsafe { rust_classify_char(c) };
+
+        assert_eq!(class, rust_class);
+    }
+
+    for x in -15..25 {
+        let class = unsafe { classify_signed(x) };
+        let rust_class = unsafe { rust_classify_signed(x) };
+
+        assert_eq!(class, rust_class);
+    }
+
+    // Both bounds of each range are included
+    let bounds = [
+        (-10, -1),
+        (-1, -1),
+        (1, 11),
+        (10, 11),
+        (11, 10),
+        (20, 10),
+        (21, 100),
+    ];
+
+    for &(x, class) in bounds.iter() {
+        assert_eq!(unsafe { rust_classify_signed(x) }, class);
+    }
+
+    // The ranges are translated into range patterns
+    let src = include_str!("case_ranges.rs");
+
+    assert!(src.contains("..="));
+}
@@ -0,0 +1,28 @@
+//! save_stderr
+
+// Comments are placed by where they're spelled, even though the `#line`
+// directives below make the functions claim to come before this one
+
+int line_first(int x) {
+    // first body comment
+    return x + 1;
+}
+
+#line 1 "generated.c"
+int line_second(int x) {
+    // second body comment
+    return x * 2;
+}
+
+#line 1 "generated.c"
+int line_third(int x) {
+    // third body comment
+    return line_first(line_second(x));
+}
+
+// `__builtin_parity` isn't translated, so this reports a diagnostic at the
+// presumed location of the call
+#line 1 "generated.c"
+int line_parity(unsigned x) {
+    return __builtin_parity(x);
+}
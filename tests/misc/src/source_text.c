@@ -0,0 +1,12 @@
+//! export_source_text, save_stderr
+
+int source_text(int x, int y) {
+    int a = (x + y) * 2;
+    int b = x > y ? x - y : y - x;
+    return a + (b << 1);
+}
+
+// `__builtin_parity` isn't translated, so the error quotes its spelling
+int source_text_parity(int x, int y) {
+    return __builtin_parity((x + y) * 2);
+}
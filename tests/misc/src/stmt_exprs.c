@@ -0,0 +1,43 @@
+#define MAX(a, b) ({         \
+    __typeof__(a) _a = (a);  \
+    __typeof__(b) _b = (b);  \
+    _a > _b ? _a : _b;       \
+})
+
+#define SWAP(x, y) ({              \
+    __typeof__(x) _tmp = (x);      \
+    (x) = (y);                     \
+    (y) = _tmp;                    \
+})
+
+int max3(int a, int b, int c) {
+    return MAX(a, MAX(b, c));
+}
+
+int swap_diff(int x, int y) {
+    // A statement expression whose value is discarded
+    SWAP(x, y);
+
+    return x - y;
+}
+
+int sum_to(int n) {
+    int total = ({
+        int acc = 0;
+
+        for (int i = 1; i <= n; i++) {
+            acc += i;
+        }
+
+        acc;
+    });
+
+    return total;
+}
+
+int empty_then_value(int n) {
+    // The value of a statement expression is that of its last statement
+    ({ ; });
+
+    return ({ n * 2; }) + ({ int m = n; m + 1; });
+}
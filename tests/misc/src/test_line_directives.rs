@@ -0,0 +1,48 @@
+use crate::line_directives::{rust_line_first, rust_line_second, rust_line_third};
+
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn line_first(_: c_int) -> c_int;
+
+    fn line_second(_: c_int) -> c_int;
+
+    fn line_third(_: c_int) -> c_int;
+}
+
+pub fn test_line_directives() {
+    let src = include_str!("line_directives.rs");
+
+    // Each comment stays in the function it's written in
+    let positions: Vec<_> = [
+        "fn rust_line_first",
+        "first body comment",
+        "fn rust_line_second",
+        "second body comment",
+        "fn rust_line_third",
+        "third body comment",
+    ]
+    .iter()
+    .map(|needle| {
+        src.find(needle)
+            .unwrap_or_else(|| panic!("missing {}", needle))
+    })
+    .collect();
+
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // Diagnostics point where the `#line` directive says the call is
+    let stderr = include_str!("line_directives.stderr");
+
+    assert!(stderr.contains("Failed to translate line_parity"));
+    assert!(stderr.contains("generated.c:2:12"));
+
+    for x in [-3, 0, 5] {
+        unsafe {
+            assert_eq!(line_first(x), rust_line_first(x));
+            assert_eq!(line_second(x), rust_line_second(x));
+            assert_eq!(line_third(x), rust_line_third(x));
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::source_text::rust_source_text;
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn source_text(_: c_int, _: c_int) -> c_int;
+}
+
+pub fn test_source_text() {
+    for (x, y) in [(0, 0), (3, 7), (7, 3), (-5, 12)] {
+        let expected = unsafe { source_text(x, y) };
+        let actual = unsafe { rust_source_text(x, y) };
+        assert_eq!(expected, actual, "source_text({}, {})", x, y);
+    }
+
+    let stderr = include_str!("source_text.stderr");
+
+    assert!(stderr.contains("in `__builtin_parity((x + y) * 2)`"));
+}
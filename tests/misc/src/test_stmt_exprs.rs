@@ -0,0 +1,46 @@
+use crate::stmt_exprs::{rust_empty_then_value, rust_max3, rust_sum_to, rust_swap_diff};
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn max3(_: c_int, _: c_int, _: c_int) -> c_int;
+
+    fn swap_diff(_: c_int, _: c_int) -> c_int;
+
+    fn sum_to(_: c_int) -> c_int;
+
+    fn empty_then_value(_: c_int) -> c_int;
+}
+
+pub fn test_stmt_exprs() {
+    for &(a, b, c) in &[(1, 2, 3), (3, 2, 1), (-5, -7, -6), (4, 4, 4)] {
+        let c_max = unsafe { max3(a, b, c) };
+        let rust_max = unsafe { rust_max3(a, b, c) };
+
+        assert_eq!(c_max, rust_max);
+    }
+
+    for &(x, y) in &[(1, 2), (10, -3), (0, 0)] {
+        let c_diff = unsafe { swap_diff(x, y) };
+        let rust_diff = unsafe { rust_swap_diff(x, y) };
+
+        assert_eq!(c_diff, rust_diff);
+    }
+
+    for n in 0..10 {
+        let c_sum = unsafe { sum_to(n) };
+        let rust_sum = unsafe { rust_sum_to(n) };
+        let c_value = unsafe { empty_then_value(n) };
+        let rust_value = unsafe { rust_empty_then_value(n) };
+
+        assert_eq!(c_sum, rust_sum);
+        assert_eq!(c_value, rust_value);
+    }
+
+    // The locals declared within the statement expressions were exported
+    // along with their result expressions
+    let src = include_str!("stmt_exprs.rs");
+
+    assert!(src.contains("let mut _a: libc::c_int"));
+    assert!(src.contains("let mut acc: libc::c_int = 0"));
+}
@@ -0,0 +1,25 @@
+use crate::typedef_chains::{fixed_meters, meters, rust_total_distance};
+
+use libc::c_uint;
+
+#[link(name = "test")]
+extern "C" {
+    fn total_distance(_: *const c_uint, _: c_uint) -> c_uint;
+}
+
+pub fn test_typedef_chains() {
+    let src = include_str!("typedef_chains.rs");
+
+    // Declarations keep the typedefs they were written with
+    assert!(src.contains("pub type meters = distance;"));
+    assert!(src.contains("pub type fixed_meters_ptr = *const fixed_meters;"));
+
+    // While the typedefs stay interchangeable with the type they name
+    let legs: [fixed_meters; 3] = [100, 250, 5];
+    let expected: meters = unsafe { total_distance(legs.as_ptr(), legs.len() as c_uint) };
+    let actual = unsafe { rust_total_distance(legs.as_ptr(), legs.len() as c_uint) };
+    let _: c_uint = actual;
+
+    assert_eq!(expected, actual);
+    assert_eq!(actual, 42550);
+}
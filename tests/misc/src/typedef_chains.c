@@ -0,0 +1,16 @@
+typedef unsigned int distance;
+typedef distance meters;
+typedef const meters fixed_meters;
+typedef fixed_meters *fixed_meters_ptr;
+
+static fixed_meters marathon = 42195;
+
+meters total_distance(fixed_meters_ptr legs, unsigned int count) {
+    meters total = 0;
+
+    for (unsigned int i = 0; i < count; i++) {
+        total += legs[i];
+    }
+
+    return total + marathon;
+}
@@ -0,0 +1,11 @@
+// The C11 spelling of `__thread`
+static _Thread_local int tls_counter = 5;
+
+int bump_tls_counter(void) {
+    static _Thread_local int calls;
+
+    calls += 1;
+    tls_counter += calls;
+
+    return tls_counter;
+}
@@ -0,0 +1,15 @@
+#ifndef __APPLE__
+
+// Bare `.init` holds the code run by `_init`, so data placed there would be
+// executed at startup; use an `.init.*` subsection as firmware/kernel code does
+int __attribute__((section(".init.data"), used)) init_word = 7;
+
+int __attribute__((weak)) weak_word = 3;
+
+__attribute__((visibility("hidden"))) int hidden_word = 5;
+
+int __attribute__((weak)) weak_fn(void) {
+    return weak_word + init_word + hidden_word;
+}
+
+#endif // __APPLE__
@@ -0,0 +1,32 @@
+//! feature_thread_local
+
+use crate::c11_thread_locals::rust_bump_tls_counter;
+use libc::c_int;
+use std::thread;
+
+#[link(name = "test")]
+extern "C" {
+    fn bump_tls_counter() -> c_int;
+}
+
+fn run_test() {
+    // Both counters start over on each new thread
+    for expected in &[6, 8, 11] {
+        unsafe {
+            assert_eq!(bump_tls_counter(), *expected);
+            assert_eq!(rust_bump_tls_counter(), *expected);
+        }
+    }
+}
+
+pub fn test_c11_thread_locals() {
+    let src = include_str!("c11_thread_locals.rs");
+
+    // `_Thread_local` was exported as thread-local storage for both the file
+    // scope and the function scope static
+    assert!(src.contains("#[thread_local]\nstatic mut tls_counter: libc::c_int"));
+    assert_eq!(src.matches("#[thread_local]").count(), 2);
+
+    thread::spawn(run_test).join().unwrap();
+    thread::spawn(run_test).join().unwrap();
+}
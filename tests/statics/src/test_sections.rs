@@ -51,3 +51,29 @@ pub fn test_sectioned_used_static() {
         assert!(src.contains("extern \"C\" {\n    #[link_name = \"no_attrs\"]\n    static mut rust_aliased_static: libc::c_int;"))
     }
 }
+
+pub fn test_linker_attrs() {
+    if cfg!(not(target_os = "macos")) {
+        let src = include_str!("linker_attrs.rs");
+
+        let lines: Vec<&str> = src.lines().collect();
+        let attrs_before = |item: &str| {
+            let pos = lines
+                .iter()
+                .position(|&x| x.starts_with(item))
+                .expect("Did not find expected item in source");
+            &lines[pos.saturating_sub(3)..pos]
+        };
+
+        // The ordering of these attributes is not stable between LLVM versions
+        let init_word = attrs_before("pub static mut rust_init_word: libc::c_int");
+        assert!(init_word.contains(&"#[used]"));
+        assert!(init_word.contains(&"#[link_section = \".init.data\"]"));
+
+        let weak_word = attrs_before("pub static mut rust_weak_word: libc::c_int");
+        assert!(weak_word.contains(&"#[linkage = \"weak\"]"));
+
+        let weak_fn = attrs_before("pub unsafe extern \"C\" fn rust_weak_fn()");
+        assert!(weak_fn.contains(&"#[linkage = \"weak\"]"));
+    }
+}
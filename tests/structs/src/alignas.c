@@ -0,0 +1,34 @@
+#include <stdalign.h>
+#include <stddef.h>
+#include <stdint.h>
+
+_Alignas(16) int aligned_global = 7;
+
+struct aligned_field {
+    char tag;
+    _Alignas(16) int value;
+};
+
+size_t alignment_of_aligned_field(void) {
+    return alignof(struct aligned_field);
+}
+
+size_t size_of_aligned_field(void) {
+    return sizeof(struct aligned_field);
+}
+
+size_t offset_of_aligned_value(void) {
+    return offsetof(struct aligned_field, value);
+}
+
+int sum_aligned(void) {
+    struct aligned_field s = { 1, 2 };
+
+    return s.tag + s.value + aligned_global;
+}
+
+int aligned_local(void) {
+    _Alignas(32) int local = 5;
+
+    return ((uintptr_t) &local % 32 == 0) + local;
+}
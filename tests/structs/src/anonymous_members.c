@@ -0,0 +1,32 @@
+// C11 anonymous members, whose fields are accessed as if they were the
+// enclosing struct's own
+struct tagged_value {
+    int tag;
+    union {
+        int i;
+        float f;
+        struct {
+            short lo;
+            short hi;
+        };
+    };
+};
+
+static int read_int(struct tagged_value v) { return v.i; }
+
+void anonymous_members(int *out) {
+    struct tagged_value v = { .tag = 1, .i = 0x00020001 };
+    int i = 0;
+
+    out[i++] = v.tag;
+    out[i++] = read_int(v);
+    out[i++] = v.lo;
+    out[i++] = v.hi;
+
+    v.hi = 7;
+    out[i++] = v.i >> 16;
+
+    v.f = 1.5f;
+    out[i++] = (int)(v.f * 2);
+    out[i++] = sizeof(struct tagged_value);
+}
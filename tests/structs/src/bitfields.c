@@ -271,3 +271,47 @@ void use_renamed_field(void) {
 
     rr.use = 0;
 }
+
+struct bit_widths {
+    unsigned int one: 1;
+    unsigned int five: 5;
+    unsigned int twelve: 12;
+    signed int three: 3;
+};
+
+size_t size_of_bit_widths(void) {
+    return sizeof(struct bit_widths);
+}
+
+// Setting every bit shows each field's declared width through its max value
+void fill_bit_widths(struct bit_widths *bw) {
+    bw->one = ~0u;
+    bw->five = ~0u;
+    bw->twelve = ~0u;
+    bw->three = 3;
+}
+
+// *** Dumping AST Record Layout
+//          0 | struct split_units
+//      0:0-2 |   unsigned int a
+//        4:- |   unsigned int
+//      4:0-2 |   unsigned int b
+//      4:3-7 |   unsigned int c
+//            | [sizeof=8, align=4]
+struct split_units {
+    unsigned a : 3;
+    // Forces `b` into the next storage unit rather than after `a`
+    unsigned : 0;
+    unsigned b : 3;
+    unsigned c : 5;
+};
+
+size_t size_of_split_units(void) {
+    return sizeof(struct split_units);
+}
+
+void write_split_units(struct split_units *s, unsigned a, unsigned b, unsigned c) {
+    s->a = a;
+    s->b = b;
+    s->c = c;
+}
@@ -0,0 +1,38 @@
+struct vec2 {
+    int x, y;
+};
+
+static int dot(struct vec2 a, struct vec2 b) {
+    return a.x * b.x + a.y * b.y;
+}
+
+static int scale(const struct vec2 *v, int factor) {
+    return (v->x + v->y) * factor;
+}
+
+static int sum(const int *xs, int n) {
+    int total = 0;
+
+    for (int i = 0; i < n; i++)
+        total += xs[i];
+
+    return total;
+}
+
+void compound_literals(unsigned buffer_size, int buffer[]) {
+    if (buffer_size < 5)
+        return;
+
+    // Compound literals passed by value, by address and decayed to a pointer
+    buffer[0] = dot((struct vec2){ .x = 2, .y = 3 }, (struct vec2){ 4, 5 });
+    buffer[1] = scale(&(struct vec2){ .y = 7 }, 3);
+    buffer[2] = sum((int[]){ 1, 2, 3, 4 }, 4);
+
+    // A compound literal is an lvalue living until the end of its block
+    struct vec2 *p = &(struct vec2){ 1, 1 };
+    p->x += 10;
+    buffer[3] = p->x + p->y;
+
+    // Compound literals used directly as operands
+    buffer[4] = (struct vec2){ 6, 8 }.y - (int[]){ 1, 2 }[1];
+}
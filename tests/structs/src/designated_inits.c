@@ -0,0 +1,45 @@
+struct point {
+    int x;
+    int y;
+    int z;
+};
+
+struct shape {
+    struct point origin;
+    int sides[4];
+    int id;
+};
+
+void designated_inits(const unsigned int buffer_size, int buffer[const]) {
+    if (buffer_size < 16) return;
+
+    // Out of order field designators, leaving `y` zeroed
+    struct point p = { .z = 3, .x = 1 };
+
+    // Index designators, including one which skips ahead and one
+    // which is followed by a positional initializer
+    int arr[5] = { [3] = 7, [1] = 5, 6 };
+
+    // Nested field and index designators, in no particular order
+    struct shape s = { .sides[2] = 9, .origin.y = 4, .id = 8, .sides[0] = 2 };
+
+    int i = 0;
+
+    buffer[i++] = p.x;
+    buffer[i++] = p.y;
+    buffer[i++] = p.z;
+
+    for (int j = 0; j < 5; j++) {
+        buffer[i++] = arr[j];
+    }
+
+    buffer[i++] = s.origin.x;
+    buffer[i++] = s.origin.y;
+    buffer[i++] = s.origin.z;
+
+    for (int j = 0; j < 4; j++) {
+        buffer[i++] = s.sides[j];
+    }
+
+    buffer[i++] = s.id;
+}
@@ -0,0 +1,19 @@
+// Whether a plain `int` bitfield is signed is implementation defined; clang
+// makes it signed, so it holds -1 just like an explicitly signed one
+typedef struct {
+    int plain: 3;
+    signed int explicit_signed: 3;
+    unsigned int explicit_unsigned: 3;
+} plain_int_bitfields;
+
+void read_plain_int_bitfields(int *out) {
+    plain_int_bitfields b = {0, 0, 0};
+
+    b.plain = -1;
+    b.explicit_signed = -1;
+    b.explicit_unsigned = 7;
+
+    out[0] = b.plain;
+    out[1] = b.explicit_signed;
+    out[2] = b.explicit_unsigned;
+}
@@ -0,0 +1,25 @@
+#include <stddef.h>
+
+#pragma pack(push, 1)
+struct wire_header {
+    char kind;
+    int length;
+    short flags;
+};
+#pragma pack(pop)
+
+struct native_header {
+    char kind;
+    int length;
+    short flags;
+};
+
+// Writes the size and member offsets of both structs to `out`
+void pragma_pack_layout(size_t out[6]) {
+    out[0] = sizeof(struct wire_header);
+    out[1] = offsetof(struct wire_header, length);
+    out[2] = offsetof(struct wire_header, flags);
+    out[3] = sizeof(struct native_header);
+    out[4] = offsetof(struct native_header, length);
+    out[5] = offsetof(struct native_header, flags);
+}
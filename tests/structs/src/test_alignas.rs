@@ -0,0 +1,50 @@
+use crate::alignas::{aligned_field, aligned_global, rust_aligned_local, rust_sum_aligned};
+use libc::{c_int, size_t};
+use std::mem::{align_of, size_of, zeroed};
+use std::ptr::addr_of;
+
+#[link(name = "test")]
+extern "C" {
+    fn alignment_of_aligned_field() -> size_t;
+    fn size_of_aligned_field() -> size_t;
+    fn offset_of_aligned_value() -> size_t;
+    fn sum_aligned() -> c_int;
+    fn aligned_local() -> c_int;
+}
+
+pub fn test_aligned_field() {
+    let c_alignment = unsafe { alignment_of_aligned_field() };
+    let c_size = unsafe { size_of_aligned_field() };
+    let c_offset = unsafe { offset_of_aligned_value() };
+
+    // The field's alignment carries over to the whole struct
+    assert_eq!(align_of::<aligned_field>(), c_alignment);
+    assert_eq!(align_of::<aligned_field>(), 16);
+    assert_eq!(size_of::<aligned_field>(), c_size);
+
+    // and padding keeps the field at its C offset
+    let s: aligned_field = unsafe { zeroed() };
+    let offset = addr_of!(s.0.value) as usize - addr_of!(s) as usize;
+
+    assert_eq!(offset, c_offset);
+    assert_eq!(offset, 16);
+
+    let src = include_str!("alignas.rs");
+
+    assert!(src.contains("#[repr(C, align(16))]\npub struct aligned_field("));
+}
+
+pub fn test_aligned_global() {
+    let expected = unsafe { sum_aligned() };
+
+    assert_eq!(unsafe { rust_sum_aligned() }, expected);
+    assert_eq!(expected, 10);
+    assert_eq!(unsafe { addr_of!(aligned_global) } as usize % 16, 0);
+}
+
+pub fn test_aligned_local() {
+    let expected = unsafe { aligned_local() };
+
+    assert_eq!(unsafe { rust_aligned_local() }, expected);
+    assert_eq!(expected, 6);
+}
@@ -0,0 +1,30 @@
+use crate::anonymous_members::rust_anonymous_members;
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn anonymous_members(_: *mut c_int);
+}
+
+const BUFFER_SIZE: usize = 7;
+
+pub fn test_anonymous_members() {
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut rust_buffer = [0; BUFFER_SIZE];
+    let expected_buffer = [1, 0x00020001, 1, 2, 7, 3, 8];
+
+    unsafe {
+        anonymous_members(buffer.as_mut_ptr());
+        rust_anonymous_members(rust_buffer.as_mut_ptr());
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+
+    // The inner fields are reached through the synthesized member names
+    let src = include_str!("anonymous_members.rs");
+
+    assert!(src.contains("pub c2rust_unnamed: C2RustUnnamed"));
+    assert!(src.contains(".c2rust_unnamed.i"));
+    assert!(src.contains(".c2rust_unnamed.c2rust_unnamed.hi"));
+}
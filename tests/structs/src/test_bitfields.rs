@@ -1,13 +1,14 @@
 //! extern_crate_c2rust_bitfields
 
 use crate::bitfields::{
-    from_csmith, mixed_bitfields, padded_bitfield, rust_compare_three_byte_date, rust_get_bf_ptr,
-    rust_init_bitfield_array, rust_init_from_csmith, rust_ma_results, rust_modify_bf_ptr,
-    rust_multiple_assignments, rust_ops_padded_bitfield, rust_ops_padded_bitfield_init,
-    rust_static_date, rust_two_eight_bits_init, rust_use_renamed_field, rust_write_three_byte_date,
+    bit_widths, from_csmith, mixed_bitfields, padded_bitfield, rust_compare_three_byte_date,
+    rust_fill_bit_widths, rust_get_bf_ptr, rust_init_bitfield_array, rust_init_from_csmith,
+    rust_ma_results, rust_modify_bf_ptr, rust_multiple_assignments, rust_ops_padded_bitfield,
+    rust_ops_padded_bitfield_init, rust_static_date, rust_two_eight_bits_init,
+    rust_use_renamed_field, rust_write_split_units, rust_write_three_byte_date, split_units,
     three_byte_date, two_eight_bits,
 };
-use std::mem::size_of;
+use std::mem::{size_of, transmute};
 
 extern "C" {
     fn size_of_three_byte_date() -> usize;
@@ -24,6 +25,10 @@ extern "C" {
     fn size_of_two_eight_bits() -> usize;
     fn multiple_assignments();
     static ma_results: [u8; 17];
+    fn size_of_bit_widths() -> usize;
+    fn fill_bit_widths(_: *mut bit_widths);
+    fn size_of_split_units() -> usize;
+    fn write_split_units(_: *mut split_units, _: u32, _: u32, _: u32);
 }
 
 pub fn test_three_byte_date() {
@@ -229,3 +234,51 @@ pub fn test_multiple_assignments() {
         assert_eq!(ma_results, rust_ma_results);
     }
 }
+
+pub fn test_bit_widths() {
+    let c_size_of = unsafe { size_of_bit_widths() };
+
+    assert_eq!(size_of::<bit_widths>(), c_size_of);
+
+    let mut bw: bit_widths = unsafe { std::mem::zeroed() };
+    let mut rust_bw: bit_widths = unsafe { std::mem::zeroed() };
+
+    unsafe {
+        fill_bit_widths(&mut bw);
+        rust_fill_bit_widths(&mut rust_bw);
+    }
+
+    // Each getter only sees as many bits as the C field was declared with
+    for bw in &[bw, rust_bw] {
+        assert_eq!(bw.one(), 1);
+        assert_eq!(bw.five(), 31);
+        assert_eq!(bw.twelve(), 4095);
+        assert_eq!(bw.three(), 3);
+    }
+}
+
+pub fn test_zero_width_boundary() {
+    let c_size_of = unsafe { size_of_split_units() };
+
+    assert_eq!(size_of::<split_units>(), c_size_of);
+    assert_eq!(c_size_of, 8);
+
+    let mut c_split: split_units = unsafe { std::mem::zeroed() };
+    let mut rust_split: split_units = unsafe { std::mem::zeroed() };
+
+    unsafe {
+        write_split_units(&mut c_split, 5, 6, 17);
+        rust_write_split_units(&mut rust_split, 5, 6, 17);
+    }
+
+    assert_eq!(c_split.a(), 5);
+    assert_eq!(c_split.b(), 6);
+    assert_eq!(c_split.c(), 17);
+
+    // The `: 0` boundary puts `b` and `c` in the second storage unit
+    let c_bytes: [u8; 8] = unsafe { transmute(c_split) };
+    let rust_bytes: [u8; 8] = unsafe { transmute(rust_split) };
+
+    assert_eq!(c_bytes, [5, 0, 0, 0, 6 | 17 << 3, 0, 0, 0]);
+    assert_eq!(rust_bytes, c_bytes);
+}
@@ -0,0 +1,23 @@
+use crate::compound_literals::rust_compound_literals;
+use libc::{c_int, c_uint};
+
+#[link(name = "test")]
+extern "C" {
+    fn compound_literals(_: c_uint, _: *mut c_int);
+}
+
+const BUFFER_SIZE: usize = 5;
+
+pub fn test_compound_literals() {
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut rust_buffer = [0; BUFFER_SIZE];
+    let expected_buffer = [23, 21, 10, 12, 6];
+
+    unsafe {
+        compound_literals(BUFFER_SIZE as u32, buffer.as_mut_ptr());
+        rust_compound_literals(BUFFER_SIZE as u32, rust_buffer.as_mut_ptr());
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+}
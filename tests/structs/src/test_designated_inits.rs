@@ -0,0 +1,23 @@
+use crate::designated_inits::rust_designated_inits;
+use libc::{c_int, c_uint};
+
+#[link(name = "test")]
+extern "C" {
+    fn designated_inits(_: c_uint, _: *mut c_int);
+}
+
+const BUFFER_SIZE: usize = 16;
+
+pub fn test_designated_inits() {
+    let mut buffer = [0; BUFFER_SIZE];
+    let mut rust_buffer = [0; BUFFER_SIZE];
+    let expected_buffer = [1, 0, 3, 0, 5, 6, 7, 0, 0, 4, 0, 2, 0, 9, 0, 8];
+
+    unsafe {
+        designated_inits(BUFFER_SIZE as u32, buffer.as_mut_ptr());
+        rust_designated_inits(BUFFER_SIZE as u32, rust_buffer.as_mut_ptr());
+    }
+
+    assert_eq!(buffer, rust_buffer);
+    assert_eq!(buffer, expected_buffer);
+}
@@ -0,0 +1,31 @@
+//! extern_crate_c2rust_bitfields
+
+use crate::plain_int_bitfields::rust_read_plain_int_bitfields;
+
+use libc::c_int;
+
+#[link(name = "test")]
+extern "C" {
+    fn read_plain_int_bitfields(_: *mut c_int);
+}
+
+pub fn test_plain_int_bitfields() {
+    let src = include_str!("plain_int_bitfields.rs");
+
+    // clang resolved the plain `int` bitfield as signed
+    assert!(src.contains(r#"#[bitfield(name = "plain", ty = "libc::c_int", bits = "0..=2")]"#));
+    assert!(src.contains(
+        r#"#[bitfield(name = "explicit_unsigned", ty = "libc::c_uint", bits = "6..=8")]"#
+    ));
+
+    let mut values = [0; 3];
+    let mut rust_values = [0; 3];
+
+    unsafe {
+        read_plain_int_bitfields(values.as_mut_ptr());
+        rust_read_plain_int_bitfields(rust_values.as_mut_ptr());
+    }
+
+    assert_eq!(values, rust_values);
+    assert_eq!(values, [-1, -1, 7]);
+}
@@ -0,0 +1,31 @@
+use crate::pragma_pack::{native_header, rust_pragma_pack_layout, wire_header};
+
+use libc::size_t;
+use std::mem::{align_of, size_of};
+
+#[link(name = "test")]
+extern "C" {
+    fn pragma_pack_layout(_: *mut size_t);
+}
+
+pub fn test_pragma_pack() {
+    let src = include_str!("pragma_pack.rs");
+
+    // Only the struct under `#pragma pack(1)` is packed
+    assert_eq!(src.matches("#[repr(C, packed)]").count(), 1);
+
+    assert_eq!(size_of::<wire_header>(), 7);
+    assert_eq!(align_of::<wire_header>(), 1);
+    assert_eq!(align_of::<native_header>(), align_of::<libc::c_int>());
+
+    let mut layout = [0; 6];
+    let mut rust_layout = [0; 6];
+
+    unsafe {
+        pragma_pack_layout(layout.as_mut_ptr());
+        rust_pragma_pack_layout(rust_layout.as_mut_ptr());
+    }
+
+    assert_eq!(layout, rust_layout);
+    assert_eq!(layout[..3], [7, 1, 5]);
+}
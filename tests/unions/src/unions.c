@@ -22,6 +22,11 @@ union __attribute__((packed)) packed_union {
     char as_chars[5];
 };
 
+union __attribute__((aligned(16))) aligned_union {
+    int as_int;
+    char as_chars[5];
+};
+
 void entry(const unsigned int buffer_size, int buffer[const])
 {
     int i = 0;
@@ -67,4 +72,7 @@ void entry(const unsigned int buffer_size, int buffer[const])
     union my_union u7;
     u7 = (union my_union)i;
     buffer[i++] = u7.as_int;
+
+    buffer[i++] = sizeof(union aligned_union);
+    buffer[i++] = _Alignof(union aligned_union);
 }